@@ -2,10 +2,12 @@
 
 use clap::{crate_authors, crate_version};
 use nispor::{
-    Iface, IfaceConf, IfaceState, IfaceType, Mptcp, NetConf, NetState,
-    NetStateFilter, NetStateIfaceFilter, NetStateRouteFilter,
-    NetStateRouteRuleFilter, NisporError, Route, RouteProtocol, RouteRule,
-    RouteScope,
+    FdbEntry, Iface, IfaceConf, IfaceState, IfaceType, IpsecInfo, Mptcp,
+    Neighbor, NetConf, NetState, NetStateFdbFilter, NetStateFilter,
+    NetStateIfaceFilter, NetStateIpsecFilter, NetStateNeighborFilter,
+    NetStateNetnsFilter, NetStateNexthopFilter, NetStateRouteFilter,
+    NetStateRouteRuleFilter, NetnsInfo, Nexthop, NisporError, Route,
+    RouteProtocol, RouteRule, RouteScope,
 };
 use serde::Serialize;
 use std::collections::HashMap;
@@ -235,11 +237,16 @@ impl fmt::Display for CliError {
 enum CliReply {
     Pass,
     Brief(Vec<CliIfaceBrief>),
-    Full(NetState),
+    Full(Box<NetState>),
     Ifaces(Vec<Iface>),
     Routes(Vec<Route>),
     RouteRules(Vec<RouteRule>),
+    Neighbors(Vec<Neighbor>),
+    Fdb(Vec<FdbEntry>),
+    Nexthops(Vec<Nexthop>),
+    Netns(Vec<NetnsInfo>),
     Mptcp(Mptcp),
+    Ipsec(IpsecInfo),
 }
 
 #[derive(PartialEq, Eq)]
@@ -272,10 +279,33 @@ macro_rules! npc_print {
                 writeln!(stdout(), "{}", $display_func(&rules).unwrap()).ok();
                 process::exit(0);
             }
+            CliReply::Neighbors(neighbors) => {
+                writeln!(stdout(), "{}", $display_func(&neighbors).unwrap())
+                    .ok();
+                process::exit(0);
+            }
+            CliReply::Fdb(entries) => {
+                writeln!(stdout(), "{}", $display_func(&entries).unwrap()).ok();
+                process::exit(0);
+            }
+            CliReply::Nexthops(nexthops) => {
+                writeln!(stdout(), "{}", $display_func(&nexthops).unwrap())
+                    .ok();
+                process::exit(0);
+            }
+            CliReply::Netns(netns_list) => {
+                writeln!(stdout(), "{}", $display_func(&netns_list).unwrap())
+                    .ok();
+                process::exit(0);
+            }
             CliReply::Mptcp(mptcp) => {
                 writeln!(stdout(), "{}", $display_func(&mptcp).unwrap()).ok();
                 process::exit(0);
             }
+            CliReply::Ipsec(ipsec) => {
+                writeln!(stdout(), "{}", $display_func(&ipsec).unwrap()).ok();
+                process::exit(0);
+            }
         }
     };
 }
@@ -453,7 +483,73 @@ fn main() {
                 ),
         )
         .subcommand(clap::Command::new("rule").about("Show route route"))
+        .subcommand(
+            clap::Command::new("neigh")
+                .about("Show neighbor(ARP/NDP) entries")
+                .arg(
+                    clap::Arg::new("dev")
+                        .short('d')
+                        .long("dev")
+                        .action(clap::ArgAction::Append)
+                        .help(
+                            "Show only neighbor entries of \
+                            the specified interface",
+                        ),
+                ),
+        )
+        .subcommand(
+            clap::Command::new("fdb")
+                .about("Show bridge/VxLAN forwarding database entries")
+                .arg(
+                    clap::Arg::new("dev")
+                        .short('d')
+                        .long("dev")
+                        .action(clap::ArgAction::Append)
+                        .help(
+                            "Show only FDB entries of \
+                            the specified interface",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("br")
+                        .short('b')
+                        .long("br")
+                        .action(clap::ArgAction::Append)
+                        .help(
+                            "Show only FDB entries of \
+                            the specified bridge",
+                        ),
+                ),
+        )
+        .subcommand(
+            clap::Command::new("nexthop")
+                .about("Show nexthop objects")
+                .arg(
+                    clap::Arg::new("dev")
+                        .short('d')
+                        .long("dev")
+                        .action(clap::ArgAction::Append)
+                        .help(
+                            "Show only nexthops using the specified interface",
+                        ),
+                ),
+        )
+        .subcommand(
+            clap::Command::new("netns")
+                .about("Show network namespaces")
+                .arg(
+                    clap::Arg::new("process")
+                        .short('p')
+                        .long("process")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Include network namespaces of all processes"),
+                ),
+        )
         .subcommand(clap::Command::new("mptcp").about("Show mptcp state"))
+        .subcommand(
+            clap::Command::new("ipsec")
+                .about("Show IPsec states and policies without keys"),
+        )
         .subcommand(
             clap::Command::new("set")
                 .about("Set network state from file")
@@ -499,9 +595,24 @@ fn main() {
     } else if let Some(m) = matches.subcommand_matches("rule") {
         output_format = parse_arg_output_format(m);
         print_result(get_rules(), output_format);
+    } else if let Some(m) = matches.subcommand_matches("neigh") {
+        output_format = parse_arg_output_format(m);
+        print_result(get_neighbors(m), output_format);
+    } else if let Some(m) = matches.subcommand_matches("fdb") {
+        output_format = parse_arg_output_format(m);
+        print_result(get_fdb(m), output_format);
+    } else if let Some(m) = matches.subcommand_matches("nexthop") {
+        output_format = parse_arg_output_format(m);
+        print_result(get_nexthops(m), output_format);
+    } else if let Some(m) = matches.subcommand_matches("netns") {
+        output_format = parse_arg_output_format(m);
+        print_result(get_netns_list(m), output_format);
     } else if let Some(m) = matches.subcommand_matches("mptcp") {
         output_format = parse_arg_output_format(m);
         print_result(get_mptcp(), output_format);
+    } else if let Some(m) = matches.subcommand_matches("ipsec") {
+        output_format = parse_arg_output_format(m);
+        print_result(get_ipsec(), output_format);
     } else {
         print_result(get_brief(&matches), output_format);
     }
@@ -604,8 +715,14 @@ fn get_ifaces(matches: &clap::ArgMatches) -> Result<CliReply, CliError> {
         iface_filter.include_ip_address = true;
         iface_filter.include_sriov_vf_info = true;
         iface_filter.include_bridge_vlan = true;
+        iface_filter.include_bridge_mdb = true;
         iface_filter.include_ethtool = true;
         iface_filter.include_mptcp = true;
+        iface_filter.include_stats = true;
+        iface_filter.include_tc = true;
+        iface_filter.include_wireguard = true;
+        iface_filter.include_macsec = true;
+        iface_filter.include_ip_sysctl = true;
         filter.iface = Some(iface_filter);
 
         let state = NetState::retrieve_with_filter(&filter)?;
@@ -677,6 +794,51 @@ fn get_rules() -> Result<CliReply, CliError> {
     Ok(CliReply::RouteRules(state.rules))
 }
 
+fn get_neighbors(matches: &clap::ArgMatches) -> Result<CliReply, CliError> {
+    let mut neighbor_filter = NetStateNeighborFilter::default();
+    if let Some(iface_name) = matches.get_one::<String>("dev") {
+        neighbor_filter.iface = Some(iface_name.to_string());
+    }
+    let mut filter = NetStateFilter::minimum();
+    filter.neighbor = Some(neighbor_filter);
+    let state = NetState::retrieve_with_filter(&filter)?;
+    Ok(CliReply::Neighbors(state.neighbors))
+}
+
+fn get_fdb(matches: &clap::ArgMatches) -> Result<CliReply, CliError> {
+    let mut fdb_filter = NetStateFdbFilter::default();
+    if let Some(iface_name) = matches.get_one::<String>("dev") {
+        fdb_filter.iface = Some(iface_name.to_string());
+    }
+    if let Some(br_name) = matches.get_one::<String>("br") {
+        fdb_filter.controller = Some(br_name.to_string());
+    }
+    let mut filter = NetStateFilter::minimum();
+    filter.fdb = Some(fdb_filter);
+    let state = NetState::retrieve_with_filter(&filter)?;
+    Ok(CliReply::Fdb(state.fdb))
+}
+
+fn get_nexthops(matches: &clap::ArgMatches) -> Result<CliReply, CliError> {
+    let mut nexthop_filter = NetStateNexthopFilter::default();
+    if let Some(iface_name) = matches.get_one::<String>("dev") {
+        nexthop_filter.iface = Some(iface_name.to_string());
+    }
+    let mut filter = NetStateFilter::minimum();
+    filter.nexthop = Some(nexthop_filter);
+    let state = NetState::retrieve_with_filter(&filter)?;
+    Ok(CliReply::Nexthops(state.nexthops))
+}
+
+fn get_netns_list(matches: &clap::ArgMatches) -> Result<CliReply, CliError> {
+    let mut netns_filter = NetStateNetnsFilter::default();
+    netns_filter.include_process = matches.get_flag("process");
+    let mut filter = NetStateFilter::minimum();
+    filter.netns_list = Some(netns_filter);
+    let state = NetState::retrieve_with_filter(&filter)?;
+    Ok(CliReply::Netns(state.netns_list))
+}
+
 fn get_mptcp() -> Result<CliReply, CliError> {
     let mut iface_filter = NetStateIfaceFilter::minimum();
     iface_filter.include_mptcp = true;
@@ -686,6 +848,13 @@ fn get_mptcp() -> Result<CliReply, CliError> {
     Ok(CliReply::Mptcp(state.mptcp.unwrap_or_default()))
 }
 
+fn get_ipsec() -> Result<CliReply, CliError> {
+    let mut filter = NetStateFilter::minimum();
+    filter.ipsec = Some(NetStateIpsecFilter::default());
+    let state = NetState::retrieve_with_filter(&filter)?;
+    Ok(CliReply::Ipsec(state.ipsec.unwrap_or_default()))
+}
+
 fn get_brief(matches: &clap::ArgMatches) -> Result<CliReply, CliError> {
     let mut filter = NetStateFilter::minimum();
     let mut iface_filter = NetStateIfaceFilter::minimum();
@@ -723,5 +892,5 @@ fn get_brief(matches: &clap::ArgMatches) -> Result<CliReply, CliError> {
 }
 
 fn get_full() -> Result<CliReply, CliError> {
-    Ok(CliReply::Full(NetState::retrieve()?))
+    Ok(CliReply::Full(Box::new(NetState::retrieve()?)))
 }
@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::panic;
+
+use crate::NetState;
+
+use super::utils::assert_value_match;
+
+const IFACE_NAME: &str = "br0";
+
+const EXPECTED_MDB: &str = r#"---
+- port: eth1
+  group: 239.1.1.1
+  state: permanent
+  flags: []
+- port: eth1
+  group: "ff0e::1"
+  state: permanent
+  flags: []"#;
+
+const EXPECTED_MULTICAST_ROUTER_PORTS: &str = r#"---
+- port: eth2
+  router_type: perm"#;
+
+#[test]
+fn test_get_br_mdb_yaml() {
+    with_br_mdb_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces[IFACE_NAME];
+        let bridge_info = iface.bridge.as_ref().unwrap();
+        let mut mdb = bridge_info.mdb.clone().unwrap();
+        mdb.sort_by_key(|e| e.group.clone());
+        assert_value_match(EXPECTED_MDB, &mdb);
+        assert_value_match(
+            EXPECTED_MULTICAST_ROUTER_PORTS,
+            &bridge_info.multicast_router_ports,
+        );
+    });
+}
+
+fn with_br_mdb_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("brmdb");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::NetState;
+use crate::{
+    error::ErrorKind, NetConf, NetState, NetStateFilter, NetStateIfaceFilter,
+};
 use pretty_assertions::assert_eq;
 
 use std::panic;
@@ -98,6 +100,63 @@ fn test_get_ethtool_pause_yaml() {
     });
 }
 
+const EXPECTED_FEC_INFO: &str = r#"---
+configured:
+  - none
+auto: false
+active: none"#;
+
+#[test]
+fn test_get_ethtool_fec_yaml() {
+    with_netdevsim_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces[IFACE_NAME0];
+        assert_value_match(
+            EXPECTED_FEC_INFO,
+            &iface.ethtool.as_ref().unwrap().fec,
+        );
+    });
+}
+
+#[test]
+fn test_get_ethtool_eee_of_netdevsim() {
+    with_netdevsim_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let ethtool_info = state.ifaces[IFACE_NAME0].ethtool.as_ref().unwrap();
+        // netdevsim has no EEE support, the EEE dump should skip it without
+        // failing other ethtool information.
+        assert_eq!(ethtool_info.eee, None);
+        assert!(ethtool_info.fec.is_some());
+        assert!(ethtool_info.channels.is_some());
+    });
+}
+
+const EXPECTED_PAUSE_STATS: &str = r#"---
+rx_pause_frames: 1
+tx_pause_frames: 2"#;
+
+#[test]
+fn test_get_ethtool_stats_of_netdevsim() {
+    with_netdevsim_iface(|| {
+        let filter = NetStateFilter {
+            iface: Some(NetStateIfaceFilter {
+                include_ethtool_stats: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let state = NetState::retrieve_with_filter(&filter).unwrap();
+        let stats = state.ifaces[IFACE_NAME0]
+            .ethtool
+            .as_ref()
+            .unwrap()
+            .statistics
+            .as_ref()
+            .unwrap();
+        assert_value_match(EXPECTED_PAUSE_STATS, &stats.pause);
+    });
+}
+
 #[test]
 fn test_get_ethtool_feature_yaml_of_loopback() {
     let mut state = NetState::retrieve().unwrap();
@@ -187,6 +246,41 @@ fn test_get_ethtool_link_mode_yaml() {
     });
 }
 
+#[test]
+fn test_set_ethtool_coalesce_and_link_mode() {
+    with_tun_iface(|| {
+        let net_conf: NetConf = serde_yaml::from_str(&format!(
+            r#"---
+ifaces:
+  - name: {IFACE_TUN_NAME}
+    ethtool:
+      coalesce:
+        rx_max_frames: 30
+      link_mode:
+        auto_negotiate: false
+        speed: 100
+        duplex: half"#
+        ))
+        .unwrap();
+        net_conf.apply().unwrap();
+        let state = NetState::retrieve().unwrap();
+        let ethtool_info =
+            state.ifaces[IFACE_TUN_NAME].ethtool.as_ref().unwrap();
+        assert_value_match(
+            r#"---
+rx_max_frames: 30"#,
+            &ethtool_info.coalesce,
+        );
+        assert_value_match(
+            r#"---
+auto_negotiate: false
+speed: 100
+duplex: half"#,
+            &ethtool_info.link_mode,
+        );
+    });
+}
+
 fn with_tun_iface<T>(test: T)
 where
     T: FnOnce() + panic::UnwindSafe,
@@ -200,3 +294,137 @@ where
     super::utils::clear_network_environment();
     assert!(result.is_ok())
 }
+
+const IFACE_VETH_NAME: &str = "veth1";
+const EXPECTED_ETHTOOL_CHANNELS: &str = r#"---
+rx: 1
+rx_max: 1
+tx: 1
+tx_max: 1"#;
+const EXPECTED_ETHTOOL_LINK_STATE: &str = r#"---
+link_detected: true"#;
+const EXPECTED_ETHTOOL_DRIVER: &str = r#"---
+driver: veth"#;
+
+#[test]
+fn test_get_ethtool_channels_yaml() {
+    with_veth_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces[IFACE_VETH_NAME];
+        assert_value_match(
+            EXPECTED_ETHTOOL_CHANNELS,
+            &iface.ethtool.as_ref().unwrap().channels,
+        );
+    });
+}
+
+#[test]
+fn test_get_ethtool_timestamping_and_link_state() {
+    with_veth_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let ethtool_info = state.ifaces[IFACE_VETH_NAME].ethtool.as_ref();
+        let ts_info = ethtool_info.unwrap().timestamping.as_ref().unwrap();
+        assert!(ts_info
+            .capabilities
+            .contains(&"software-transmit".to_string()));
+        assert_eq!(ts_info.phc_index, None);
+        assert_value_match(
+            EXPECTED_ETHTOOL_LINK_STATE,
+            &ethtool_info.unwrap().link_state,
+        );
+    });
+}
+
+#[test]
+fn test_get_ethtool_driver_yaml() {
+    with_veth_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let ethtool_info =
+            state.ifaces[IFACE_VETH_NAME].ethtool.as_ref().unwrap();
+        assert_value_match(EXPECTED_ETHTOOL_DRIVER, &ethtool_info.driver);
+        // Statistics are not included by default
+        assert_eq!(ethtool_info.statistics, None);
+    });
+}
+
+#[test]
+fn test_get_ethtool_of_filtered_iface() {
+    with_veth_iface(|| {
+        let mut filter = NetStateFilter::minimum();
+        let mut iface_filter = NetStateIfaceFilter::minimum();
+        iface_filter.iface_name = Some(IFACE_VETH_NAME.to_string());
+        iface_filter.include_ethtool = true;
+        iface_filter.include_ethtool_features = true;
+        filter.iface = Some(iface_filter);
+        let state = NetState::retrieve_with_filter(&filter).unwrap();
+        assert_eq!(state.ifaces.len(), 1);
+        let ethtool_info =
+            state.ifaces[IFACE_VETH_NAME].ethtool.as_ref().unwrap();
+        assert!(ethtool_info.features.is_some());
+        assert_eq!(ethtool_info.pause, None);
+        assert_eq!(ethtool_info.ring, None);
+        assert_value_match(EXPECTED_ETHTOOL_CHANNELS, &ethtool_info.channels);
+        assert_value_match(EXPECTED_ETHTOOL_DRIVER, &ethtool_info.driver);
+    });
+}
+
+#[test]
+fn test_set_ethtool_features() {
+    with_veth_iface(|| {
+        let net_conf: NetConf = serde_yaml::from_str(&format!(
+            r#"---
+ifaces:
+  - name: {IFACE_VETH_NAME}
+    ethtool:
+      features:
+        rx-gro: true
+        tx-generic-segmentation: false"#
+        ))
+        .unwrap();
+        net_conf.apply().unwrap();
+        let state = NetState::retrieve().unwrap();
+        let features = state.ifaces[IFACE_VETH_NAME]
+            .ethtool
+            .as_ref()
+            .unwrap()
+            .features
+            .as_ref()
+            .unwrap();
+        assert_eq!(features.changeable.get("rx-gro"), Some(&true));
+        assert_eq!(
+            features.changeable.get("tx-generic-segmentation"),
+            Some(&false)
+        );
+    });
+}
+
+#[test]
+fn test_set_ethtool_fixed_feature() {
+    with_veth_iface(|| {
+        let net_conf: NetConf = serde_yaml::from_str(&format!(
+            r#"---
+ifaces:
+  - name: {IFACE_VETH_NAME}
+    ethtool:
+      features:
+        loopback: true"#
+        ))
+        .unwrap();
+        let e = net_conf.apply().unwrap_err();
+        assert!(matches!(e.kind, ErrorKind::InvalidArgument));
+    });
+}
+
+fn with_veth_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("veth");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
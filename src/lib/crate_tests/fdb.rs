@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{FdbEntryState, NetState, NetStateFdbFilter, NetStateFilter};
+
+use std::panic;
+
+use super::utils::assert_value_match;
+
+const EXPECTED_BRIDGE_PORT_FDB: &str = r#"---
+- mac: "00:23:45:67:89:1d"
+  iface: eth1
+  controller: br0
+  vlan: 10
+  state: static
+  flags:
+    - master"#;
+
+const EXPECTED_VXLAN_FDB: &str = r#"---
+- mac: "00:00:00:00:00:00"
+  iface: vxlan0
+  state: permanent
+  flags:
+    - self
+  dst: 192.0.2.2"#;
+
+#[test]
+fn test_get_bridge_port_fdb_yaml() {
+    with_fdb_test_iface(|| {
+        let mut filter = NetStateFilter::minimum();
+        filter.fdb = Some(NetStateFdbFilter {
+            iface: Some("eth1".to_string()),
+            controller: Some("br0".to_string()),
+        });
+        let state = NetState::retrieve_with_filter(&filter).unwrap();
+        let entries: Vec<_> = state
+            .fdb
+            .into_iter()
+            .filter(|e| e.state == FdbEntryState::Static)
+            .collect();
+        assert_value_match(EXPECTED_BRIDGE_PORT_FDB, &entries);
+    });
+}
+
+#[test]
+fn test_get_vxlan_fdb_yaml() {
+    with_fdb_test_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let entries: Vec<_> = state
+            .fdb
+            .into_iter()
+            .filter(|e| e.iface == "vxlan0" && e.dst.is_some())
+            .collect();
+        assert_value_match(EXPECTED_VXLAN_FDB, &entries);
+    });
+}
+
+fn with_fdb_test_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("fdb");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
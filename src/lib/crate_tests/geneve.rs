@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetConf, NetState};
+use pretty_assertions::assert_eq;
+
+use std::panic;
+
+use super::utils::assert_value_match;
+
+const IFACE_NAME: &str = "geneve0";
+
+const EXPECTED_GENEVE_INFO: &str = r#"---
+remote: 8.8.8.8
+geneve_id: 102
+ttl: 64
+tos: 16
+dst_port: 6082
+collect_metadata: false
+udp_check_sum: true
+udp6_zero_check_sum_tx: false
+udp6_zero_check_sum_rx: false
+label: 0
+ttl_inherit: false
+df: 0
+inner_proto_inherit: false"#;
+
+#[test]
+fn test_get_geneve_iface_yaml() {
+    with_geneve_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces[IFACE_NAME];
+        assert_eq!(iface.iface_type, crate::IfaceType::Geneve);
+        assert_value_match(EXPECTED_GENEVE_INFO, &iface.geneve);
+    });
+}
+
+const GENEVE_CREATE_YML: &str = r#"---
+ifaces:
+  - name: geneve1
+    type: geneve
+    geneve:
+      geneve_id: 103
+      remote: "2001:db8::1"
+      ttl: 32"#;
+
+const GENEVE_DELETE_YML: &str = r#"---
+ifaces:
+  - name: geneve1
+    type: geneve
+    state: absent"#;
+
+const EXPECTED_CREATED_GENEVE_INFO: &str = r#"---
+remote: "2001:db8::1"
+geneve_id: 103
+ttl: 32
+dst_port: 6081
+collect_metadata: false"#;
+
+#[test]
+fn test_create_delete_geneve() {
+    let net_conf: NetConf = serde_yaml::from_str(GENEVE_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["geneve1"];
+    assert_eq!(&iface.iface_type, &crate::IfaceType::Geneve);
+    assert_value_match(EXPECTED_CREATED_GENEVE_INFO, &iface.geneve);
+
+    let net_conf: NetConf = serde_yaml::from_str(GENEVE_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("geneve1"));
+}
+
+fn with_geneve_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("geneve");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
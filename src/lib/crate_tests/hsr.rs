@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::NetState;
+use pretty_assertions::assert_eq;
+
+use std::panic;
+
+use super::utils::assert_value_match;
+
+const IFACE_NAME: &str = "hsr0";
+
+const EXPECTED_HSR_INFO: &str = r#"---
+slave1: eth1
+slave2: eth2
+supervision_addr: "01:15:4e:00:01:2d"
+protocol: hsr"#;
+
+#[test]
+fn test_get_hsr_iface_yaml() {
+    with_hsr_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces[IFACE_NAME];
+        assert_eq!(iface.iface_type, crate::IfaceType::Hsr);
+        assert_value_match(EXPECTED_HSR_INFO, &iface.hsr);
+        for slave in ["eth1", "eth2"] {
+            let slave_iface = &state.ifaces[slave];
+            assert_eq!(slave_iface.controller, Some(IFACE_NAME.to_string()));
+            assert_eq!(
+                slave_iface.controller_type,
+                Some(crate::ControllerType::Hsr)
+            );
+        }
+    });
+}
+
+fn with_hsr_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("hsr");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{NetConf, NetState};
+use crate::{
+    Ipv6AddrGenMode, NetConf, NetState, NetStateFilter, NetStateIfaceFilter,
+};
 use pretty_assertions::assert_eq;
 
 use std::panic;
@@ -198,3 +200,61 @@ fn test_ipv6_token() {
         );
     })
 }
+
+const EXPECTED_IPV4_SYSCTL: &str = r#"---
+forwarding: true
+rp_filter: 2"#;
+
+const EXPECTED_IPV6_SYSCTL: &str = r#"---
+accept_ra: 2
+hop_limit: 32
+disable_ipv6: false"#;
+
+fn with_ip_sysctl<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("ip_sysctl");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
+
+#[test]
+fn test_ip_sysctl() {
+    with_ip_sysctl(|| {
+        let filter = NetStateFilter {
+            iface: Some(NetStateIfaceFilter {
+                include_ip_sysctl: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let state = NetState::retrieve_with_filter(&filter).unwrap();
+        let iface = &state.ifaces[IFACE_NAME];
+        let ipv4_info = iface.ipv4.as_ref().unwrap();
+        assert_value_match(EXPECTED_IPV4_SYSCTL, &ipv4_info.sysctl);
+        let ipv6_info = iface.ipv6.as_ref().unwrap();
+        assert_value_match(EXPECTED_IPV6_SYSCTL, &ipv6_info.sysctl);
+        assert_eq!(ipv6_info.addr_gen_mode, Some(Ipv6AddrGenMode::None));
+        assert_eq!(ipv6_info.ra_received, Some(false));
+    })
+}
+
+#[test]
+fn test_ipv6_addr_gen_mode_without_sysctl() {
+    with_ip_sysctl(|| {
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces[IFACE_NAME];
+        assert_eq!(iface.ipv4, None);
+        let ipv6_info = iface.ipv6.as_ref().unwrap();
+        assert!(ipv6_info.addresses.is_empty());
+        assert_eq!(ipv6_info.sysctl, None);
+        assert_eq!(ipv6_info.addr_gen_mode, Some(Ipv6AddrGenMode::None));
+        assert_eq!(ipv6_info.ra_received, Some(false));
+    })
+}
@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{IfaceType, NetState};
+use pretty_assertions::assert_eq;
+
+use std::panic;
+
+use super::utils::assert_value_match;
+
+const EXPECTED_GRE_INFO: &str = r#"---
+base_iface: eth1
+local: 192.0.2.1
+remote: 192.0.2.2
+ikey: 100
+okey: 101
+icsum: false
+ocsum: false
+ttl: 64
+tos: 10
+pmtudisc: true
+encap_type: none"#;
+
+const EXPECTED_GRETAP_INFO: &str = r#"---
+local: 192.0.2.1
+remote: 192.0.2.3
+ikey: 102
+okey: 102
+icsum: true
+ocsum: true
+ttl: 0
+pmtudisc: false"#;
+
+const EXPECTED_IP6GRE_INFO: &str = r#"---
+base_iface: eth1
+local: "2001:db8::1"
+remote: "2001:db8::2"
+ttl: 32
+encap_limit: 4"#;
+
+const EXPECTED_IPIP_INFO: &str = r#"---
+base_iface: eth1
+local: 192.0.2.1
+remote: 192.0.2.4
+ttl: 32
+pmtudisc: true
+encap_type: none
+encap_sport: 0
+encap_dport: 0"#;
+
+const EXPECTED_SIT_INFO: &str = r#"---
+local: 192.0.2.1
+remote: 192.0.2.5
+ttl: 32"#;
+
+const EXPECTED_IP6TNL_INFO: &str = r#"---
+local: "2001:db8::1"
+remote: "2001:db8::3"
+ttl: 16"#;
+
+#[test]
+fn test_get_gre_iface_yaml() {
+    with_ip_tunnel_ifaces(|| {
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces["gre1"];
+        assert_eq!(iface.iface_type, IfaceType::Gre);
+        assert_value_match(EXPECTED_GRE_INFO, &iface.gre);
+        let iface = &state.ifaces["gretap1"];
+        assert_eq!(iface.iface_type, IfaceType::GreTap);
+        assert_value_match(EXPECTED_GRETAP_INFO, &iface.gre);
+        let iface = &state.ifaces["ip6gre1"];
+        assert_eq!(iface.iface_type, IfaceType::Ip6Gre);
+        assert_value_match(EXPECTED_IP6GRE_INFO, &iface.gre);
+        assert_eq!(iface.gre.as_ref().unwrap().ikey, None);
+    });
+}
+
+#[test]
+fn test_get_ip_tunnel_iface_yaml() {
+    with_ip_tunnel_ifaces(|| {
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces["ipip1"];
+        assert_eq!(iface.iface_type, IfaceType::Ipip);
+        assert_value_match(EXPECTED_IPIP_INFO, &iface.ip_tunnel);
+        let iface = &state.ifaces["sit1"];
+        assert_eq!(iface.iface_type, IfaceType::Sit);
+        assert_value_match(EXPECTED_SIT_INFO, &iface.ip_tunnel);
+        let iface = &state.ifaces["ip6tnl1"];
+        assert_eq!(iface.iface_type, IfaceType::Ip6Tnl);
+        assert_value_match(EXPECTED_IP6TNL_INFO, &iface.ip_tunnel);
+    });
+}
+
+fn with_ip_tunnel_ifaces<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("ip_tunnel");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
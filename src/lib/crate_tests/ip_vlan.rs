@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetConf, NetState};
+use pretty_assertions::assert_eq;
+
+use std::panic;
+
+use super::utils::assert_value_match;
+
+const EXPECTED_IP_VLAN_INFO: &str = r#"---
+base_iface: eth1
+mode: l3s
+flag: private"#;
+
+const EXPECTED_IP_VTAP_INFO: &str = r#"---
+base_iface: eth1
+mode: l2
+flag: vepa"#;
+
+#[test]
+fn test_get_ip_vlan_iface_yaml() {
+    with_ip_vlan_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces["ipvlan0"];
+        assert_eq!(iface.iface_type, crate::IfaceType::IpVlan);
+        assert_value_match(EXPECTED_IP_VLAN_INFO, &iface.ip_vlan);
+        let iface = &state.ifaces["ipvtap0"];
+        assert_eq!(iface.iface_type, crate::IfaceType::IpVtap);
+        assert_value_match(EXPECTED_IP_VTAP_INFO, &iface.ip_vtap);
+    });
+}
+
+const BASE_IFACE_CREATE_YML: &str = r#"---
+ifaces:
+  - name: veth1
+    type: veth
+    veth:
+      peer: veth1.ep"#;
+
+const IP_VLAN_CREATE_YML: &str = r#"---
+ifaces:
+  - name: ipvlan1
+    type: ip_vlan
+    ip_vlan:
+      base_iface: veth1
+      mode: l2
+      flag: vepa"#;
+
+const IP_VLAN_DELETE_YML: &str = r#"---
+ifaces:
+  - name: ipvlan1
+    type: ip_vlan
+    state: absent
+  - name: veth1
+    state: absent"#;
+
+const EXPECTED_CREATED_IP_VLAN_INFO: &str = r#"---
+base_iface: veth1
+mode: l2
+flag: vepa"#;
+
+#[test]
+fn test_create_delete_ip_vlan() {
+    let net_conf: NetConf =
+        serde_yaml::from_str(BASE_IFACE_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let net_conf: NetConf = serde_yaml::from_str(IP_VLAN_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["ipvlan1"];
+    assert_eq!(&iface.iface_type, &crate::IfaceType::IpVlan);
+    assert_value_match(EXPECTED_CREATED_IP_VLAN_INFO, &iface.ip_vlan);
+
+    let net_conf: NetConf = serde_yaml::from_str(IP_VLAN_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("ipvlan1"));
+}
+
+fn with_ip_vlan_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("ipvlan");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetState, NetStateFilter, NetStateIpsecFilter};
+use pretty_assertions::assert_eq;
+
+use std::panic;
+
+use super::utils::assert_value_match;
+
+const EXPECTED_XFRM_INFO: &str = r#"---
+base_iface: eth1
+if_id: 7
+collect_metadata: false"#;
+
+const EXPECTED_IPSEC_INFO: &str = r#"---
+states:
+  - address_family: ipv4
+    src: 192.0.2.1
+    dst: 192.0.2.2
+    protocol: esp
+    spi: 256
+    mode: tunnel
+    reqid: 1
+    replay_window: 32
+    if_id: 7
+    current:
+      bytes: 0
+      packets: 0
+policies:
+  - selector:
+      src: 10.0.0.0/24
+      dst: 10.1.0.0/24
+      proto: 6
+      dport: 80
+    direction: out
+    action: allow
+    priority: 10
+    if_id: 7
+    templates:
+      - address_family: ipv4
+        src: 192.0.2.1
+        dst: 192.0.2.2
+        protocol: esp
+        spi: 0
+        mode: tunnel
+        reqid: 1
+        optional: false"#;
+
+#[test]
+fn test_get_xfrm_iface_yaml() {
+    with_ipsec_env(|| {
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces["xfrm1"];
+        assert_eq!(iface.iface_type, crate::IfaceType::Xfrm);
+        assert_value_match(EXPECTED_XFRM_INFO, &iface.xfrm);
+    });
+}
+
+#[test]
+fn test_get_ipsec_yaml() {
+    with_ipsec_env(|| {
+        let mut filter = NetStateFilter::minimum();
+        filter.ipsec = Some(NetStateIpsecFilter::default());
+        let state = NetState::retrieve_with_filter(&filter).unwrap();
+        assert_value_match(EXPECTED_IPSEC_INFO, &state.ipsec);
+    });
+}
+
+fn with_ipsec_env<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("ipsec");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
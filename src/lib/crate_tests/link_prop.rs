@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    IfaceFlags, NetConf, NetState, NetStateFilter, NetStateIfaceFilter,
+};
+use pretty_assertions::assert_eq;
+
+use std::panic;
+
+use super::utils::assert_value_match;
+
+const IFACE_NAME: &str = "veth1";
+const IFACE_ALT_NAME: &str = "nispor-veth1-long-alt-name";
+
+const EXPECTED_LINK_PROP_INFO: &str = r#"---
+name: veth1
+alt_names:
+  - nispor-veth1-long-alt-name
+alias: nispor test veth
+group: 5
+txqlen: 2000"#;
+
+#[test]
+fn test_get_iface_link_prop_yaml() {
+    with_link_prop_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces[IFACE_NAME];
+        assert_value_match(EXPECTED_LINK_PROP_INFO, iface);
+        assert!(iface.qdisc.is_some());
+        assert!(iface.num_tx_queues.is_some());
+        assert!(iface.num_rx_queues.is_some());
+    });
+}
+
+#[test]
+fn test_get_iface_by_alt_name() {
+    with_link_prop_iface(|| {
+        let mut filter = NetStateFilter::minimum();
+        let mut iface_filter = NetStateIfaceFilter::minimum();
+        iface_filter.iface_name = Some(IFACE_ALT_NAME.to_string());
+        filter.iface = Some(iface_filter);
+        let state = NetState::retrieve_with_filter(&filter).unwrap();
+        assert_eq!(state.ifaces.len(), 1);
+        assert!(state.ifaces.contains_key(IFACE_NAME));
+    });
+}
+
+fn with_link_prop_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("link_prop");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
+
+const LINK_PROP_CREATE_YML: &str = r#"---
+ifaces:
+  - name: veth1
+    type: veth
+    veth:
+      peer: veth1.ep
+    mtu: 1400
+    alias: nispor test veth
+    alt_names:
+      - nispor-veth1-long-alt-name
+    group: 5
+    txqlen: 2000
+    promisc: true
+    allmulti: true
+    noarp: true
+  - name: veth1.ep
+    type: veth"#;
+
+const LINK_PROP_CHANGE_YML: &str = r#"---
+ifaces:
+  - name: veth1
+    type: veth
+    mtu: 1500
+    alias: ""
+    alt_names:
+      - nispor-veth1-alt-name-2
+    promisc: false
+    allmulti: false
+    noarp: false"#;
+
+const LINK_PROP_DELETE_YML: &str = r#"---
+ifaces:
+  - name: veth1
+    type: veth
+    state: absent"#;
+
+#[test]
+fn test_change_iface_link_prop() {
+    let net_conf: NetConf = serde_yaml::from_str(LINK_PROP_CREATE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces[IFACE_NAME];
+    assert_value_match(EXPECTED_LINK_PROP_INFO, iface);
+    assert_eq!(iface.mtu, 1400);
+    assert!(iface.flags.contains(&IfaceFlags::Promisc));
+    assert!(iface.flags.contains(&IfaceFlags::AllMulti));
+    assert!(iface.flags.contains(&IfaceFlags::NoArp));
+
+    let net_conf: NetConf = serde_yaml::from_str(LINK_PROP_CHANGE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces[IFACE_NAME];
+    assert_eq!(iface.mtu, 1500);
+    assert_eq!(iface.alias, None);
+    assert_eq!(iface.alt_names, vec!["nispor-veth1-alt-name-2".to_string()]);
+    assert_eq!(iface.group, Some(5));
+    assert_eq!(iface.txqlen, Some(2000));
+    assert!(!iface.flags.contains(&IfaceFlags::Promisc));
+    assert!(!iface.flags.contains(&IfaceFlags::AllMulti));
+    assert!(!iface.flags.contains(&IfaceFlags::NoArp));
+
+    let net_conf: NetConf = serde_yaml::from_str(LINK_PROP_DELETE_YML).unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get(IFACE_NAME));
+}
@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::NetState;
+use pretty_assertions::assert_eq;
+
+use std::panic;
+
+use super::utils::assert_value_match;
+
+const IFACE_NAME: &str = "macsec0";
+
+const EXPECTED_MACSEC_INFO: &str = r#"---
+base_iface: eth1
+sci: 00234567891a000b
+port: 11
+cipher_suite: gcm_aes128
+icv_len: 16
+encoding_sa: 0
+validate: strict
+encrypt: true
+protect: true
+include_sci: true
+end_station: false
+scb: false
+replay_protect: true
+window: 32
+offload: "off"
+tx_sa:
+  - an: 0
+    active: true
+    next_pn: 100
+rx_sc:
+  - sci: 00234567891b04d2
+    active: true
+    sa:
+      - an: 1
+        active: true
+        next_pn: 1"#;
+
+#[test]
+fn test_get_macsec_iface_yaml() {
+    with_macsec_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces[IFACE_NAME];
+        assert_eq!(iface.iface_type, crate::IfaceType::Macsec);
+        assert_value_match(EXPECTED_MACSEC_INFO, &iface.macsec);
+    });
+}
+
+fn with_macsec_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("macsec");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
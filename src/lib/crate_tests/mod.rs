@@ -9,24 +9,54 @@ mod bond;
 #[cfg(test)]
 mod bridge;
 #[cfg(test)]
+mod bridge_mdb;
+#[cfg(test)]
 mod bridge_vlan_filter;
 #[cfg(test)]
 mod dummy;
 #[cfg(test)]
 mod ethtool;
 #[cfg(test)]
+mod fdb;
+#[cfg(test)]
+mod geneve;
+#[cfg(test)]
+mod hsr;
+#[cfg(test)]
 mod ip;
 #[cfg(test)]
+mod ip_tunnel;
+#[cfg(test)]
+mod ip_vlan;
+#[cfg(test)]
+mod ipsec;
+#[cfg(test)]
+mod link_prop;
+#[cfg(test)]
 mod mac_vlan;
 #[cfg(test)]
 mod mac_vtap;
 #[cfg(test)]
+mod macsec;
+#[cfg(test)]
+mod neighbor;
+#[cfg(test)]
+mod netns;
+#[cfg(test)]
+mod nexthop;
+#[cfg(test)]
 mod route;
 #[cfg(test)]
 mod route_rule;
 #[cfg(test)]
+mod sriov;
+#[cfg(test)]
+mod stats;
+#[cfg(test)]
 mod tap;
 #[cfg(test)]
+mod tc;
+#[cfg(test)]
 mod tun;
 #[cfg(test)]
 mod veth;
@@ -36,3 +66,7 @@ mod vlan;
 mod vrf;
 #[cfg(test)]
 mod vxlan;
+#[cfg(test)]
+mod wireguard;
+#[cfg(test)]
+mod xdp;
@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    IpFamily, NeighborState, NetState, NetStateFilter, NetStateNeighborFilter,
+};
+
+use std::panic;
+
+use super::utils::assert_value_match;
+
+const TEST_IFACE: &str = "eth1";
+
+const EXPECTED_YAML_OUTPUT: &str = r#"---
+- address_family: ipv6
+  dst: "2001:db8:f::2"
+  lladdr: "00:23:45:67:89:1d"
+  iface: eth1
+  state: permanent
+  flags:
+    - router
+- address_family: ipv4
+  dst: 192.0.2.2
+  lladdr: "00:23:45:67:89:1c"
+  iface: eth1
+  state: permanent
+  flags: []
+- address_family: ipv4
+  dst: 192.0.2.3
+  iface: eth1
+  state: none
+  flags:
+    - proxy"#;
+
+const EXPECTED_IPV4_PERMANENT_YAML_OUTPUT: &str = r#"---
+- address_family: ipv4
+  dst: 192.0.2.2
+  lladdr: "00:23:45:67:89:1c"
+  iface: eth1
+  state: permanent
+  flags: []"#;
+
+#[test]
+fn test_get_neighbor_yaml() {
+    with_neighbor_test_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let neighbors: Vec<_> = state
+            .neighbors
+            .into_iter()
+            .filter(|n| {
+                n.iface.as_deref() == Some(TEST_IFACE)
+                    && (n.state == NeighborState::Permanent
+                        || n.state == NeighborState::None)
+            })
+            .collect();
+        assert_value_match(EXPECTED_YAML_OUTPUT, &neighbors);
+    });
+}
+
+#[test]
+fn test_get_neighbor_with_filter_yaml() {
+    with_neighbor_test_iface(|| {
+        let neighbor_filter = NetStateNeighborFilter {
+            iface: Some(TEST_IFACE.to_string()),
+            family: Some(IpFamily::Ipv4),
+            state: Some(NeighborState::Permanent),
+        };
+        let mut filter = NetStateFilter::minimum();
+        filter.neighbor = Some(neighbor_filter);
+        let state = NetState::retrieve_with_filter(&filter).unwrap();
+        assert_value_match(
+            EXPECTED_IPV4_PERMANENT_YAML_OUTPUT,
+            &state.neighbors,
+        );
+    });
+}
+
+fn with_neighbor_test_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("neigh");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
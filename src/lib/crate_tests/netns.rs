@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::ErrorKind, NetState, NetStateFilter, NetStateIfaceFilter,
+    NetStateNetnsFilter, NetnsSelector,
+};
+
+use std::panic;
+
+use super::utils::assert_value_match;
+
+const TEST_NETNS: &str = "nispor_test";
+
+const EXPECTED_IFACE_YAML_OUTPUT: &str = r#"---
+name: eth1.ep
+iface_type: veth
+state: up
+mac_address: "00:23:45:67:89:1c"
+link_netnsid: 0
+ipv4:
+  addresses:
+    - address: 192.0.2.2
+      prefix_len: 24"#;
+
+#[test]
+fn test_get_netns_by_name() {
+    with_netns_test_iface(|| {
+        let filter = NetStateFilter {
+            netns: Some(NetnsSelector::Name(TEST_NETNS.to_string())),
+            ..Default::default()
+        };
+        let state = NetState::retrieve_with_filter(&filter).unwrap();
+        assert!(!state.ifaces.contains_key("eth1"));
+        assert_value_match(
+            EXPECTED_IFACE_YAML_OUTPUT,
+            &state.ifaces["eth1.ep"],
+        );
+
+        // Original network namespace should be restored
+        let state = NetState::retrieve().unwrap();
+        assert!(state.ifaces.contains_key("eth1"));
+        assert!(!state.ifaces.contains_key("eth1.ep"));
+    });
+}
+
+#[test]
+fn test_get_netns_by_path() {
+    with_netns_test_iface(|| {
+        let mut filter = NetStateFilter::minimum();
+        filter.iface = Some(NetStateIfaceFilter::minimum());
+        filter.netns =
+            Some(NetnsSelector::Path(format!("/run/netns/{TEST_NETNS}")));
+        let state = NetState::retrieve_with_filter(&filter).unwrap();
+        assert!(state.ifaces.contains_key("eth1.ep"));
+    });
+}
+
+#[test]
+fn test_get_netns_by_pid() {
+    with_netns_test_iface(|| {
+        let filter = NetStateFilter {
+            netns: Some(NetnsSelector::Pid(std::process::id())),
+            ..Default::default()
+        };
+        let state = NetState::retrieve_with_filter(&filter).unwrap();
+        assert!(state.ifaces.contains_key("eth1"));
+    });
+}
+
+const EXPECTED_VETH_YAML_OUTPUT: &str = r#"---
+name: eth1
+iface_type: veth
+link_netnsid: 0
+link_netns: nispor_test
+veth:
+  peer: eth1.ep
+  peer_netns: nispor_test"#;
+
+#[test]
+fn test_get_netns_list() {
+    with_netns_test_iface(|| {
+        let mut filter = NetStateFilter::minimum();
+        filter.netns_list = Some(NetStateNetnsFilter::default());
+        let state = NetState::retrieve_with_filter(&filter).unwrap();
+        let netns = state
+            .netns_list
+            .iter()
+            .find(|n| n.name.as_deref() == Some(TEST_NETNS))
+            .unwrap();
+        assert_eq!(netns.path, format!("/run/netns/{TEST_NETNS}"));
+        assert_eq!(netns.nsid, Some(0));
+        assert!(netns.pids.is_empty());
+    });
+}
+
+#[test]
+fn test_get_netns_list_with_process() {
+    with_netns_test_iface(|| {
+        let mut filter = NetStateFilter::minimum();
+        filter.netns_list = Some(NetStateNetnsFilter {
+            include_process: true,
+        });
+        let state = NetState::retrieve_with_filter(&filter).unwrap();
+        let pid = std::process::id();
+        assert!(state
+            .netns_list
+            .iter()
+            .any(|n| n.name.is_none() && n.pids.contains(&pid)));
+    });
+}
+
+#[test]
+fn test_get_veth_peer_in_other_netns() {
+    with_netns_test_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        assert_value_match(EXPECTED_VETH_YAML_OUTPUT, &state.ifaces["eth1"]);
+    });
+}
+
+#[test]
+fn test_get_netns_not_found() {
+    let filter = NetStateFilter {
+        netns: Some(NetnsSelector::Name("not_exist_netns".to_string())),
+        ..Default::default()
+    };
+    let e = NetState::retrieve_with_filter(&filter).unwrap_err();
+    assert!(matches!(e.kind, ErrorKind::InvalidArgument));
+}
+
+fn with_netns_test_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("netns");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetState, NetStateFilter, NetStateNexthopFilter};
+
+use std::panic;
+
+use super::utils::assert_value_match;
+
+const TEST_IFACE: &str = "eth1";
+const TEST_ROUTE_TABLE_ID: u32 = 100;
+
+const EXPECTED_YAML_OUTPUT: &str = r#"---
+- id: 11
+  address_family: ipv4
+  flags:
+    - on_link
+  gateway: 192.0.2.254
+  iface: eth1
+  blackhole: false
+  fdb: false
+- id: 12
+  address_family: ipv4
+  flags:
+    - on_link
+  gateway: 192.0.2.253
+  iface: eth1
+  blackhole: false
+  fdb: false
+- id: 13
+  blackhole: true
+  fdb: false
+- id: 100
+  blackhole: false
+  fdb: false
+  group_type: multipath
+  group:
+    - id: 11
+      weight: 1
+    - id: 12
+      weight: 3"#;
+
+const EXPECTED_IFACE_FILTER_YAML_OUTPUT: &str = r#"---
+- id: 11
+  iface: eth1
+- id: 12
+  iface: eth1"#;
+
+const EXPECTED_ROUTE_YAML_OUTPUT: &str = r#"---
+- dst: 198.51.100.0/24
+  multipath:
+    - via: 192.0.2.254
+      iface: eth1
+      weight: 1
+    - via: 192.0.2.253
+      iface: eth1
+      weight: 3
+  nh_id: 100
+  nexthop:
+    id: 100
+    group_type: multipath
+- dst: 203.0.113.0/24
+  oif: eth1
+  gateway: 192.0.2.254
+  nh_id: 11
+  nexthop:
+    id: 11
+    gateway: 192.0.2.254
+    iface: eth1"#;
+
+#[test]
+fn test_get_nexthop_yaml() {
+    with_nexthop_test_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let mut nexthops: Vec<_> = state
+            .nexthops
+            .into_iter()
+            .filter(|n| [11, 12, 13, 100].contains(&n.id))
+            .collect();
+        nexthops.sort_by_key(|n| n.id);
+        assert_value_match(EXPECTED_YAML_OUTPUT, &nexthops);
+    });
+}
+
+#[test]
+fn test_get_nexthop_with_filter_yaml() {
+    with_nexthop_test_iface(|| {
+        let nexthop_filter = NetStateNexthopFilter {
+            iface: Some(TEST_IFACE.to_string()),
+        };
+        let mut filter = NetStateFilter::minimum();
+        filter.nexthop = Some(nexthop_filter);
+        let state = NetState::retrieve_with_filter(&filter).unwrap();
+        let mut nexthops = state.nexthops;
+        nexthops.sort_by_key(|n| n.id);
+        assert_value_match(EXPECTED_IFACE_FILTER_YAML_OUTPUT, &nexthops);
+    });
+}
+
+#[test]
+fn test_get_route_with_nexthop_yaml() {
+    with_nexthop_test_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let routes: Vec<_> = state
+            .routes
+            .into_iter()
+            .filter(|r| r.table == TEST_ROUTE_TABLE_ID && r.nh_id.is_some())
+            .collect();
+        assert_value_match(EXPECTED_ROUTE_YAML_OUTPUT, &routes);
+    });
+}
+
+fn with_nexthop_test_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("nexthop");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
@@ -0,0 +1,117 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{error::ErrorKind, NetConf, NetState, VfLinkState};
+use pretty_assertions::assert_eq;
+
+use std::panic;
+
+use super::utils::assert_value_match;
+
+const IFACE_NAME: &str = "sim0";
+
+const EXPECTED_VF_INFO: &str = r#"---
+id: 0
+mac: "00:23:45:67:89:30"
+vlan_id: 101
+qos: 5
+spoof_check: true
+trust: true
+link_state: enable
+min_tx_rate: 100
+max_tx_rate: 200
+query_rss: true"#;
+
+#[test]
+fn test_set_sriov_vfs() {
+    with_netdevsim_iface(|| {
+        let net_conf: NetConf = serde_yaml::from_str(&format!(
+            r#"---
+ifaces:
+  - name: {IFACE_NAME}
+    state: down
+    sriov:
+      total_vfs: 2
+      vfs:
+        - id: 0
+          mac: "00:23:45:67:89:30"
+          vlan_id: 101
+          qos: 5
+          spoof_check: true
+          trust: true
+          link_state: enable
+          min_tx_rate: 100
+          max_tx_rate: 200
+          query_rss: true
+        - id: 1
+          link_state: disable"#
+        ))
+        .unwrap();
+        net_conf.apply().unwrap();
+        let state = NetState::retrieve().unwrap();
+        let vfs = &state.ifaces[IFACE_NAME].sriov.as_ref().unwrap().vfs;
+        assert_eq!(vfs.len(), 2);
+        assert_value_match(EXPECTED_VF_INFO, &vfs[0]);
+        assert_eq!(vfs[1].link_state, VfLinkState::Disable);
+
+        let net_conf: NetConf = serde_yaml::from_str(&format!(
+            r#"---
+ifaces:
+  - name: {IFACE_NAME}
+    state: down
+    sriov:
+      total_vfs: 0"#
+        ))
+        .unwrap();
+        net_conf.apply().unwrap();
+        let state = NetState::retrieve().unwrap();
+        assert!(state.ifaces[IFACE_NAME]
+            .sriov
+            .as_ref()
+            .map(|s| s.vfs.is_empty())
+            .unwrap_or(true));
+    });
+}
+
+#[test]
+fn test_set_sriov_vf_out_of_range() {
+    with_netdevsim_iface(|| {
+        let get_vf_count = || {
+            NetState::retrieve().unwrap().ifaces[IFACE_NAME]
+                .sriov
+                .as_ref()
+                .map(|s| s.vfs.len())
+                .unwrap_or_default()
+        };
+        let vf_count = get_vf_count();
+        let net_conf: NetConf = serde_yaml::from_str(&format!(
+            r#"---
+ifaces:
+  - name: {IFACE_NAME}
+    state: down
+    sriov:
+      total_vfs: 1
+      vfs:
+        - id: 1
+          trust: true"#
+        ))
+        .unwrap();
+        let e = net_conf.apply().unwrap_err();
+        assert!(matches!(e.kind, ErrorKind::InvalidArgument));
+        // Invalid VF ID should be rejected before changing total VFs
+        assert_eq!(get_vf_count(), vf_count);
+    });
+}
+
+fn with_netdevsim_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("sim");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::panic;
+
+use pretty_assertions::assert_eq;
+
+use crate::{NetState, NetStateFilter, NetStateIfaceFilter};
+
+const IFACE_NAME: &str = "veth1";
+
+#[test]
+fn test_get_iface_stats() {
+    with_veth_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let stats = state.ifaces[IFACE_NAME].stats.as_ref().unwrap();
+        assert_eq!(stats.rx_errors, 0);
+        assert_eq!(stats.tx_errors, 0);
+    });
+}
+
+#[test]
+fn test_iface_stats_excluded_by_filter() {
+    with_veth_iface(|| {
+        let mut filter = NetStateFilter::minimum();
+        let mut iface_filter = NetStateIfaceFilter::minimum();
+        iface_filter.iface_name = Some(IFACE_NAME.to_string());
+        filter.iface = Some(iface_filter);
+        let state = NetState::retrieve_with_filter(&filter).unwrap();
+        assert_eq!(state.ifaces[IFACE_NAME].stats, None);
+    });
+}
+
+fn with_veth_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("veth");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetState, NetStateFilter, NetStateIfaceFilter};
+
+use std::panic;
+
+use super::utils::assert_value_match;
+
+const EXPECTED_HTB_YAML_OUTPUT: &str = r#"---
+root:
+  kind: htb
+  handle: "1:"
+  parent: root
+  htb:
+    default_class: 16
+child_qdiscs: []
+classes:
+  - kind: htb
+    handle: "1:10"
+    parent: root
+    htb:
+      rate: 1250000
+      ceil: 2500000
+filters:
+  - kind: u32
+    parent: "1:"
+    protocol: ip
+    priority: 1
+    handle: "0x0"
+  - kind: u32
+    parent: "1:"
+    protocol: ip
+    priority: 1
+    handle: "0x80000000"
+  - kind: u32
+    parent: "1:"
+    protocol: ip
+    priority: 1
+    handle: "0x80000800"
+    class_id: "1:10""#;
+
+const EXPECTED_TBF_CLSACT_YAML_OUTPUT: &str = r#"---
+root:
+  kind: tbf
+  handle: "2:"
+  parent: root
+  tbf:
+    rate: 125000
+    burst: 4096
+ingress:
+  kind: clsact
+  handle: "ffff:"
+  parent: ingress
+filters:
+  - kind: u32
+    parent: ingress
+    protocol: all
+    priority: 10
+    handle: "0x0"
+  - kind: u32
+    parent: ingress
+    protocol: all
+    priority: 10
+    handle: "0x80000000"
+  - kind: u32
+    parent: ingress
+    protocol: all
+    priority: 10
+    handle: "0x80000800"
+    class_id: "1:1""#;
+
+#[test]
+fn test_get_tc_htb_yaml() {
+    with_tc_test_ifaces(|| {
+        let state = retrieve_with_tc();
+        let iface = &state.ifaces["eth1"];
+        assert_value_match(EXPECTED_HTB_YAML_OUTPUT, &iface.tc);
+    });
+}
+
+#[test]
+fn test_get_tc_tbf_clsact_yaml() {
+    with_tc_test_ifaces(|| {
+        let state = retrieve_with_tc();
+        let iface = &state.ifaces["eth2"];
+        assert_value_match(EXPECTED_TBF_CLSACT_YAML_OUTPUT, &iface.tc);
+    });
+}
+
+#[test]
+fn test_tc_excluded_by_filter() {
+    with_tc_test_ifaces(|| {
+        let mut filter = NetStateFilter::minimum();
+        let mut iface_filter = NetStateIfaceFilter::minimum();
+        iface_filter.iface_name = Some("eth1".to_string());
+        filter.iface = Some(iface_filter);
+        let state = NetState::retrieve_with_filter(&filter).unwrap();
+        assert_eq!(state.ifaces["eth1"].tc, None);
+    });
+}
+
+#[test]
+fn test_tc_excluded_by_default() {
+    with_tc_test_ifaces(|| {
+        let state = NetState::retrieve().unwrap();
+        assert_eq!(state.ifaces["eth1"].tc, None);
+    });
+}
+
+fn retrieve_with_tc() -> NetState {
+    let filter = NetStateFilter {
+        iface: Some(NetStateIfaceFilter {
+            include_tc: true,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    NetState::retrieve_with_filter(&filter).unwrap()
+}
+
+fn with_tc_test_ifaces<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("tc");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
@@ -0,0 +1,155 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetConf, NetState, NetStateFilter, NetStateIfaceFilter};
+
+use std::panic;
+
+use super::utils::assert_value_match;
+
+const IFACE_NAME: &str = "wg0";
+
+const EXPECTED_WIREGUARD_INFO: &str = r#"---
+listen_port: 51820
+fwmark: 16
+peers:
+  - public_key: "xTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg="
+    endpoint: "192.0.2.1:51820"
+    allowed_ips:
+      - 198.51.100.0/24
+      - 2001:db8::/64
+    persistent_keepalive_interval: 25"#;
+
+#[test]
+fn test_get_wireguard_yaml() {
+    with_wireguard_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces[IFACE_NAME];
+        assert_eq!(iface.iface_type, crate::IfaceType::Wireguard);
+        assert_value_match(EXPECTED_WIREGUARD_INFO, &iface.wireguard);
+        let public_key = iface
+            .wireguard
+            .as_ref()
+            .unwrap()
+            .public_key
+            .as_ref()
+            .unwrap();
+        assert_eq!(public_key.len(), 44);
+    });
+}
+
+#[test]
+fn test_get_wireguard_with_iface_filter() {
+    with_wireguard_iface(|| {
+        let mut iface_filter = NetStateIfaceFilter::minimum();
+        iface_filter.iface_name = Some(IFACE_NAME.to_string());
+        iface_filter.include_wireguard = true;
+        let mut filter = NetStateFilter::minimum();
+        filter.iface = Some(iface_filter);
+        let state = NetState::retrieve_with_filter(&filter).unwrap();
+        assert_value_match(
+            EXPECTED_WIREGUARD_INFO,
+            &state.ifaces[IFACE_NAME].wireguard,
+        );
+    });
+}
+
+const TEST_PRIVATE_KEY: &str = "yAnz5TF+lXXJte14tji3zlMNq+hd2rYUIgJBgB3fBmk=";
+const TEST_PEER1_PUBLIC_KEY: &str =
+    "xTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg=";
+const TEST_PEER2_PUBLIC_KEY: &str =
+    "TrMvSoP4jYQlY6RIzBgbssQqY3vxI2Pi+y71lOWWXX0=";
+
+const EXPECTED_CREATED_WIREGUARD_INFO: &str = r#"---
+public_key: "HIgo9xNzJMWLKASShiTqIybxZ0U3wGLiUeJ1PKf8ykw="
+listen_port: 51821
+fwmark: 32
+peers:
+  - public_key: "xTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg="
+    endpoint: "192.0.2.1:51820"
+    allowed_ips:
+      - 198.51.100.0/24
+    persistent_keepalive_interval: 0"#;
+
+const EXPECTED_CHANGED_WIREGUARD_INFO: &str = r#"---
+listen_port: 51821
+fwmark: 32
+peers:
+  - public_key: "TrMvSoP4jYQlY6RIzBgbssQqY3vxI2Pi+y71lOWWXX0="
+    endpoint: "[2001:db8::1]:51820"
+    allowed_ips:
+      - 2001:db8:1::/64
+    persistent_keepalive_interval: 25"#;
+
+#[test]
+fn test_create_change_delete_wireguard() {
+    let key_file = std::env::temp_dir().join("nispor_test_wg.key");
+    std::fs::write(&key_file, TEST_PRIVATE_KEY).unwrap();
+
+    let net_conf: NetConf = serde_yaml::from_str(&format!(
+        r#"---
+ifaces:
+  - name: wg1
+    type: wireguard
+    wireguard:
+      private_key_file: {}
+      listen_port: 51821
+      fwmark: 32
+      peers:
+        - public_key: "{TEST_PEER1_PUBLIC_KEY}"
+          endpoint: "192.0.2.1:51820"
+          allowed_ips:
+            - 198.51.100.0/24"#,
+        key_file.display()
+    ))
+    .unwrap();
+    net_conf.apply().unwrap();
+    std::fs::remove_file(&key_file).ok();
+    let state = NetState::retrieve().unwrap();
+    let iface = &state.ifaces["wg1"];
+    assert_eq!(&iface.iface_type, &crate::IfaceType::Wireguard);
+    assert_value_match(EXPECTED_CREATED_WIREGUARD_INFO, &iface.wireguard);
+
+    let net_conf: NetConf = serde_yaml::from_str(&format!(
+        r#"---
+ifaces:
+  - name: wg1
+    wireguard:
+      peers:
+        - public_key: "{TEST_PEER2_PUBLIC_KEY}"
+          endpoint: "[2001:db8::1]:51820"
+          allowed_ips:
+            - 2001:db8:1::/64
+          persistent_keepalive_interval: 25"#
+    ))
+    .unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    let wg_info = state.ifaces["wg1"].wireguard.as_ref().unwrap();
+    assert_eq!(wg_info.peers.len(), 1);
+    assert_value_match(EXPECTED_CHANGED_WIREGUARD_INFO, &wg_info);
+
+    let net_conf: NetConf = serde_yaml::from_str(
+        r#"---
+ifaces:
+  - name: wg1
+    state: absent"#,
+    )
+    .unwrap();
+    net_conf.apply().unwrap();
+    let state = NetState::retrieve().unwrap();
+    assert_eq!(None, state.ifaces.get("wg1"));
+}
+
+fn with_wireguard_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("wireguard");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
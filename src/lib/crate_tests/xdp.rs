@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use netlink_packet_route::{
+    rtnl::link::nlas::Nla, LinkMessage, IFLA_XDP, IFLA_XDP_ATTACHED,
+    IFLA_XDP_DRV_PROG_ID, IFLA_XDP_HW_PROG_ID, IFLA_XDP_PROG_ID,
+    IFLA_XDP_SKB_PROG_ID,
+};
+use netlink_packet_utils::{nla::NlaBuffer, ParseableParametrized};
+use pretty_assertions::assert_eq;
+
+use std::panic;
+
+use crate::{
+    ifaces::parse_nl_msg_to_iface, NetState, XdpAttachMode, XdpInfo, XdpProgram,
+};
+
+const IFACE_NAME: &str = "veth1";
+
+const XDP_ATTACHED_NONE: u8 = 0;
+const XDP_ATTACHED_DRV: u8 = 1;
+const XDP_ATTACHED_SKB: u8 = 2;
+const XDP_ATTACHED_HW: u8 = 3;
+const XDP_ATTACHED_MULTI: u8 = 4;
+
+#[test]
+fn test_get_xdp_none_without_program() {
+    with_veth_iface(|| {
+        let state = NetState::retrieve().unwrap();
+        let iface = &state.ifaces[IFACE_NAME];
+        assert_eq!(iface.xdp, None);
+    });
+}
+
+#[test]
+fn test_parse_xdp_not_attached() {
+    let raw = gen_raw_xdp_nla(XDP_ATTACHED_NONE, &[]);
+    assert_eq!(parse_raw_xdp_nla(&raw), None);
+}
+
+#[test]
+fn test_parse_xdp_single_mode() {
+    for (attached, kind, mode) in [
+        (
+            XDP_ATTACHED_SKB,
+            IFLA_XDP_SKB_PROG_ID,
+            XdpAttachMode::Generic,
+        ),
+        (
+            XDP_ATTACHED_DRV,
+            IFLA_XDP_DRV_PROG_ID,
+            XdpAttachMode::Driver,
+        ),
+        (XDP_ATTACHED_HW, IFLA_XDP_HW_PROG_ID, XdpAttachMode::Offload),
+    ] {
+        let raw =
+            gen_raw_xdp_nla(attached, &[(IFLA_XDP_PROG_ID, 31), (kind, 31)]);
+        assert_eq!(
+            parse_raw_xdp_nla(&raw),
+            Some(XdpInfo {
+                mode,
+                programs: vec![new_xdp_prog(31, mode)],
+            })
+        );
+    }
+}
+
+#[test]
+fn test_parse_xdp_multi_mode() {
+    let raw = gen_raw_xdp_nla(
+        XDP_ATTACHED_MULTI,
+        &[
+            (IFLA_XDP_SKB_PROG_ID, 31),
+            (IFLA_XDP_DRV_PROG_ID, 32),
+            (IFLA_XDP_HW_PROG_ID, 33),
+        ],
+    );
+    assert_eq!(
+        parse_raw_xdp_nla(&raw),
+        Some(XdpInfo {
+            mode: XdpAttachMode::Multi,
+            programs: vec![
+                new_xdp_prog(31, XdpAttachMode::Generic),
+                new_xdp_prog(32, XdpAttachMode::Driver),
+                new_xdp_prog(33, XdpAttachMode::Offload),
+            ],
+        })
+    );
+}
+
+#[test]
+fn test_parse_xdp_prog_id_only() {
+    // Old kernel does not report the per-mode program ID
+    let raw = gen_raw_xdp_nla(XDP_ATTACHED_DRV, &[(IFLA_XDP_PROG_ID, 31)]);
+    assert_eq!(
+        parse_raw_xdp_nla(&raw),
+        Some(XdpInfo {
+            mode: XdpAttachMode::Driver,
+            programs: vec![new_xdp_prog(31, XdpAttachMode::Driver)],
+        })
+    );
+}
+
+fn new_xdp_prog(id: u32, mode: XdpAttachMode) -> XdpProgram {
+    XdpProgram {
+        id,
+        mode,
+        ..Default::default()
+    }
+}
+
+// Build the raw IFLA_XDP attribute as kernel does in rtnl_xdp_fill()
+fn gen_raw_xdp_nla(attached: u8, prog_ids: &[(u32, u32)]) -> Vec<u8> {
+    let mut payload = Vec::new();
+    payload.extend_from_slice(&5u16.to_ne_bytes());
+    payload.extend_from_slice(&(IFLA_XDP_ATTACHED as u16).to_ne_bytes());
+    payload.extend_from_slice(&[attached, 0, 0, 0]);
+    for (kind, prog_id) in prog_ids {
+        payload.extend_from_slice(&8u16.to_ne_bytes());
+        payload.extend_from_slice(&(*kind as u16).to_ne_bytes());
+        payload.extend_from_slice(&prog_id.to_ne_bytes());
+    }
+    let mut raw = Vec::new();
+    raw.extend_from_slice(&(payload.len() as u16 + 4).to_ne_bytes());
+    raw.extend_from_slice(&IFLA_XDP.to_ne_bytes());
+    raw.extend_from_slice(&payload);
+    raw
+}
+
+// Parse the raw IFLA_XDP attribute as part of RTM_NEWLINK message.
+// The program name, tag and type are not queried in this step.
+fn parse_raw_xdp_nla(raw: &[u8]) -> Option<XdpInfo> {
+    let nla = NlaBuffer::new_checked(raw).unwrap();
+    let mut nl_msg = LinkMessage::default();
+    nl_msg.header.index = 1;
+    nl_msg.nlas.push(Nla::IfName(IFACE_NAME.to_string()));
+    nl_msg
+        .nlas
+        .push(Nla::parse_with_param(&nla, libc::AF_UNSPEC as u16).unwrap());
+    parse_nl_msg_to_iface(&nl_msg).unwrap().unwrap().xdp
+}
+
+fn with_veth_iface<T>(test: T)
+where
+    T: FnOnce() + panic::UnwindSafe,
+{
+    super::utils::set_network_environment("veth");
+
+    let result = panic::catch_unwind(|| {
+        test();
+    });
+
+    super::utils::clear_network_environment();
+    assert!(result.is_ok())
+}
@@ -0,0 +1,175 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::os::unix::io::AsRawFd;
+
+use futures::stream::TryStreamExt;
+use netlink_packet_route::{
+    neighbour::Nla, NeighbourMessage, AF_BRIDGE, NUD_NOARP, NUD_PERMANENT,
+    NUD_REACHABLE, NUD_STALE,
+};
+use rtnetlink::new_connection;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    fdb_filter::{apply_kernel_fdb_filter, should_drop_by_filter},
+    filter::enable_kernel_strict_check,
+    mac::parse_as_mac,
+    neighbor::parse_neighbor_flags,
+    netlink::{parse_as_ipv4, parse_as_ipv6, parse_as_u32},
+    NeighborFlag, NetStateFdbFilter, NisporError,
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct FdbEntry {
+    pub mac: String,
+    pub iface: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub controller: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vlan: Option<u16>,
+    pub state: FdbEntryState,
+    pub flags: Vec<NeighborFlag>,
+    // Below are VXLAN only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dst: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dst_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vni: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub src_vni: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub via: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum FdbEntryState {
+    /* Local address of the bridge or its ports */
+    Permanent,
+    /* Statically configured by user */
+    Static,
+    /* Learned from traffic and subject to ageing */
+    Dynamic,
+    Other(u16),
+    Unknown,
+}
+
+impl From<u16> for FdbEntryState {
+    fn from(d: u16) -> Self {
+        // VxLAN use NUD_NOARP | NUD_PERMANENT for permanent entries.
+        if d & NUD_PERMANENT > 0 {
+            Self::Permanent
+        } else if d & NUD_NOARP > 0 {
+            Self::Static
+        } else if d & (NUD_REACHABLE | NUD_STALE) > 0 {
+            Self::Dynamic
+        } else {
+            Self::Other(d)
+        }
+    }
+}
+
+impl Default for FdbEntryState {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+pub(crate) async fn get_fdb_entries(
+    iface_name2index: &HashMap<String, u32>,
+    filter: &NetStateFdbFilter,
+) -> Result<Vec<FdbEntry>, NisporError> {
+    let mut entries = Vec::new();
+    let (mut connection, handle, _) = new_connection()?;
+
+    let mut ifindex_to_name = HashMap::new();
+    for (name, index) in iface_name2index.iter() {
+        ifindex_to_name.insert(format!("{index}"), name.to_string());
+    }
+
+    enable_kernel_strict_check(connection.socket_mut().as_raw_fd())?;
+    tokio::spawn(connection);
+
+    let mut fdb_handle = handle.neighbours().get();
+    fdb_handle.message_mut().header.family = AF_BRIDGE as u8;
+    apply_kernel_fdb_filter(&mut fdb_handle, filter, iface_name2index)?;
+
+    let mut fdb_msgs = fdb_handle.execute();
+    while let Some(fdb_msg) = fdb_msgs.try_next().await? {
+        let entry = get_fdb_entry(fdb_msg, &ifindex_to_name)?;
+        if should_drop_by_filter(&entry, filter) {
+            continue;
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+fn get_fdb_entry(
+    fdb_msg: NeighbourMessage,
+    ifindex_to_name: &HashMap<String, String>,
+) -> Result<FdbEntry, NisporError> {
+    let mut entry = FdbEntry::default();
+    let header = &fdb_msg.header;
+    entry.iface = ifindex_to_iface_name(header.ifindex, ifindex_to_name);
+    entry.state = header.state.into();
+    entry.flags = parse_neighbor_flags(header.flags);
+    for nla in &fdb_msg.nlas {
+        match nla {
+            Nla::LinkLocalAddress(d) => {
+                entry.mac = parse_as_mac(d.len(), d)?;
+            }
+            Nla::Master(d) => {
+                entry.controller = Some(ifindex_to_iface_name(
+                    parse_as_u32(d)?,
+                    ifindex_to_name,
+                ));
+            }
+            Nla::Vlan(d) => {
+                entry.vlan = Some(*d);
+            }
+            Nla::Destination(d) => {
+                entry.dst = Some(match d.len() {
+                    4 => parse_as_ipv4(d)?.to_string(),
+                    16 => parse_as_ipv6(d)?.to_string(),
+                    _ => format!("{d:?}"),
+                });
+            }
+            Nla::Port(d) => {
+                entry.dst_port = Some(u16::from_be_bytes([
+                    *d.first().ok_or_else(|| {
+                        NisporError::bug("wrong index at NDA_PORT".into())
+                    })?,
+                    *d.get(1).ok_or_else(|| {
+                        NisporError::bug("wrong index at NDA_PORT".into())
+                    })?,
+                ]));
+            }
+            Nla::Vni(d) => {
+                entry.vni = Some(*d);
+            }
+            Nla::SourceVni(d) => {
+                entry.src_vni = Some(*d);
+            }
+            Nla::IfIndex(d) => {
+                entry.via = Some(ifindex_to_iface_name(*d, ifindex_to_name));
+            }
+            Nla::CacheInfo(_) => (),
+            _ => log::debug!("Unknown NLA message for FDB entry {:?}", nla),
+        }
+    }
+    Ok(entry)
+}
+
+fn ifindex_to_iface_name(
+    index: u32,
+    ifindex_to_name: &HashMap<String, String>,
+) -> String {
+    match ifindex_to_name.get(&format!("{index}")) {
+        Some(iface_name) => iface_name.clone(),
+        None => format!("{index}"),
+    }
+}
@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::neighbour::Nla;
+use rtnetlink::NeighbourGetRequest;
+
+use crate::{FdbEntry, NisporError};
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct NetStateFdbFilter {
+    /// Returned FDB entries will only contain entries of specified interface.
+    pub iface: Option<String>,
+    /// Returned FDB entries will only contain entries of ports attached to
+    /// specified bridge.
+    pub controller: Option<String>,
+}
+
+pub(crate) fn apply_kernel_fdb_filter(
+    handle: &mut NeighbourGetRequest,
+    filter: &NetStateFdbFilter,
+    iface_name2index: &HashMap<String, u32>,
+) -> Result<(), NisporError> {
+    if let Some(iface) = filter.iface.as_ref() {
+        let index = get_iface_index(iface, iface_name2index)?;
+        handle.message_mut().nlas.push(Nla::IfIndex(index));
+    }
+    if let Some(controller) = filter.controller.as_ref() {
+        let index = get_iface_index(controller, iface_name2index)?;
+        handle
+            .message_mut()
+            .nlas
+            .push(Nla::Master(index.to_ne_bytes().to_vec()));
+    }
+    Ok(())
+}
+
+fn get_iface_index(
+    iface_name: &str,
+    iface_name2index: &HashMap<String, u32>,
+) -> Result<u32, NisporError> {
+    match iface_name2index.get(iface_name) {
+        Some(index) => Ok(*index),
+        None => {
+            let e = NisporError::invalid_argument(format!(
+                "Interface {iface_name} not found"
+            ));
+            log::error!("{}", e);
+            Err(e)
+        }
+    }
+}
+
+pub(crate) fn should_drop_by_filter(
+    entry: &FdbEntry,
+    filter: &NetStateFdbFilter,
+) -> bool {
+    // Kernel will ignore the filter when strict check is not supported,
+    // hence we double check in user space.
+    (filter.iface.is_some() && filter.iface.as_ref() != Some(&entry.iface))
+        || (filter.controller.is_some()
+            && filter.controller != entry.controller)
+}
@@ -3,8 +3,9 @@
 use std::os::unix::io::RawFd;
 
 use crate::{
-    NetStateIfaceFilter, NetStateRouteFilter, NetStateRouteRuleFilter,
-    NisporError,
+    NetStateFdbFilter, NetStateIfaceFilter, NetStateIpsecFilter,
+    NetStateNeighborFilter, NetStateNetnsFilter, NetStateNexthopFilter,
+    NetStateRouteFilter, NetStateRouteRuleFilter, NetnsSelector, NisporError,
 };
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -26,6 +27,40 @@ pub struct NetStateFilter {
     /// NetStateRouteRuleFilter::default() -- full route rule infromation.
     /// When set to None, no route rule will be included in result.
     pub route_rule: Option<NetStateRouteRuleFilter>,
+
+    /// Filter applied to neighbor(ARP/NDP) entries, default is
+    /// NetStateNeighborFilter::default() -- all neighbor entries.
+    /// When set to None, no neighbor will be included in result.
+    pub neighbor: Option<NetStateNeighborFilter>,
+
+    /// Filter applied to bridge/VxLAN forwarding database entries, default
+    /// is NetStateFdbFilter::default() -- all FDB entries.
+    /// When set to None, no FDB entry will be included in result.
+    pub fdb: Option<NetStateFdbFilter>,
+
+    /// Filter applied to nexthop objects, default is
+    /// NetStateNexthopFilter::default() -- all nexthop objects.
+    /// When set to None, no nexthop will be included in result.
+    pub nexthop: Option<NetStateNexthopFilter>,
+
+    /// Network namespace to query, default is None -- the network namespace
+    /// of current thread.
+    /// Switching network namespace requires `CAP_SYS_ADMIN` capability.
+    /// The SR-IOV VF interface names are not available as they are read from
+    /// sysfs of current mount namespace.
+    pub netns: Option<NetnsSelector>,
+
+    /// Filter applied to the network namespace list, default is
+    /// NetStateNetnsFilter::default() -- all named network namespaces.
+    /// When set to None, no network namespace will be included in result.
+    pub netns_list: Option<NetStateNetnsFilter>,
+
+    /// Filter applied to IPsec states and policies, default is
+    /// NetStateIpsecFilter::default() -- all states and policies.
+    /// When set to None, no IPsec information will be included in result.
+    /// Querying IPsec requires `CAP_NET_ADMIN` capability. The `ipsec` of
+    /// result will be None when permission denied or query failed.
+    pub ipsec: Option<NetStateIpsecFilter>,
 }
 
 impl Default for NetStateFilter {
@@ -34,6 +69,12 @@ impl Default for NetStateFilter {
             iface: Some(NetStateIfaceFilter::default()),
             route: Some(NetStateRouteFilter::default()),
             route_rule: Some(NetStateRouteRuleFilter::default()),
+            neighbor: Some(NetStateNeighborFilter::default()),
+            fdb: Some(NetStateFdbFilter::default()),
+            nexthop: Some(NetStateNexthopFilter::default()),
+            netns: None,
+            netns_list: Some(NetStateNetnsFilter::default()),
+            ipsec: Some(NetStateIpsecFilter::default()),
         }
     }
 }
@@ -47,6 +88,12 @@ impl NetStateFilter {
             iface: None,
             route: None,
             route_rule: None,
+            neighbor: None,
+            fdb: None,
+            nexthop: None,
+            netns: None,
+            netns_list: None,
+            ipsec: None,
         }
     }
 }
@@ -2,7 +2,8 @@
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[non_exhaustive]
-/// The `NetStateIfaceFilter::default()` will retrieve full information.
+/// The `NetStateIfaceFilter::default()` will retrieve full information except
+/// the expensive ones marked as `By default: false`.
 /// To query only interested part, please use `NetStateIfaceFilter::minimum()`
 /// along with additional property set to `Some()`.
 pub struct NetStateIfaceFilter {
@@ -14,10 +15,48 @@ pub struct NetStateIfaceFilter {
     pub include_sriov_vf_info: bool,
     /// Include Bridge VLAN information or not. By default: true
     pub include_bridge_vlan: bool,
+    /// Include Bridge multicast database(MDB) and multicast router ports
+    /// information or not. By default: true
+    pub include_bridge_mdb: bool,
     /// Include ethool information or not. By default: true
     pub include_ethtool: bool,
     /// Include mptcp information or not. By default: true
     pub include_mptcp: bool,
+    /// Include interface statistics or not. By default: true
+    pub include_stats: bool,
+    /// Include traffic control(qdisc, class and filter) information or not.
+    /// Querying them requires multiple netlink dumps per interface.
+    /// By default: false
+    pub include_tc: bool,
+    /// Include WireGuard information or not, requires `CAP_NET_ADMIN`.
+    /// By default: true
+    pub include_wireguard: bool,
+    /// Include MACsec secure channels and associations or not.
+    /// By default: true
+    pub include_macsec: bool,
+    /// Include per-interface IPv4/IPv6 sysctl values or not.
+    /// By default: false
+    pub include_ip_sysctl: bool,
+    /// Include ethtool standard statistics(IEEE 802.3, RMON and pause
+    /// counters) or not. Querying them is expensive as driver might need
+    /// to read hardware registers. Ignored when `include_ethtool` is false.
+    /// By default: false
+    pub include_ethtool_stats: bool,
+    /// Include ethtool pause information or not. Ignored when
+    /// `include_ethtool` is false. By default: true
+    pub include_ethtool_pause: bool,
+    /// Include ethtool features information or not. Ignored when
+    /// `include_ethtool` is false. By default: true
+    pub include_ethtool_features: bool,
+    /// Include ethtool coalesce information or not. Ignored when
+    /// `include_ethtool` is false. By default: true
+    pub include_ethtool_coalesce: bool,
+    /// Include ethtool ring information or not. Ignored when
+    /// `include_ethtool` is false. By default: true
+    pub include_ethtool_ring: bool,
+    /// Include ethtool link mode information or not. Ignored when
+    /// `include_ethtool` is false. By default: true
+    pub include_ethtool_link_mode: bool,
 }
 
 impl Default for NetStateIfaceFilter {
@@ -27,8 +66,20 @@ impl Default for NetStateIfaceFilter {
             include_ip_address: true,
             include_sriov_vf_info: true,
             include_bridge_vlan: true,
+            include_bridge_mdb: true,
             include_ethtool: true,
             include_mptcp: true,
+            include_stats: true,
+            include_tc: false,
+            include_wireguard: true,
+            include_macsec: true,
+            include_ip_sysctl: false,
+            include_ethtool_stats: false,
+            include_ethtool_pause: true,
+            include_ethtool_features: true,
+            include_ethtool_coalesce: true,
+            include_ethtool_ring: true,
+            include_ethtool_link_mode: true,
         }
     }
 }
@@ -40,8 +91,20 @@ impl NetStateIfaceFilter {
             include_ip_address: false,
             include_sriov_vf_info: false,
             include_bridge_vlan: false,
+            include_bridge_mdb: false,
             include_ethtool: false,
             include_mptcp: false,
+            include_stats: false,
+            include_tc: false,
+            include_wireguard: false,
+            include_macsec: false,
+            include_ip_sysctl: false,
+            include_ethtool_stats: false,
+            include_ethtool_pause: false,
+            include_ethtool_features: false,
+            include_ethtool_coalesce: false,
+            include_ethtool_ring: false,
+            include_ethtool_link_mode: false,
         }
     }
 }
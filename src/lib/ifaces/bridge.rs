@@ -10,7 +10,7 @@ use crate::{
     netlink::{
         parse_af_spec_bridge_info, parse_bridge_info, parse_bridge_port_info,
     },
-    ControllerType, Iface, NisporError,
+    ControllerType, Iface, NisporError, RouteProtocol,
 };
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
@@ -156,6 +156,10 @@ pub struct BridgeInfo {
     pub multicast_igmp_version: Option<u8>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub multicast_mld_version: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mdb: Option<Vec<BridgeMdbEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_router_ports: Option<Vec<BridgeMulticastRouterPort>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
@@ -386,6 +390,138 @@ pub(crate) fn parse_bridge_vlan_info(
     Ok(())
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct BridgeMdbEntry {
+    pub port: String,
+    pub group: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vid: Option<u16>,
+    pub state: BridgeMdbEntryState,
+    pub flags: Vec<BridgeMdbEntryFlag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timer: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_mode: Option<BridgeMdbFilterMode>,
+    // Only IGMPv3/MLDv2 has source list
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sources: Option<Vec<BridgeMdbSource>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<RouteProtocol>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum BridgeMdbEntryState {
+    Temporary,
+    Permanent,
+    Other(u8),
+    Unknown,
+}
+
+impl Default for BridgeMdbEntryState {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+const MDB_TEMPORARY: u8 = 0;
+const MDB_PERMANENT: u8 = 1;
+
+impl From<u8> for BridgeMdbEntryState {
+    fn from(d: u8) -> Self {
+        match d {
+            MDB_TEMPORARY => Self::Temporary,
+            MDB_PERMANENT => Self::Permanent,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum BridgeMdbEntryFlag {
+    Offload,
+    FastLeave,
+    StarExclude,
+    Blocked,
+}
+
+const MDB_FLAGS_OFFLOAD: u8 = 1 << 0;
+const MDB_FLAGS_FAST_LEAVE: u8 = 1 << 1;
+const MDB_FLAGS_STAR_EXCL: u8 = 1 << 2;
+const MDB_FLAGS_BLOCKED: u8 = 1 << 3;
+
+pub(crate) fn parse_bridge_mdb_entry_flags(d: u8) -> Vec<BridgeMdbEntryFlag> {
+    let mut ret = Vec::new();
+    for (flag, value) in [
+        (MDB_FLAGS_OFFLOAD, BridgeMdbEntryFlag::Offload),
+        (MDB_FLAGS_FAST_LEAVE, BridgeMdbEntryFlag::FastLeave),
+        (MDB_FLAGS_STAR_EXCL, BridgeMdbEntryFlag::StarExclude),
+        (MDB_FLAGS_BLOCKED, BridgeMdbEntryFlag::Blocked),
+    ] {
+        if d & flag > 0 {
+            ret.push(value);
+        }
+    }
+    ret
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum BridgeMdbFilterMode {
+    Exclude,
+    Include,
+    Other(u8),
+    Unknown,
+}
+
+impl Default for BridgeMdbFilterMode {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+const MCAST_EXCLUDE: u8 = 0;
+const MCAST_INCLUDE: u8 = 1;
+
+impl From<u8> for BridgeMdbFilterMode {
+    fn from(d: u8) -> Self {
+        match d {
+            MCAST_EXCLUDE => Self::Exclude,
+            MCAST_INCLUDE => Self::Include,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct BridgeMdbSource {
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timer: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct BridgeMulticastRouterPort {
+    pub port: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vid: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub router_type: Option<BridgePortMulticastRouterType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timer: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inet_timer: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inet6_timer: Option<u32>,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
 #[non_exhaustive]
 pub struct BridgeConf {}
@@ -3,14 +3,94 @@
 use std::collections::{hash_map::Entry, BTreeMap, HashMap};
 
 use ethtool::{
-    EthtoolAttr, EthtoolCoalesceAttr, EthtoolFeatureAttr, EthtoolFeatureBit,
-    EthtoolHandle, EthtoolHeader, EthtoolLinkModeAttr, EthtoolPauseAttr,
-    EthtoolRingAttr,
+    EthtoolAttr, EthtoolChannelAttr, EthtoolCoalesceAttr, EthtoolError,
+    EthtoolFeatureAttr, EthtoolFeatureBit, EthtoolFecAttr, EthtoolHandle,
+    EthtoolHeader, EthtoolLinkModeAttr, EthtoolPauseAttr, EthtoolRingAttr,
 };
-use futures::stream::TryStreamExt;
+use futures::stream::{TryStream, TryStreamExt};
+use netlink_packet_utils::nla::{DefaultNla, NlasIterator, NLA_F_NESTED};
 use serde::{Deserialize, Serialize, Serializer};
 
-use crate::NisporError;
+use crate::{
+    ifaces::ethtool_stats::{dump_ethtool_stats, EthtoolStatsInfo},
+    netlink::{
+        emit_nlas, genl_dump, genl_family_id, genl_get, parse_as_string,
+        parse_as_u32, parse_as_u8,
+    },
+    NetStateIfaceFilter, NisporError,
+};
+
+// The ethtool crate does not support EEE, link state and private flags yet,
+// they are queried by raw generic netlink messages. The timestamping is also
+// queried this way as the ethtool crate ignores the bits of bitset without
+// mask.
+pub(crate) const ETHTOOL_GENL_NAME: &str = "ethtool";
+pub(crate) const ETHTOOL_GENL_VERSION: u8 = 1;
+
+const ETHTOOL_MSG_LINKSTATE_GET: u8 = 6;
+const ETHTOOL_MSG_PRIVFLAGS_GET: u8 = 13;
+const ETHTOOL_MSG_EEE_GET: u8 = 23;
+const ETHTOOL_MSG_TSINFO_GET: u8 = 25;
+
+// ETHTOOL_A_*_HEADER of all messages except ETHTOOL_MSG_STATS_GET
+pub(crate) const ETHTOOL_A_HEADER: u16 = 1;
+
+pub(crate) const ETHTOOL_A_HEADER_DEV_NAME: u16 = 2;
+pub(crate) const ETHTOOL_A_HEADER_FLAGS: u16 = 3;
+
+pub(crate) const ETHTOOL_A_BITSET_NOMASK: u16 = 1;
+pub(crate) const ETHTOOL_A_BITSET_SIZE: u16 = 2;
+pub(crate) const ETHTOOL_A_BITSET_BITS: u16 = 3;
+pub(crate) const ETHTOOL_A_BITSET_VALUE: u16 = 4;
+pub(crate) const ETHTOOL_A_BITSET_BITS_BIT: u16 = 1;
+pub(crate) const ETHTOOL_A_BITSET_BIT_NAME: u16 = 2;
+pub(crate) const ETHTOOL_A_BITSET_BIT_VALUE: u16 = 3;
+
+const ETHTOOL_A_LINKSTATE_LINK: u16 = 2;
+const ETHTOOL_A_LINKSTATE_SQI: u16 = 3;
+const ETHTOOL_A_LINKSTATE_SQI_MAX: u16 = 4;
+const ETHTOOL_A_LINKSTATE_EXT_STATE: u16 = 5;
+const ETHTOOL_A_LINKSTATE_EXT_SUBSTATE: u16 = 6;
+const ETHTOOL_A_LINKSTATE_EXT_DOWN_CNT: u16 = 7;
+
+const ETHTOOL_A_PRIVFLAGS_FLAGS: u16 = 2;
+
+const ETHTOOL_A_TSINFO_TIMESTAMPING: u16 = 2;
+const ETHTOOL_A_TSINFO_TX_TYPES: u16 = 3;
+const ETHTOOL_A_TSINFO_RX_FILTERS: u16 = 4;
+const ETHTOOL_A_TSINFO_PHC_INDEX: u16 = 5;
+
+const ETHTOOL_A_EEE_MODES_OURS: u16 = 2;
+const ETHTOOL_A_EEE_MODES_PEER: u16 = 3;
+const ETHTOOL_A_EEE_ACTIVE: u16 = 4;
+const ETHTOOL_A_EEE_ENABLED: u16 = 5;
+const ETHTOOL_A_EEE_TX_LPI_ENABLED: u16 = 6;
+const ETHTOOL_A_EEE_TX_LPI_TIMER: u16 = 7;
+
+const SIOCETHTOOL: u64 = 0x8946;
+const ETHTOOL_GDRVINFO: u32 = 0x3;
+const IFNAMSIZ: usize = 16;
+const ETHTOOL_BUSINFO_LEN: usize = 32;
+
+// Offsets in `struct ethtool_drvinfo`
+const DRVINFO_DRIVER_OFFSET: usize = 4;
+const DRVINFO_VERSION_OFFSET: usize = 36;
+const DRVINFO_FW_VERSION_OFFSET: usize = 68;
+const DRVINFO_BUS_INFO_OFFSET: usize = 100;
+const DRVINFO_EROM_VERSION_OFFSET: usize = 132;
+const DRVINFO_LEN: usize = 196;
+
+const ETHTOOL_LINK_EXT_STATE_AUTONEG: u8 = 0;
+const ETHTOOL_LINK_EXT_STATE_LINK_TRAINING_FAILURE: u8 = 1;
+const ETHTOOL_LINK_EXT_STATE_LINK_LOGICAL_MISMATCH: u8 = 2;
+const ETHTOOL_LINK_EXT_STATE_BAD_SIGNAL_INTEGRITY: u8 = 3;
+const ETHTOOL_LINK_EXT_STATE_NO_CABLE: u8 = 4;
+const ETHTOOL_LINK_EXT_STATE_CABLE_ISSUE: u8 = 5;
+const ETHTOOL_LINK_EXT_STATE_EEPROM_ISSUE: u8 = 6;
+const ETHTOOL_LINK_EXT_STATE_CALIBRATION_FAILURE: u8 = 7;
+const ETHTOOL_LINK_EXT_STATE_POWER_BUDGET_EXCEEDED: u8 = 8;
+const ETHTOOL_LINK_EXT_STATE_OVERHEAT: u8 = 9;
+const ETHTOOL_LINK_EXT_STATE_MODULE: u8 = 10;
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
 #[non_exhaustive]
@@ -144,6 +224,186 @@ pub struct EthtoolLinkModeInfo {
     pub lanes: Option<u32>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolChannelInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rx: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rx_max: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_max: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub other: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub other_max: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub combined: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub combined_max: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolEeeInfo {
+    /// Link modes supporting EEE
+    pub supported: Vec<String>,
+    /// Link modes advertised with EEE
+    pub advertised: Vec<String>,
+    /// Link modes advertised with EEE by link partner
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peer: Option<Vec<String>>,
+    pub active: bool,
+    pub enabled: bool,
+    pub tx_lpi_enabled: bool,
+    /// Time in microseconds
+    pub tx_lpi_timer: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum EthtoolFecMode {
+    None,
+    Rs,
+    Baser,
+    Llrs,
+    Other(u32),
+    Unknown,
+}
+
+impl From<&ethtool::EthtoolFecMode> for EthtoolFecMode {
+    fn from(v: &ethtool::EthtoolFecMode) -> Self {
+        match v {
+            ethtool::EthtoolFecMode::None => Self::None,
+            ethtool::EthtoolFecMode::Rs => Self::Rs,
+            ethtool::EthtoolFecMode::Baser => Self::Baser,
+            ethtool::EthtoolFecMode::Llrs => Self::Llrs,
+            ethtool::EthtoolFecMode::Other(d, _) => Self::Other(*d),
+        }
+    }
+}
+
+impl Default for EthtoolFecMode {
+    fn default() -> Self {
+        EthtoolFecMode::Unknown
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolFecInfo {
+    pub configured: Vec<EthtoolFecMode>,
+    /// Driver chooses FEC mode based on SFP module, this is not
+    /// auto-negotiation
+    pub auto: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<EthtoolFecMode>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolTimestampingInfo {
+    /// SO_TIMESTAMPING flags supported
+    pub capabilities: Vec<String>,
+    pub tx_types: Vec<String>,
+    pub rx_filters: Vec<String>,
+    /// Index of PTP hardware clock, `/dev/ptp<phc_index>`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phc_index: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum EthtoolLinkExtState {
+    Autoneg,
+    LinkTrainingFailure,
+    LinkLogicalMismatch,
+    BadSignalIntegrity,
+    NoCable,
+    CableIssue,
+    EepromIssue,
+    CalibrationFailure,
+    PowerBudgetExceeded,
+    Overheat,
+    Module,
+    Other(u8),
+    Unknown,
+}
+
+impl From<u8> for EthtoolLinkExtState {
+    fn from(d: u8) -> Self {
+        match d {
+            ETHTOOL_LINK_EXT_STATE_AUTONEG => Self::Autoneg,
+            ETHTOOL_LINK_EXT_STATE_LINK_TRAINING_FAILURE => {
+                Self::LinkTrainingFailure
+            }
+            ETHTOOL_LINK_EXT_STATE_LINK_LOGICAL_MISMATCH => {
+                Self::LinkLogicalMismatch
+            }
+            ETHTOOL_LINK_EXT_STATE_BAD_SIGNAL_INTEGRITY => {
+                Self::BadSignalIntegrity
+            }
+            ETHTOOL_LINK_EXT_STATE_NO_CABLE => Self::NoCable,
+            ETHTOOL_LINK_EXT_STATE_CABLE_ISSUE => Self::CableIssue,
+            ETHTOOL_LINK_EXT_STATE_EEPROM_ISSUE => Self::EepromIssue,
+            ETHTOOL_LINK_EXT_STATE_CALIBRATION_FAILURE => {
+                Self::CalibrationFailure
+            }
+            ETHTOOL_LINK_EXT_STATE_POWER_BUDGET_EXCEEDED => {
+                Self::PowerBudgetExceeded
+            }
+            ETHTOOL_LINK_EXT_STATE_OVERHEAT => Self::Overheat,
+            ETHTOOL_LINK_EXT_STATE_MODULE => Self::Module,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl Default for EthtoolLinkExtState {
+    fn default() -> Self {
+        EthtoolLinkExtState::Unknown
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolLinkStateInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_detected: Option<bool>,
+    /// Signal Quality Index
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sqi: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sqi_max: Option<u32>,
+    /// Extended reason of link down
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext_state: Option<EthtoolLinkExtState>,
+    /// The meaning of sub-state depends on `ext_state`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext_substate: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext_down_count: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolDriverInfo {
+    pub driver: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub firmware_version: Option<String>,
+    /// Bus address, e.g. PCI address `0000:00:1f.6`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bus_info: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expansion_rom_version: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
 #[non_exhaustive]
 pub struct EthtoolInfo {
@@ -157,6 +417,25 @@ pub struct EthtoolInfo {
     pub ring: Option<EthtoolRingInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub link_mode: Option<EthtoolLinkModeInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channels: Option<EthtoolChannelInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eee: Option<EthtoolEeeInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fec: Option<EthtoolFecInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamping: Option<EthtoolTimestampingInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_state: Option<EthtoolLinkStateInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<EthtoolDriverInfo>,
+    /// Driver specific flags
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_flags: Option<BTreeMap<String, bool>>,
+    /// Only available when `NetStateIfaceFilter.include_ethtool_stats` is
+    /// true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statistics: Option<EthtoolStatsInfo>,
 }
 
 fn ordered_map<S>(
@@ -170,7 +449,11 @@ where
     ordered.serialize(serializer)
 }
 
+// When `iface_name` is defined, only query specified interface using GET
+// requests instead of dumping all interfaces.
 pub(crate) async fn get_ethtool_infos(
+    filter: &NetStateIfaceFilter,
+    iface_name: Option<&str>,
 ) -> Result<HashMap<String, EthtoolInfo>, NisporError> {
     let mut infos: HashMap<String, EthtoolInfo> = HashMap::new();
 
@@ -178,88 +461,90 @@ pub(crate) async fn get_ethtool_infos(
 
     tokio::spawn(connection);
 
-    let mut pause_infos = dump_pause_infos(&mut handle).await?;
-    let mut feature_infos = dump_feature_infos(&mut handle).await?;
-    let mut coalesce_infos = dump_coalesce_infos(&mut handle).await?;
-    let mut ring_infos = dump_ring_infos(&mut handle).await?;
-    let mut link_mode_infos = dump_link_mode_infos(&mut handle).await?;
-
-    for (iface_name, pause_info) in pause_infos.drain() {
-        infos.insert(
-            iface_name,
-            EthtoolInfo {
-                pause: Some(pause_info),
-                ..Default::default()
-            },
-        );
+    if filter.include_ethtool_pause {
+        for (name, pause_info) in
+            dump_pause_infos(&mut handle, iface_name).await?
+        {
+            infos.entry(name).or_default().pause = Some(pause_info);
+        }
     }
-
-    for (iface_name, feature_info) in feature_infos.drain() {
-        match infos.get_mut(&iface_name) {
-            Some(ref mut info) => {
-                info.features = Some(feature_info);
-            }
-            None => {
-                infos.insert(
-                    iface_name,
-                    EthtoolInfo {
-                        features: Some(feature_info),
-                        ..Default::default()
-                    },
-                );
-            }
-        };
+    if filter.include_ethtool_features {
+        for (name, feature_info) in
+            dump_feature_infos(&mut handle, iface_name).await?
+        {
+            infos.entry(name).or_default().features = Some(feature_info);
+        }
     }
-
-    for (iface_name, coalesce_info) in coalesce_infos.drain() {
-        match infos.get_mut(&iface_name) {
-            Some(ref mut info) => {
-                info.coalesce = Some(coalesce_info);
-            }
-            None => {
-                infos.insert(
-                    iface_name,
-                    EthtoolInfo {
-                        coalesce: Some(coalesce_info),
-                        ..Default::default()
-                    },
-                );
-            }
-        };
+    if filter.include_ethtool_coalesce {
+        for (name, coalesce_info) in
+            dump_coalesce_infos(&mut handle, iface_name).await?
+        {
+            infos.entry(name).or_default().coalesce = Some(coalesce_info);
+        }
+    }
+    if filter.include_ethtool_ring {
+        for (name, ring_info) in
+            dump_ring_infos(&mut handle, iface_name).await?
+        {
+            infos.entry(name).or_default().ring = Some(ring_info);
+        }
+    }
+    if filter.include_ethtool_link_mode {
+        for (name, link_mode_info) in
+            dump_link_mode_infos(&mut handle, iface_name).await?
+        {
+            infos.entry(name).or_default().link_mode = Some(link_mode_info);
+        }
+    }
+    for (name, channel_info) in
+        dump_channel_infos(&mut handle, iface_name).await?
+    {
+        infos.entry(name).or_default().channels = Some(channel_info);
+    }
+    for (name, fec_info) in dump_fec_infos(&mut handle, iface_name).await? {
+        infos.entry(name).or_default().fec = Some(fec_info);
     }
 
-    for (iface_name, ring_info) in ring_infos.drain() {
-        match infos.get_mut(&iface_name) {
-            Some(ref mut info) => {
-                info.ring = Some(ring_info);
-            }
-            None => {
-                infos.insert(
-                    iface_name,
-                    EthtoolInfo {
-                        ring: Some(ring_info),
-                        ..Default::default()
-                    },
-                );
+    if let Some(family_id) = genl_family_id(ETHTOOL_GENL_NAME).await? {
+        for (name, eee_info) in dump_eee_infos(family_id, iface_name).await? {
+            infos.entry(name).or_default().eee = Some(eee_info);
+        }
+        for (name, timestamping_info) in
+            dump_timestamping_infos(family_id, iface_name).await?
+        {
+            infos.entry(name).or_default().timestamping =
+                Some(timestamping_info);
+        }
+        for (name, link_state_info) in
+            dump_link_state_infos(family_id, iface_name).await?
+        {
+            infos.entry(name).or_default().link_state = Some(link_state_info);
+        }
+        for (name, private_flags) in
+            dump_private_flags(family_id, iface_name).await?
+        {
+            infos.entry(name).or_default().private_flags = Some(private_flags);
+        }
+        if filter.include_ethtool_stats {
+            for (name, stats_info) in
+                dump_ethtool_stats(family_id, iface_name).await?
+            {
+                infos.entry(name).or_default().statistics = Some(stats_info);
             }
-        };
+        }
     }
 
-    for (iface_name, link_mode_info) in link_mode_infos.drain() {
-        match infos.get_mut(&iface_name) {
-            Some(ref mut info) => {
-                info.link_mode = Some(link_mode_info);
-            }
-            None => {
-                infos.insert(
+    for (iface_name, info) in infos.iter_mut() {
+        match get_driver_info(iface_name) {
+            Ok(d) => info.driver = d,
+            Err(e) => {
+                log::info!(
+                    "Failed to query driver information of {}: {}",
                     iface_name,
-                    EthtoolInfo {
-                        link_mode: Some(link_mode_info),
-                        ..Default::default()
-                    },
+                    e
                 );
             }
-        };
+        }
     }
 
     Ok(infos)
@@ -267,10 +552,11 @@ pub(crate) async fn get_ethtool_infos(
 
 async fn dump_pause_infos(
     handle: &mut EthtoolHandle,
+    iface_name: Option<&str>,
 ) -> Result<HashMap<String, EthtoolPauseInfo>, NisporError> {
     let mut infos = HashMap::new();
-    let mut pause_handle = handle.pause().get(None).execute().await;
-    while let Some(genl_msg) = pause_handle.try_next().await? {
+    let mut pause_handle = handle.pause().get(iface_name).execute().await;
+    while let Some(genl_msg) = next_ethtool_reply(&mut pause_handle).await? {
         let ethtool_msg = genl_msg.payload;
         let mut iface_name = None;
         let mut pause_info = EthtoolPauseInfo::default();
@@ -297,10 +583,11 @@ async fn dump_pause_infos(
 
 async fn dump_feature_infos(
     handle: &mut EthtoolHandle,
+    iface_name: Option<&str>,
 ) -> Result<HashMap<String, EthtoolFeatureInfo>, NisporError> {
     let mut infos = HashMap::new();
-    let mut feature_handle = handle.feature().get(None).execute().await;
-    while let Some(genl_msg) = feature_handle.try_next().await? {
+    let mut feature_handle = handle.feature().get(iface_name).execute().await;
+    while let Some(genl_msg) = next_ethtool_reply(&mut feature_handle).await? {
         let ethtool_msg = genl_msg.payload;
         let mut iface_name = None;
         let mut fixed_features: HashMap<String, bool> = HashMap::new();
@@ -398,10 +685,11 @@ async fn dump_feature_infos(
 
 async fn dump_coalesce_infos(
     handle: &mut EthtoolHandle,
+    iface_name: Option<&str>,
 ) -> Result<HashMap<String, EthtoolCoalesceInfo>, NisporError> {
     let mut infos = HashMap::new();
-    let mut coalesce_handle = handle.coalesce().get(None).execute().await;
-    while let Some(genl_msg) = coalesce_handle.try_next().await? {
+    let mut coalesce_handle = handle.coalesce().get(iface_name).execute().await;
+    while let Some(genl_msg) = next_ethtool_reply(&mut coalesce_handle).await? {
         let ethtool_msg = genl_msg.payload;
         let mut iface_name = None;
         let mut coalesce_info = EthtoolCoalesceInfo::default();
@@ -493,10 +781,11 @@ async fn dump_coalesce_infos(
 
 async fn dump_ring_infos(
     handle: &mut EthtoolHandle,
+    iface_name: Option<&str>,
 ) -> Result<HashMap<String, EthtoolRingInfo>, NisporError> {
     let mut infos = HashMap::new();
-    let mut ring_handle = handle.ring().get(None).execute().await;
-    while let Some(genl_msg) = ring_handle.try_next().await? {
+    let mut ring_handle = handle.ring().get(iface_name).execute().await;
+    while let Some(genl_msg) = next_ethtool_reply(&mut ring_handle).await? {
         let ethtool_msg = genl_msg.payload;
         let mut iface_name = None;
         let mut ring_info = EthtoolRingInfo::default();
@@ -536,10 +825,13 @@ async fn dump_ring_infos(
 
 async fn dump_link_mode_infos(
     handle: &mut EthtoolHandle,
+    iface_name: Option<&str>,
 ) -> Result<HashMap<String, EthtoolLinkModeInfo>, NisporError> {
     let mut infos = HashMap::new();
-    let mut link_mode_handle = handle.link_mode().get(None).execute().await;
-    while let Some(genl_msg) = link_mode_handle.try_next().await? {
+    let mut link_mode_handle =
+        handle.link_mode().get(iface_name).execute().await;
+    while let Some(genl_msg) = next_ethtool_reply(&mut link_mode_handle).await?
+    {
         let ethtool_msg = genl_msg.payload;
         let mut iface_name = None;
         let mut link_mode_info = EthtoolLinkModeInfo::default();
@@ -589,6 +881,523 @@ async fn dump_link_mode_infos(
     Ok(infos)
 }
 
+async fn dump_channel_infos(
+    handle: &mut EthtoolHandle,
+    iface_name: Option<&str>,
+) -> Result<HashMap<String, EthtoolChannelInfo>, NisporError> {
+    let mut infos = HashMap::new();
+    let mut channel_handle = handle.channel().get(iface_name).execute().await;
+    while let Some(genl_msg) = next_ethtool_reply(&mut channel_handle).await? {
+        let ethtool_msg = genl_msg.payload;
+        let mut iface_name = None;
+        let mut channel_info = EthtoolChannelInfo::default();
+        for nla in &ethtool_msg.nlas {
+            if let EthtoolAttr::Channel(nla) = nla {
+                match nla {
+                    EthtoolChannelAttr::Header(hdrs) => {
+                        iface_name = get_iface_name_from_header(hdrs)
+                    }
+                    EthtoolChannelAttr::RxMax(d) => {
+                        channel_info.rx_max = Some(*d)
+                    }
+                    EthtoolChannelAttr::TxMax(d) => {
+                        channel_info.tx_max = Some(*d)
+                    }
+                    EthtoolChannelAttr::OtherMax(d) => {
+                        channel_info.other_max = Some(*d)
+                    }
+                    EthtoolChannelAttr::CombinedMax(d) => {
+                        channel_info.combined_max = Some(*d)
+                    }
+                    EthtoolChannelAttr::RxCount(d) => {
+                        channel_info.rx = Some(*d)
+                    }
+                    EthtoolChannelAttr::TxCount(d) => {
+                        channel_info.tx = Some(*d)
+                    }
+                    EthtoolChannelAttr::OtherCount(d) => {
+                        channel_info.other = Some(*d)
+                    }
+                    EthtoolChannelAttr::CombinedCount(d) => {
+                        channel_info.combined = Some(*d)
+                    }
+                    _ => log::warn!(
+                        "WARN: Unsupported EthtoolChannelAttr {:?}",
+                        nla
+                    ),
+                }
+            }
+        }
+        if let Some(i) = iface_name {
+            infos.insert(i, channel_info);
+        }
+    }
+    Ok(infos)
+}
+
+async fn dump_fec_infos(
+    handle: &mut EthtoolHandle,
+    iface_name: Option<&str>,
+) -> Result<HashMap<String, EthtoolFecInfo>, NisporError> {
+    let mut infos = HashMap::new();
+    let mut fec_handle = handle.fec().get(iface_name).execute().await;
+    while let Some(genl_msg) = next_ethtool_reply(&mut fec_handle).await? {
+        let ethtool_msg = genl_msg.payload;
+        let mut iface_name = None;
+        let mut fec_info = EthtoolFecInfo::default();
+        for nla in &ethtool_msg.nlas {
+            if let EthtoolAttr::Fec(nla) = nla {
+                match nla {
+                    EthtoolFecAttr::Header(hdrs) => {
+                        iface_name = get_iface_name_from_header(hdrs)
+                    }
+                    EthtoolFecAttr::Modes(modes) => {
+                        fec_info.configured =
+                            modes.iter().map(EthtoolFecMode::from).collect()
+                    }
+                    EthtoolFecAttr::Auto(d) => fec_info.auto = *d,
+                    EthtoolFecAttr::Active(d) => {
+                        fec_info.active = Some(d.into())
+                    }
+                    // FEC statistics are not included
+                    EthtoolFecAttr::Stats(_) => (),
+                    _ => {
+                        log::warn!("WARN: Unsupported EthtoolFecAttr {:?}", nla)
+                    }
+                }
+            }
+        }
+        if let Some(i) = iface_name {
+            infos.insert(i, fec_info);
+        }
+    }
+    Ok(infos)
+}
+
+async fn dump_timestamping_infos(
+    family_id: u16,
+    iface_name: Option<&str>,
+) -> Result<HashMap<String, EthtoolTimestampingInfo>, NisporError> {
+    let mut infos = HashMap::new();
+    for reply in ethtool_genl_query(
+        family_id,
+        ETHTOOL_MSG_TSINFO_GET,
+        ETHTOOL_A_HEADER,
+        &[],
+        &[],
+        iface_name,
+    )
+    .await?
+    {
+        let mut iface_name = None;
+        let mut ts_info = EthtoolTimestampingInfo::default();
+        for nla in NlasIterator::new(reply.as_slice()) {
+            let nla = nla?;
+            match nla.kind() {
+                ETHTOOL_A_HEADER => {
+                    iface_name = parse_genl_header_iface_name(nla.value())?;
+                }
+                ETHTOOL_A_TSINFO_TIMESTAMPING => {
+                    ts_info.capabilities = parse_bitset_names(nla.value())?;
+                }
+                ETHTOOL_A_TSINFO_TX_TYPES => {
+                    ts_info.tx_types = parse_bitset_names(nla.value())?;
+                }
+                ETHTOOL_A_TSINFO_RX_FILTERS => {
+                    ts_info.rx_filters = parse_bitset_names(nla.value())?;
+                }
+                ETHTOOL_A_TSINFO_PHC_INDEX => {
+                    ts_info.phc_index = Some(parse_as_u32(nla.value())?);
+                }
+                _ => log::debug!(
+                    "Unsupported ethtool tsinfo attribute {} {:?}",
+                    nla.kind(),
+                    nla.value()
+                ),
+            }
+        }
+        if let Some(i) = iface_name {
+            infos.insert(i, ts_info);
+        }
+    }
+    Ok(infos)
+}
+
+async fn dump_eee_infos(
+    family_id: u16,
+    iface_name: Option<&str>,
+) -> Result<HashMap<String, EthtoolEeeInfo>, NisporError> {
+    let mut infos = HashMap::new();
+    for reply in ethtool_genl_query(
+        family_id,
+        ETHTOOL_MSG_EEE_GET,
+        ETHTOOL_A_HEADER,
+        &[],
+        &[],
+        iface_name,
+    )
+    .await?
+    {
+        let mut iface_name = None;
+        let mut eee_info = EthtoolEeeInfo::default();
+        for nla in NlasIterator::new(reply.as_slice()) {
+            let nla = nla?;
+            match nla.kind() {
+                ETHTOOL_A_HEADER => {
+                    iface_name = parse_genl_header_iface_name(nla.value())?;
+                }
+                ETHTOOL_A_EEE_MODES_OURS => {
+                    // The value is advertised modes, the mask is supported
+                    // modes.
+                    for (name, value) in parse_bitset_bits(nla.value())? {
+                        if value {
+                            eee_info.advertised.push(name.clone());
+                        }
+                        eee_info.supported.push(name);
+                    }
+                }
+                ETHTOOL_A_EEE_MODES_PEER => {
+                    eee_info.peer =
+                        Some(
+                            parse_bitset_bits(nla.value())?
+                                .into_iter()
+                                .filter_map(|(name, value)| {
+                                    if value {
+                                        Some(name)
+                                    } else {
+                                        None
+                                    }
+                                })
+                                .collect(),
+                        );
+                }
+                ETHTOOL_A_EEE_ACTIVE => {
+                    eee_info.active = parse_as_u8(nla.value())? > 0;
+                }
+                ETHTOOL_A_EEE_ENABLED => {
+                    eee_info.enabled = parse_as_u8(nla.value())? > 0;
+                }
+                ETHTOOL_A_EEE_TX_LPI_ENABLED => {
+                    eee_info.tx_lpi_enabled = parse_as_u8(nla.value())? > 0;
+                }
+                ETHTOOL_A_EEE_TX_LPI_TIMER => {
+                    eee_info.tx_lpi_timer = parse_as_u32(nla.value())?;
+                }
+                _ => log::warn!(
+                    "WARN: Unsupported ethtool EEE attribute {} {:?}",
+                    nla.kind(),
+                    nla.value()
+                ),
+            }
+        }
+        if let Some(i) = iface_name {
+            infos.insert(i, eee_info);
+        }
+    }
+    Ok(infos)
+}
+
+async fn dump_link_state_infos(
+    family_id: u16,
+    iface_name: Option<&str>,
+) -> Result<HashMap<String, EthtoolLinkStateInfo>, NisporError> {
+    let mut infos = HashMap::new();
+    for reply in ethtool_genl_query(
+        family_id,
+        ETHTOOL_MSG_LINKSTATE_GET,
+        ETHTOOL_A_HEADER,
+        &[],
+        &[],
+        iface_name,
+    )
+    .await?
+    {
+        let mut iface_name = None;
+        let mut link_state_info = EthtoolLinkStateInfo::default();
+        for nla in NlasIterator::new(reply.as_slice()) {
+            let nla = nla?;
+            match nla.kind() {
+                ETHTOOL_A_HEADER => {
+                    iface_name = parse_genl_header_iface_name(nla.value())?;
+                }
+                ETHTOOL_A_LINKSTATE_LINK => {
+                    link_state_info.link_detected =
+                        Some(parse_as_u8(nla.value())? > 0);
+                }
+                ETHTOOL_A_LINKSTATE_SQI => {
+                    link_state_info.sqi = Some(parse_as_u32(nla.value())?);
+                }
+                ETHTOOL_A_LINKSTATE_SQI_MAX => {
+                    link_state_info.sqi_max = Some(parse_as_u32(nla.value())?);
+                }
+                ETHTOOL_A_LINKSTATE_EXT_STATE => {
+                    link_state_info.ext_state =
+                        Some(parse_as_u8(nla.value())?.into());
+                }
+                ETHTOOL_A_LINKSTATE_EXT_SUBSTATE => {
+                    link_state_info.ext_substate =
+                        Some(parse_as_u8(nla.value())?);
+                }
+                ETHTOOL_A_LINKSTATE_EXT_DOWN_CNT => {
+                    link_state_info.ext_down_count =
+                        Some(parse_as_u32(nla.value())?);
+                }
+                _ => log::warn!(
+                    "WARN: Unsupported ethtool link state attribute {} {:?}",
+                    nla.kind(),
+                    nla.value()
+                ),
+            }
+        }
+        // Kernel replies with header only for interface without any link
+        // state information.
+        if let Some(i) = iface_name {
+            if link_state_info != EthtoolLinkStateInfo::default() {
+                infos.insert(i, link_state_info);
+            }
+        }
+    }
+    Ok(infos)
+}
+
+async fn dump_private_flags(
+    family_id: u16,
+    iface_name: Option<&str>,
+) -> Result<HashMap<String, BTreeMap<String, bool>>, NisporError> {
+    let mut infos = HashMap::new();
+    for reply in ethtool_genl_query(
+        family_id,
+        ETHTOOL_MSG_PRIVFLAGS_GET,
+        ETHTOOL_A_HEADER,
+        &[],
+        &[],
+        iface_name,
+    )
+    .await?
+    {
+        let mut iface_name = None;
+        let mut flags = BTreeMap::new();
+        for nla in NlasIterator::new(reply.as_slice()) {
+            let nla = nla?;
+            match nla.kind() {
+                ETHTOOL_A_HEADER => {
+                    iface_name = parse_genl_header_iface_name(nla.value())?;
+                }
+                ETHTOOL_A_PRIVFLAGS_FLAGS => {
+                    flags.extend(parse_bitset_bits(nla.value())?);
+                }
+                _ => log::debug!(
+                    "Unsupported ethtool private flags attribute {} {:?}",
+                    nla.kind(),
+                    nla.value()
+                ),
+            }
+        }
+        if let Some(i) = iface_name {
+            if !flags.is_empty() {
+                infos.insert(i, flags);
+            }
+        }
+    }
+    Ok(infos)
+}
+
+// The ethtool GET request on specified interface fails with EOPNOTSUPP when
+// the driver does not support it, treat it as no information.
+async fn next_ethtool_reply<S, T>(
+    stream: &mut S,
+) -> Result<Option<T>, NisporError>
+where
+    S: TryStream<Ok = T, Error = EthtoolError> + Unpin,
+{
+    match stream.try_next().await {
+        Ok(reply) => Ok(reply),
+        Err(EthtoolError::NetlinkError(e))
+            if e.raw_code().abs() == libc::EOPNOTSUPP =>
+        {
+            Ok(None)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+// The `header_nlas` will be placed into the `header_kind` nested attribute
+// along with the interface name.
+pub(crate) fn gen_ethtool_header_nla(
+    header_kind: u16,
+    header_nlas: &[DefaultNla],
+    iface_name: Option<&str>,
+) -> DefaultNla {
+    let mut all_header_nlas = Vec::new();
+    if let Some(iface_name) = iface_name {
+        let mut iface_name_raw = iface_name.as_bytes().to_vec();
+        iface_name_raw.push(0);
+        all_header_nlas
+            .push(DefaultNla::new(ETHTOOL_A_HEADER_DEV_NAME, iface_name_raw));
+    }
+    all_header_nlas.extend_from_slice(header_nlas);
+    DefaultNla::new(header_kind | NLA_F_NESTED, emit_nlas(&all_header_nlas))
+}
+
+// Dump all interfaces when `iface_name` is None, otherwise send GET request
+// for specified interface only.
+pub(crate) async fn ethtool_genl_query(
+    family_id: u16,
+    cmd: u8,
+    header_kind: u16,
+    header_nlas: &[DefaultNla],
+    nlas: &[DefaultNla],
+    iface_name: Option<&str>,
+) -> Result<Vec<Vec<u8>>, NisporError> {
+    let mut all_nlas = Vec::new();
+    if iface_name.is_some() || !header_nlas.is_empty() {
+        all_nlas.push(gen_ethtool_header_nla(
+            header_kind,
+            header_nlas,
+            iface_name,
+        ));
+    }
+    all_nlas.extend_from_slice(nlas);
+
+    if iface_name.is_some() {
+        Ok(genl_get(family_id, cmd, ETHTOOL_GENL_VERSION, &all_nlas)
+            .await?
+            .into_iter()
+            .collect())
+    } else {
+        genl_dump(family_id, cmd, ETHTOOL_GENL_VERSION, &all_nlas).await
+    }
+}
+
+// ETHTOOL_MSG_DRVINFO_GET is not available in ethtool netlink interface,
+// use ETHTOOL_GDRVINFO ioctl instead.
+// Return None if interface does not support it.
+fn get_driver_info(
+    iface_name: &str,
+) -> Result<Option<EthtoolDriverInfo>, NisporError> {
+    if iface_name.len() >= IFNAMSIZ {
+        return Err(NisporError::invalid_argument(format!(
+            "Interface name {iface_name} is too long"
+        )));
+    }
+    let mut drvinfo = [0u8; DRVINFO_LEN];
+    drvinfo[..4].copy_from_slice(&ETHTOOL_GDRVINFO.to_ne_bytes());
+    // struct ifreq: ifr_name followed by ifr_data pointer
+    let mut ifreq = [0u8; IFNAMSIZ + 24];
+    ifreq[..iface_name.len()].copy_from_slice(iface_name.as_bytes());
+    ifreq[IFNAMSIZ..IFNAMSIZ + std::mem::size_of::<usize>()]
+        .copy_from_slice(&(drvinfo.as_mut_ptr() as usize).to_ne_bytes());
+
+    let fd = unsafe {
+        libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0)
+    };
+    if fd < 0 {
+        return Err(NisporError::bug(format!(
+            "Failed to create socket for ETHTOOL_GDRVINFO: {}",
+            std::io::Error::last_os_error()
+        )));
+    }
+    let rc = unsafe { libc::ioctl(fd, SIOCETHTOOL, ifreq.as_mut_ptr()) };
+    let error = std::io::Error::last_os_error();
+    unsafe {
+        libc::close(fd);
+    }
+    if rc < 0 {
+        return match error.raw_os_error() {
+            Some(libc::EOPNOTSUPP) | Some(libc::ENODEV) => Ok(None),
+            _ => Err(NisporError::bug(format!(
+                "ETHTOOL_GDRVINFO failed on {iface_name}: {error}"
+            ))),
+        };
+    }
+
+    let driver = parse_drvinfo_string(&drvinfo, DRVINFO_DRIVER_OFFSET);
+    if driver.is_empty() {
+        return Ok(None);
+    }
+    let get_string = |offset| {
+        let value = parse_drvinfo_string(&drvinfo, offset);
+        if value.is_empty() {
+            None
+        } else {
+            Some(value)
+        }
+    };
+    Ok(Some(EthtoolDriverInfo {
+        driver,
+        version: get_string(DRVINFO_VERSION_OFFSET),
+        firmware_version: get_string(DRVINFO_FW_VERSION_OFFSET),
+        bus_info: get_string(DRVINFO_BUS_INFO_OFFSET),
+        expansion_rom_version: get_string(DRVINFO_EROM_VERSION_OFFSET),
+    }))
+}
+
+// All strings in `struct ethtool_drvinfo` are 32 bytes
+fn parse_drvinfo_string(data: &[u8], offset: usize) -> String {
+    let data = &data[offset..offset + ETHTOOL_BUSINFO_LEN];
+    let end = data.iter().position(|c| *c == 0).unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..end]).to_string()
+}
+
+pub(crate) fn parse_genl_header_iface_name(
+    data: &[u8],
+) -> Result<Option<String>, NisporError> {
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        if nla.kind() == ETHTOOL_A_HEADER_DEV_NAME {
+            return Ok(Some(parse_as_string(nla.value())));
+        }
+    }
+    Ok(None)
+}
+
+// Return the names of bits set in verbose(non-compact) bitset
+fn parse_bitset_names(data: &[u8]) -> Result<Vec<String>, NisporError> {
+    Ok(parse_bitset_bits(data)?
+        .into_iter()
+        .filter_map(|(name, value)| if value { Some(name) } else { None })
+        .collect())
+}
+
+// Parse the verbose(non-compact) bitset into name and value of each bit.
+// Bitset without mask only lists the bits set, and ETHTOOL_A_BITSET_BIT_VALUE
+// is omitted for them.
+fn parse_bitset_bits(data: &[u8]) -> Result<Vec<(String, bool)>, NisporError> {
+    let mut ret = Vec::new();
+    let mut no_mask = false;
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        if nla.kind() == ETHTOOL_A_BITSET_NOMASK {
+            no_mask = true;
+        }
+        if nla.kind() != ETHTOOL_A_BITSET_BITS {
+            continue;
+        }
+        for bit_nla in NlasIterator::new(nla.value()) {
+            let bit_nla = bit_nla?;
+            if bit_nla.kind() != ETHTOOL_A_BITSET_BITS_BIT {
+                continue;
+            }
+            let mut name = None;
+            let mut value = no_mask;
+            for attr in NlasIterator::new(bit_nla.value()) {
+                let attr = attr?;
+                match attr.kind() {
+                    ETHTOOL_A_BITSET_BIT_NAME => {
+                        name = Some(parse_as_string(attr.value()));
+                    }
+                    ETHTOOL_A_BITSET_BIT_VALUE => value = true,
+                    _ => (),
+                }
+            }
+            if let Some(name) = name {
+                ret.push((name, value));
+            }
+        }
+    }
+    Ok(ret)
+}
+
 fn get_iface_name_from_header(hdrs: &[EthtoolHeader]) -> Option<String> {
     for hdr in hdrs {
         if let EthtoolHeader::DevName(iface_name) = hdr {
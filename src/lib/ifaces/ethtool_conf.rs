@@ -0,0 +1,542 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use netlink_packet_utils::nla::{DefaultNla, NLA_F_NESTED};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ifaces::ethtool::{
+        gen_ethtool_header_nla, ETHTOOL_A_BITSET_BITS,
+        ETHTOOL_A_BITSET_BITS_BIT, ETHTOOL_A_BITSET_BIT_NAME,
+        ETHTOOL_A_BITSET_BIT_VALUE, ETHTOOL_A_BITSET_NOMASK, ETHTOOL_A_HEADER,
+        ETHTOOL_A_HEADER_FLAGS, ETHTOOL_GENL_NAME, ETHTOOL_GENL_VERSION,
+    },
+    netlink::{emit_nlas, genl_family_id, genl_request},
+    EthtoolCoalesceInfo, EthtoolInfo, EthtoolLinkModeDuplex, Iface,
+    NisporError,
+};
+
+// The ethtool crate only support changing channels, all the settings are
+// applied by raw generic netlink messages.
+const ETHTOOL_MSG_LINKMODES_SET: u8 = 5;
+const ETHTOOL_MSG_FEATURES_SET: u8 = 12;
+const ETHTOOL_MSG_RINGS_SET: u8 = 16;
+const ETHTOOL_MSG_COALESCE_SET: u8 = 20;
+const ETHTOOL_MSG_PAUSE_SET: u8 = 22;
+
+const ETHTOOL_FLAG_OMIT_REPLY: u32 = 1 << 1;
+
+const ETHTOOL_A_LINKMODES_AUTONEG: u16 = 2;
+const ETHTOOL_A_LINKMODES_OURS: u16 = 3;
+const ETHTOOL_A_LINKMODES_SPEED: u16 = 5;
+const ETHTOOL_A_LINKMODES_DUPLEX: u16 = 6;
+
+const ETHTOOL_A_FEATURES_WANTED: u16 = 3;
+
+const ETHTOOL_A_RINGS_RX: u16 = 6;
+const ETHTOOL_A_RINGS_RX_MINI: u16 = 7;
+const ETHTOOL_A_RINGS_RX_JUMBO: u16 = 8;
+const ETHTOOL_A_RINGS_TX: u16 = 9;
+
+const ETHTOOL_A_COALESCE_RX_USECS: u16 = 2;
+const ETHTOOL_A_COALESCE_RX_MAX_FRAMES: u16 = 3;
+const ETHTOOL_A_COALESCE_RX_USECS_IRQ: u16 = 4;
+const ETHTOOL_A_COALESCE_RX_MAX_FRAMES_IRQ: u16 = 5;
+const ETHTOOL_A_COALESCE_TX_USECS: u16 = 6;
+const ETHTOOL_A_COALESCE_TX_MAX_FRAMES: u16 = 7;
+const ETHTOOL_A_COALESCE_TX_USECS_IRQ: u16 = 8;
+const ETHTOOL_A_COALESCE_TX_MAX_FRAMES_IRQ: u16 = 9;
+const ETHTOOL_A_COALESCE_STATS_BLOCK_USECS: u16 = 10;
+const ETHTOOL_A_COALESCE_USE_ADAPTIVE_RX: u16 = 11;
+const ETHTOOL_A_COALESCE_USE_ADAPTIVE_TX: u16 = 12;
+const ETHTOOL_A_COALESCE_PKT_RATE_LOW: u16 = 13;
+const ETHTOOL_A_COALESCE_RX_USECS_LOW: u16 = 14;
+const ETHTOOL_A_COALESCE_RX_MAX_FRAMES_LOW: u16 = 15;
+const ETHTOOL_A_COALESCE_TX_USECS_LOW: u16 = 16;
+const ETHTOOL_A_COALESCE_TX_MAX_FRAMES_LOW: u16 = 17;
+const ETHTOOL_A_COALESCE_PKT_RATE_HIGH: u16 = 18;
+const ETHTOOL_A_COALESCE_RX_USECS_HIGH: u16 = 19;
+const ETHTOOL_A_COALESCE_RX_MAX_FRAMES_HIGH: u16 = 20;
+const ETHTOOL_A_COALESCE_TX_USECS_HIGH: u16 = 21;
+const ETHTOOL_A_COALESCE_TX_MAX_FRAMES_HIGH: u16 = 22;
+const ETHTOOL_A_COALESCE_RATE_SAMPLE_INTERVAL: u16 = 23;
+
+const ETHTOOL_A_PAUSE_AUTONEG: u16 = 2;
+const ETHTOOL_A_PAUSE_RX: u16 = 3;
+const ETHTOOL_A_PAUSE_TX: u16 = 4;
+
+const DUPLEX_HALF: u8 = 0x00;
+const DUPLEX_FULL: u8 = 0x01;
+const DUPLEX_UNKNOWN: u8 = 0xff;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolConf {
+    /// Kernel feature names, e.g. `rx-gro`. Only features listed in
+    /// `EthtoolFeatureInfo.changeable` can be changed.
+    pub features: Option<BTreeMap<String, bool>>,
+    pub ring: Option<EthtoolRingConf>,
+    pub coalesce: Option<EthtoolCoalesceConf>,
+    pub pause: Option<EthtoolPauseConf>,
+    pub link_mode: Option<EthtoolLinkModeConf>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolRingConf {
+    pub rx: Option<u32>,
+    pub rx_jumbo: Option<u32>,
+    pub rx_mini: Option<u32>,
+    pub tx: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolCoalesceConf {
+    pub pkt_rate_high: Option<u32>,
+    pub pkt_rate_low: Option<u32>,
+    pub rate_sample_interval: Option<u32>,
+    pub rx_max_frames: Option<u32>,
+    pub rx_max_frames_high: Option<u32>,
+    pub rx_max_frames_irq: Option<u32>,
+    pub rx_max_frames_low: Option<u32>,
+    pub rx_usecs: Option<u32>,
+    pub rx_usecs_high: Option<u32>,
+    pub rx_usecs_irq: Option<u32>,
+    pub rx_usecs_low: Option<u32>,
+    pub stats_block_usecs: Option<u32>,
+    pub tx_max_frames: Option<u32>,
+    pub tx_max_frames_high: Option<u32>,
+    pub tx_max_frames_irq: Option<u32>,
+    pub tx_max_frames_low: Option<u32>,
+    pub tx_usecs: Option<u32>,
+    pub tx_usecs_high: Option<u32>,
+    pub tx_usecs_irq: Option<u32>,
+    pub tx_usecs_low: Option<u32>,
+    pub use_adaptive_rx: Option<bool>,
+    pub use_adaptive_tx: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolPauseConf {
+    pub rx: Option<bool>,
+    pub tx: Option<bool>,
+    pub auto_negotiate: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolLinkModeConf {
+    pub auto_negotiate: Option<bool>,
+    /// Speed in Mbps, only used when auto negotiation is disabled
+    pub speed: Option<u32>,
+    /// Only used when auto negotiation is disabled
+    pub duplex: Option<EthtoolLinkModeDuplex>,
+    /// Full list of link modes to advertise, e.g. `1000baseT/Full`.
+    /// Link modes not listed will not be advertised.
+    pub advertise: Option<Vec<String>>,
+}
+
+impl EthtoolConf {
+    // Only the changed properties are sent to kernel.
+    pub(crate) async fn apply(
+        &self,
+        cur_iface: &Iface,
+    ) -> Result<(), NisporError> {
+        let default_ethtool_info = EthtoolInfo::default();
+        let cur_info =
+            cur_iface.ethtool.as_ref().unwrap_or(&default_ethtool_info);
+
+        let mut requests: Vec<(u8, Vec<DefaultNla>)> = Vec::new();
+        if let Some(features) = self.features.as_ref() {
+            let nlas = gen_features_nlas(&cur_iface.name, features, cur_info)?;
+            if !nlas.is_empty() {
+                requests.push((ETHTOOL_MSG_FEATURES_SET, nlas));
+            }
+        }
+        if let Some(ring) = self.ring.as_ref() {
+            let nlas = ring.gen_nlas(cur_info);
+            if !nlas.is_empty() {
+                requests.push((ETHTOOL_MSG_RINGS_SET, nlas));
+            }
+        }
+        if let Some(coalesce) = self.coalesce.as_ref() {
+            let nlas = coalesce.gen_nlas(cur_info);
+            if !nlas.is_empty() {
+                requests.push((ETHTOOL_MSG_COALESCE_SET, nlas));
+            }
+        }
+        if let Some(pause) = self.pause.as_ref() {
+            let nlas = pause.gen_nlas(cur_info);
+            if !nlas.is_empty() {
+                requests.push((ETHTOOL_MSG_PAUSE_SET, nlas));
+            }
+        }
+        if let Some(link_mode) = self.link_mode.as_ref() {
+            let nlas = link_mode.gen_nlas(cur_info);
+            if !nlas.is_empty() {
+                requests.push((ETHTOOL_MSG_LINKMODES_SET, nlas));
+            }
+        }
+        if requests.is_empty() {
+            return Ok(());
+        }
+
+        let family_id = match genl_family_id(ETHTOOL_GENL_NAME).await? {
+            Some(i) => i,
+            None => {
+                let e = NisporError::bug(
+                    "Ethtool generic netlink family not found".into(),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        };
+        // Kernel replies ETHTOOL_MSG_*_SET with ACK only when
+        // ETHTOOL_FLAG_OMIT_REPLY is set.
+        let header_nla = gen_ethtool_header_nla(
+            ETHTOOL_A_HEADER,
+            &[DefaultNla::new(
+                ETHTOOL_A_HEADER_FLAGS,
+                ETHTOOL_FLAG_OMIT_REPLY.to_ne_bytes().to_vec(),
+            )],
+            Some(&cur_iface.name),
+        );
+        for (cmd, nlas) in requests {
+            let mut all_nlas = vec![header_nla.clone()];
+            all_nlas.extend(nlas);
+            if let Err(e) =
+                genl_request(family_id, cmd, ETHTOOL_GENL_VERSION, &all_nlas)
+                    .await
+            {
+                log::error!(
+                    "Failed to set ethtool configure of {}: {}",
+                    cur_iface.name,
+                    e
+                );
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl EthtoolRingConf {
+    fn gen_nlas(&self, cur_info: &EthtoolInfo) -> Vec<DefaultNla> {
+        let cur = cur_info.ring.clone().unwrap_or_default();
+        let mut nlas = Vec::new();
+        push_u32_nla(&mut nlas, ETHTOOL_A_RINGS_RX, self.rx, cur.rx);
+        push_u32_nla(
+            &mut nlas,
+            ETHTOOL_A_RINGS_RX_JUMBO,
+            self.rx_jumbo,
+            cur.rx_jumbo,
+        );
+        push_u32_nla(
+            &mut nlas,
+            ETHTOOL_A_RINGS_RX_MINI,
+            self.rx_mini,
+            cur.rx_mini,
+        );
+        push_u32_nla(&mut nlas, ETHTOOL_A_RINGS_TX, self.tx, cur.tx);
+        nlas
+    }
+}
+
+impl EthtoolCoalesceConf {
+    fn gen_nlas(&self, cur_info: &EthtoolInfo) -> Vec<DefaultNla> {
+        let cur: EthtoolCoalesceInfo =
+            cur_info.coalesce.clone().unwrap_or_default();
+        let mut nlas = Vec::new();
+        for (kind, value, cur_value) in [
+            (
+                ETHTOOL_A_COALESCE_PKT_RATE_HIGH,
+                self.pkt_rate_high,
+                cur.pkt_rate_high,
+            ),
+            (
+                ETHTOOL_A_COALESCE_PKT_RATE_LOW,
+                self.pkt_rate_low,
+                cur.pkt_rate_low,
+            ),
+            (
+                ETHTOOL_A_COALESCE_RATE_SAMPLE_INTERVAL,
+                self.rate_sample_interval,
+                cur.rate_sample_interval,
+            ),
+            (
+                ETHTOOL_A_COALESCE_RX_MAX_FRAMES,
+                self.rx_max_frames,
+                cur.rx_max_frames,
+            ),
+            (
+                ETHTOOL_A_COALESCE_RX_MAX_FRAMES_HIGH,
+                self.rx_max_frames_high,
+                cur.rx_max_frames_high,
+            ),
+            (
+                ETHTOOL_A_COALESCE_RX_MAX_FRAMES_IRQ,
+                self.rx_max_frames_irq,
+                cur.rx_max_frames_irq,
+            ),
+            (
+                ETHTOOL_A_COALESCE_RX_MAX_FRAMES_LOW,
+                self.rx_max_frames_low,
+                cur.rx_max_frames_low,
+            ),
+            (ETHTOOL_A_COALESCE_RX_USECS, self.rx_usecs, cur.rx_usecs),
+            (
+                ETHTOOL_A_COALESCE_RX_USECS_HIGH,
+                self.rx_usecs_high,
+                cur.rx_usecs_high,
+            ),
+            (
+                ETHTOOL_A_COALESCE_RX_USECS_IRQ,
+                self.rx_usecs_irq,
+                cur.rx_usecs_irq,
+            ),
+            (
+                ETHTOOL_A_COALESCE_RX_USECS_LOW,
+                self.rx_usecs_low,
+                cur.rx_usecs_low,
+            ),
+            (
+                ETHTOOL_A_COALESCE_STATS_BLOCK_USECS,
+                self.stats_block_usecs,
+                cur.stats_block_usecs,
+            ),
+            (
+                ETHTOOL_A_COALESCE_TX_MAX_FRAMES,
+                self.tx_max_frames,
+                cur.tx_max_frames,
+            ),
+            (
+                ETHTOOL_A_COALESCE_TX_MAX_FRAMES_HIGH,
+                self.tx_max_frames_high,
+                cur.tx_max_frames_high,
+            ),
+            (
+                ETHTOOL_A_COALESCE_TX_MAX_FRAMES_IRQ,
+                self.tx_max_frames_irq,
+                cur.tx_max_frames_irq,
+            ),
+            (
+                ETHTOOL_A_COALESCE_TX_MAX_FRAMES_LOW,
+                self.tx_max_frames_low,
+                cur.tx_max_frames_low,
+            ),
+            (ETHTOOL_A_COALESCE_TX_USECS, self.tx_usecs, cur.tx_usecs),
+            (
+                ETHTOOL_A_COALESCE_TX_USECS_HIGH,
+                self.tx_usecs_high,
+                cur.tx_usecs_high,
+            ),
+            (
+                ETHTOOL_A_COALESCE_TX_USECS_IRQ,
+                self.tx_usecs_irq,
+                cur.tx_usecs_irq,
+            ),
+            (
+                ETHTOOL_A_COALESCE_TX_USECS_LOW,
+                self.tx_usecs_low,
+                cur.tx_usecs_low,
+            ),
+        ] {
+            push_u32_nla(&mut nlas, kind, value, cur_value);
+        }
+        push_bool_nla(
+            &mut nlas,
+            ETHTOOL_A_COALESCE_USE_ADAPTIVE_RX,
+            self.use_adaptive_rx,
+            cur.use_adaptive_rx,
+        );
+        push_bool_nla(
+            &mut nlas,
+            ETHTOOL_A_COALESCE_USE_ADAPTIVE_TX,
+            self.use_adaptive_tx,
+            cur.use_adaptive_tx,
+        );
+        nlas
+    }
+}
+
+impl EthtoolPauseConf {
+    fn gen_nlas(&self, cur_info: &EthtoolInfo) -> Vec<DefaultNla> {
+        let cur = cur_info.pause.as_ref();
+        let mut nlas = Vec::new();
+        push_bool_nla(
+            &mut nlas,
+            ETHTOOL_A_PAUSE_AUTONEG,
+            self.auto_negotiate,
+            cur.map(|p| p.auto_negotiate),
+        );
+        push_bool_nla(
+            &mut nlas,
+            ETHTOOL_A_PAUSE_RX,
+            self.rx,
+            cur.map(|p| p.rx),
+        );
+        push_bool_nla(
+            &mut nlas,
+            ETHTOOL_A_PAUSE_TX,
+            self.tx,
+            cur.map(|p| p.tx),
+        );
+        nlas
+    }
+}
+
+impl EthtoolLinkModeConf {
+    fn gen_nlas(&self, cur_info: &EthtoolInfo) -> Vec<DefaultNla> {
+        let cur = cur_info.link_mode.as_ref();
+        let mut nlas = Vec::new();
+        push_bool_nla(
+            &mut nlas,
+            ETHTOOL_A_LINKMODES_AUTONEG,
+            self.auto_negotiate,
+            cur.map(|l| l.auto_negotiate),
+        );
+        push_u32_nla(
+            &mut nlas,
+            ETHTOOL_A_LINKMODES_SPEED,
+            self.speed,
+            cur.map(|l| l.speed),
+        );
+        if let Some(duplex) = self.duplex.as_ref() {
+            if cur.map(|l| &l.duplex) != Some(duplex) {
+                let value = match duplex {
+                    EthtoolLinkModeDuplex::Half => DUPLEX_HALF,
+                    EthtoolLinkModeDuplex::Full => DUPLEX_FULL,
+                    EthtoolLinkModeDuplex::Unknown => DUPLEX_UNKNOWN,
+                    EthtoolLinkModeDuplex::Other(d) => *d,
+                };
+                nlas.push(DefaultNla::new(
+                    ETHTOOL_A_LINKMODES_DUPLEX,
+                    vec![value],
+                ));
+            }
+        }
+        if let Some(advertise) = self.advertise.as_ref() {
+            let mut modes = advertise.clone();
+            let mut cur_modes = cur.map(|l| l.ours.clone()).unwrap_or_default();
+            modes.sort_unstable();
+            cur_modes.sort_unstable();
+            if cur.is_none() || modes != cur_modes {
+                let bits: Vec<(&str, bool)> =
+                    advertise.iter().map(|m| (m.as_str(), true)).collect();
+                nlas.push(DefaultNla::new(
+                    ETHTOOL_A_LINKMODES_OURS | NLA_F_NESTED,
+                    gen_bitset(&bits, true),
+                ));
+            }
+        }
+        nlas
+    }
+}
+
+fn gen_features_nlas(
+    iface_name: &str,
+    features: &BTreeMap<String, bool>,
+    cur_info: &EthtoolInfo,
+) -> Result<Vec<DefaultNla>, NisporError> {
+    let cur_features = match cur_info.features.as_ref() {
+        Some(f) => f,
+        None => {
+            return Err(NisporError::invalid_argument(format!(
+                "Interface {iface_name} does not support ethtool features"
+            )));
+        }
+    };
+    let mut bits = Vec::new();
+    for (name, value) in features {
+        match cur_features.changeable.get(name) {
+            Some(cur_value) => {
+                if cur_value != value {
+                    bits.push((name.as_str(), *value));
+                }
+            }
+            None => {
+                return Err(NisporError::invalid_argument(
+                    if cur_features.fixed.contains_key(name) {
+                        format!(
+                            "Ethtool feature {name} of interface \
+                            {iface_name} is fixed"
+                        )
+                    } else {
+                        format!(
+                            "Ethtool feature {name} not found in interface \
+                            {iface_name}"
+                        )
+                    },
+                ));
+            }
+        }
+    }
+    if bits.is_empty() {
+        Ok(Vec::new())
+    } else {
+        Ok(vec![DefaultNla::new(
+            ETHTOOL_A_FEATURES_WANTED | NLA_F_NESTED,
+            gen_bitset(&bits, false),
+        )])
+    }
+}
+
+// Generate verbose(non-compact) bitset using bit names.
+// With `no_mask` set, all bits not listed are cleared. Otherwise, only listed
+// bits are changed.
+fn gen_bitset(bits: &[(&str, bool)], no_mask: bool) -> Vec<u8> {
+    let bit_nlas: Vec<DefaultNla> = bits
+        .iter()
+        .map(|(name, value)| {
+            let mut name_raw = name.as_bytes().to_vec();
+            name_raw.push(0);
+            let mut attrs =
+                vec![DefaultNla::new(ETHTOOL_A_BITSET_BIT_NAME, name_raw)];
+            if *value {
+                attrs.push(DefaultNla::new(ETHTOOL_A_BITSET_BIT_VALUE, vec![]));
+            }
+            DefaultNla::new(
+                ETHTOOL_A_BITSET_BITS_BIT | NLA_F_NESTED,
+                emit_nlas(&attrs),
+            )
+        })
+        .collect();
+    let mut nlas = Vec::new();
+    if no_mask {
+        nlas.push(DefaultNla::new(ETHTOOL_A_BITSET_NOMASK, vec![]));
+    }
+    nlas.push(DefaultNla::new(
+        ETHTOOL_A_BITSET_BITS | NLA_F_NESTED,
+        emit_nlas(&bit_nlas),
+    ));
+    emit_nlas(&nlas)
+}
+
+fn push_u32_nla(
+    nlas: &mut Vec<DefaultNla>,
+    kind: u16,
+    value: Option<u32>,
+    cur_value: Option<u32>,
+) {
+    if let Some(value) = value {
+        if cur_value != Some(value) {
+            nlas.push(DefaultNla::new(kind, value.to_ne_bytes().to_vec()));
+        }
+    }
+}
+
+fn push_bool_nla(
+    nlas: &mut Vec<DefaultNla>,
+    kind: u16,
+    value: Option<bool>,
+    cur_value: Option<bool>,
+) {
+    if let Some(value) = value {
+        if cur_value != Some(value) {
+            nlas.push(DefaultNla::new(kind, vec![value as u8]));
+        }
+    }
+}
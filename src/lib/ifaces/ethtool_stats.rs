@@ -0,0 +1,397 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+
+use netlink_packet_utils::nla::{DefaultNla, NlasIterator, NLA_F_NESTED};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ifaces::ethtool::{
+        ethtool_genl_query, gen_ethtool_header_nla,
+        parse_genl_header_iface_name, ETHTOOL_A_BITSET_NOMASK,
+        ETHTOOL_A_BITSET_SIZE, ETHTOOL_A_BITSET_VALUE, ETHTOOL_A_HEADER_FLAGS,
+        ETHTOOL_GENL_VERSION,
+    },
+    netlink::{
+        emit_nlas, genl_get, parse_as_string, parse_as_u32, parse_as_u64,
+    },
+    NisporError,
+};
+
+const ETHTOOL_MSG_STRSET_GET: u8 = 1;
+const ETHTOOL_MSG_PAUSE_GET: u8 = 21;
+const ETHTOOL_MSG_STATS_GET: u8 = 32;
+
+const ETHTOOL_FLAG_STATS: u32 = 1 << 2;
+
+const ETHTOOL_A_STRSET_HEADER: u16 = 1;
+const ETHTOOL_A_STRSET_STRINGSETS: u16 = 2;
+const ETHTOOL_A_STRINGSETS_STRINGSET: u16 = 1;
+const ETHTOOL_A_STRINGSET_ID: u16 = 1;
+const ETHTOOL_A_STRINGSET_STRINGS: u16 = 3;
+const ETHTOOL_A_STRINGS_STRING: u16 = 1;
+const ETHTOOL_A_STRING_INDEX: u16 = 1;
+const ETHTOOL_A_STRING_VALUE: u16 = 2;
+
+const ETHTOOL_A_PAUSE_HEADER: u16 = 1;
+const ETHTOOL_A_PAUSE_STATS: u16 = 5;
+const ETHTOOL_A_PAUSE_STAT_TX_FRAMES: u16 = 2;
+const ETHTOOL_A_PAUSE_STAT_RX_FRAMES: u16 = 3;
+
+const ETHTOOL_A_STATS_HEADER: u16 = 2;
+const ETHTOOL_A_STATS_GROUPS: u16 = 3;
+const ETHTOOL_A_STATS_GRP: u16 = 4;
+
+const ETHTOOL_A_STATS_GRP_ID: u16 = 2;
+const ETHTOOL_A_STATS_GRP_SS_ID: u16 = 3;
+const ETHTOOL_A_STATS_GRP_STAT: u16 = 4;
+const ETHTOOL_A_STATS_GRP_HIST_RX: u16 = 5;
+const ETHTOOL_A_STATS_GRP_HIST_TX: u16 = 6;
+const ETHTOOL_A_STATS_GRP_HIST_BKT_LOW: u16 = 7;
+const ETHTOOL_A_STATS_GRP_HIST_BKT_HI: u16 = 8;
+const ETHTOOL_A_STATS_GRP_HIST_VAL: u16 = 9;
+
+const ETHTOOL_STATS_ETH_PHY: u32 = 0;
+const ETHTOOL_STATS_ETH_MAC: u32 = 1;
+const ETHTOOL_STATS_ETH_CTRL: u32 = 2;
+const ETHTOOL_STATS_RMON: u32 = 3;
+const ETHTOOL_STATS_GROUP_COUNT: u32 = 4;
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolStatsInfo {
+    /// IEEE 802.3 PHY counters, keyed by kernel string set names like
+    /// `SymbolErrorDuringCarrier`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_phy: Option<BTreeMap<String, u64>>,
+    /// IEEE 802.3 MAC counters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_mac: Option<BTreeMap<String, u64>>,
+    /// IEEE 802.3 MAC Control counters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_ctrl: Option<BTreeMap<String, u64>>,
+    /// RFC 2819 RMON counters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rmon: Option<BTreeMap<String, u64>>,
+    /// RMON packet size histogram of received packets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rmon_rx_hist: Option<Vec<EthtoolStatsHistBucket>>,
+    /// RMON packet size histogram of transmitted packets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rmon_tx_hist: Option<Vec<EthtoolStatsHistBucket>>,
+    /// Pause frame counters: `tx_pause_frames` and `rx_pause_frames`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pause: Option<BTreeMap<String, u64>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct EthtoolStatsHistBucket {
+    /// Minimum packet size in bytes of this bucket
+    pub low: u32,
+    /// Maximum packet size in bytes of this bucket
+    pub high: u32,
+    pub packets: u64,
+}
+
+#[derive(Debug, Default)]
+struct EthtoolStatsGroup {
+    id: u32,
+    string_set_id: u32,
+    // Index in string set and value
+    stats: Vec<(u32, u64)>,
+    rx_hist: Vec<EthtoolStatsHistBucket>,
+    tx_hist: Vec<EthtoolStatsHistBucket>,
+}
+
+pub(crate) async fn dump_ethtool_stats(
+    family_id: u16,
+    iface_name: Option<&str>,
+) -> Result<HashMap<String, EthtoolStatsInfo>, NisporError> {
+    let mut infos: HashMap<String, EthtoolStatsInfo> = HashMap::new();
+    let mut string_sets: HashMap<u32, HashMap<u32, String>> = HashMap::new();
+
+    // Request all groups using compact bitset
+    let groups_nlas = vec![
+        DefaultNla::new(ETHTOOL_A_BITSET_NOMASK, vec![]),
+        DefaultNla::new(
+            ETHTOOL_A_BITSET_SIZE,
+            ETHTOOL_STATS_GROUP_COUNT.to_ne_bytes().to_vec(),
+        ),
+        DefaultNla::new(
+            ETHTOOL_A_BITSET_VALUE,
+            ((1u32 << ETHTOOL_STATS_GROUP_COUNT) - 1)
+                .to_ne_bytes()
+                .to_vec(),
+        ),
+    ];
+    let nlas = vec![DefaultNla::new(
+        ETHTOOL_A_STATS_GROUPS | NLA_F_NESTED,
+        emit_nlas(&groups_nlas),
+    )];
+
+    for reply in ethtool_genl_query(
+        family_id,
+        ETHTOOL_MSG_STATS_GET,
+        ETHTOOL_A_STATS_HEADER,
+        &[],
+        &nlas,
+        iface_name,
+    )
+    .await?
+    {
+        let mut iface_name = None;
+        let mut groups = Vec::new();
+        for nla in NlasIterator::new(reply.as_slice()) {
+            let nla = nla?;
+            match nla.kind() {
+                ETHTOOL_A_STATS_HEADER => {
+                    iface_name = parse_genl_header_iface_name(nla.value())?;
+                }
+                ETHTOOL_A_STATS_GRP => {
+                    groups.push(parse_stats_group(nla.value())?);
+                }
+                _ => (),
+            }
+        }
+        let iface_name = match iface_name {
+            Some(i) => i,
+            None => continue,
+        };
+        let mut stats_info = EthtoolStatsInfo::default();
+        for group in groups {
+            if !group.stats.is_empty()
+                && !string_sets.contains_key(&group.string_set_id)
+            {
+                string_sets.insert(
+                    group.string_set_id,
+                    get_string_set(family_id, &iface_name, group.string_set_id)
+                        .await?,
+                );
+            }
+            let names = string_sets.get(&group.string_set_id);
+            let stats: BTreeMap<String, u64> = group
+                .stats
+                .iter()
+                .map(|(index, value)| {
+                    (
+                        names
+                            .and_then(|n| n.get(index))
+                            .cloned()
+                            .unwrap_or_else(|| format!("{index}")),
+                        *value,
+                    )
+                })
+                .collect();
+            let stats = if stats.is_empty() { None } else { Some(stats) };
+            match group.id {
+                ETHTOOL_STATS_ETH_PHY => stats_info.eth_phy = stats,
+                ETHTOOL_STATS_ETH_MAC => stats_info.eth_mac = stats,
+                ETHTOOL_STATS_ETH_CTRL => stats_info.eth_ctrl = stats,
+                ETHTOOL_STATS_RMON => {
+                    stats_info.rmon = stats;
+                    if !group.rx_hist.is_empty() {
+                        stats_info.rmon_rx_hist = Some(group.rx_hist);
+                    }
+                    if !group.tx_hist.is_empty() {
+                        stats_info.rmon_tx_hist = Some(group.tx_hist);
+                    }
+                }
+                _ => log::debug!("Unknown ethtool stats group {}", group.id),
+            }
+        }
+        if stats_info != EthtoolStatsInfo::default() {
+            infos.insert(iface_name, stats_info);
+        }
+    }
+
+    for (iface_name, pause_stats) in
+        dump_pause_stats(family_id, iface_name).await?
+    {
+        infos.entry(iface_name).or_default().pause = Some(pause_stats);
+    }
+
+    Ok(infos)
+}
+
+fn parse_stats_group(data: &[u8]) -> Result<EthtoolStatsGroup, NisporError> {
+    let mut group = EthtoolStatsGroup::default();
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        match nla.kind() {
+            ETHTOOL_A_STATS_GRP_ID => group.id = parse_as_u32(nla.value())?,
+            ETHTOOL_A_STATS_GRP_SS_ID => {
+                group.string_set_id = parse_as_u32(nla.value())?
+            }
+            // Each ETHTOOL_A_STATS_GRP_STAT holds single attribute using
+            // index in string set as type.
+            ETHTOOL_A_STATS_GRP_STAT => {
+                for stat_nla in NlasIterator::new(nla.value()) {
+                    let stat_nla = stat_nla?;
+                    group.stats.push((
+                        stat_nla.kind().into(),
+                        parse_as_u64(stat_nla.value())?,
+                    ));
+                }
+            }
+            ETHTOOL_A_STATS_GRP_HIST_RX => {
+                group.rx_hist.push(parse_hist_bucket(nla.value())?);
+            }
+            ETHTOOL_A_STATS_GRP_HIST_TX => {
+                group.tx_hist.push(parse_hist_bucket(nla.value())?);
+            }
+            _ => (),
+        }
+    }
+    Ok(group)
+}
+
+fn parse_hist_bucket(
+    data: &[u8],
+) -> Result<EthtoolStatsHistBucket, NisporError> {
+    let mut bucket = EthtoolStatsHistBucket::default();
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        match nla.kind() {
+            ETHTOOL_A_STATS_GRP_HIST_BKT_LOW => {
+                bucket.low = parse_as_u32(nla.value())?
+            }
+            ETHTOOL_A_STATS_GRP_HIST_BKT_HI => {
+                bucket.high = parse_as_u32(nla.value())?
+            }
+            ETHTOOL_A_STATS_GRP_HIST_VAL => {
+                bucket.packets = parse_as_u64(nla.value())?
+            }
+            _ => (),
+        }
+    }
+    Ok(bucket)
+}
+
+// The standard statistics string sets are global, but kernel still refuses
+// ETHTOOL_MSG_STRSET_GET without interface specified.
+async fn get_string_set(
+    family_id: u16,
+    iface_name: &str,
+    string_set_id: u32,
+) -> Result<HashMap<u32, String>, NisporError> {
+    let mut ret = HashMap::new();
+    let id_nla = DefaultNla::new(
+        ETHTOOL_A_STRINGSET_ID,
+        string_set_id.to_ne_bytes().to_vec(),
+    );
+    let string_set_nla = DefaultNla::new(
+        ETHTOOL_A_STRINGSETS_STRINGSET | NLA_F_NESTED,
+        emit_nlas(&[id_nla]),
+    );
+    let nlas = vec![
+        gen_ethtool_header_nla(ETHTOOL_A_STRSET_HEADER, &[], Some(iface_name)),
+        DefaultNla::new(
+            ETHTOOL_A_STRSET_STRINGSETS | NLA_F_NESTED,
+            emit_nlas(&[string_set_nla]),
+        ),
+    ];
+    let reply = match genl_get(
+        family_id,
+        ETHTOOL_MSG_STRSET_GET,
+        ETHTOOL_GENL_VERSION,
+        &nlas,
+    )
+    .await?
+    {
+        Some(r) => r,
+        None => return Ok(ret),
+    };
+
+    for nla in NlasIterator::new(reply.as_slice()) {
+        let nla = nla?;
+        if nla.kind() != ETHTOOL_A_STRSET_STRINGSETS {
+            continue;
+        }
+        for string_set_nla in NlasIterator::new(nla.value()) {
+            for string_set_attr in NlasIterator::new(string_set_nla?.value()) {
+                let string_set_attr = string_set_attr?;
+                if string_set_attr.kind() != ETHTOOL_A_STRINGSET_STRINGS {
+                    continue;
+                }
+                for string_nla in NlasIterator::new(string_set_attr.value()) {
+                    let string_nla = string_nla?;
+                    if string_nla.kind() != ETHTOOL_A_STRINGS_STRING {
+                        continue;
+                    }
+                    let mut index = None;
+                    let mut value = None;
+                    for attr in NlasIterator::new(string_nla.value()) {
+                        let attr = attr?;
+                        match attr.kind() {
+                            ETHTOOL_A_STRING_INDEX => {
+                                index = Some(parse_as_u32(attr.value())?)
+                            }
+                            ETHTOOL_A_STRING_VALUE => {
+                                value = Some(parse_as_string(attr.value()))
+                            }
+                            _ => (),
+                        }
+                    }
+                    if let (Some(index), Some(value)) = (index, value) {
+                        ret.insert(index, value);
+                    }
+                }
+            }
+        }
+    }
+    Ok(ret)
+}
+
+async fn dump_pause_stats(
+    family_id: u16,
+    iface_name: Option<&str>,
+) -> Result<HashMap<String, BTreeMap<String, u64>>, NisporError> {
+    let mut infos = HashMap::new();
+    let header_nla = DefaultNla::new(
+        ETHTOOL_A_HEADER_FLAGS,
+        ETHTOOL_FLAG_STATS.to_ne_bytes().to_vec(),
+    );
+    for reply in ethtool_genl_query(
+        family_id,
+        ETHTOOL_MSG_PAUSE_GET,
+        ETHTOOL_A_PAUSE_HEADER,
+        &[header_nla],
+        &[],
+        iface_name,
+    )
+    .await?
+    {
+        let mut iface_name = None;
+        let mut stats = BTreeMap::new();
+        for nla in NlasIterator::new(reply.as_slice()) {
+            let nla = nla?;
+            match nla.kind() {
+                ETHTOOL_A_PAUSE_HEADER => {
+                    iface_name = parse_genl_header_iface_name(nla.value())?;
+                }
+                ETHTOOL_A_PAUSE_STATS => {
+                    for stat_nla in NlasIterator::new(nla.value()) {
+                        let stat_nla = stat_nla?;
+                        let name = match stat_nla.kind() {
+                            ETHTOOL_A_PAUSE_STAT_TX_FRAMES => "tx_pause_frames",
+                            ETHTOOL_A_PAUSE_STAT_RX_FRAMES => "rx_pause_frames",
+                            _ => continue,
+                        };
+                        stats.insert(
+                            name.to_string(),
+                            parse_as_u64(stat_nla.value())?,
+                        );
+                    }
+                }
+                _ => (),
+            }
+        }
+        if let Some(i) = iface_name {
+            if !stats.is_empty() {
+                infos.insert(i, stats);
+            }
+        }
+    }
+    Ok(infos)
+}
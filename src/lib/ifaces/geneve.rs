@@ -0,0 +1,188 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use netlink_packet_route::rtnl::link::nlas::{Info, InfoData, InfoKind, Nla};
+use netlink_packet_utils::{
+    nla::{DefaultNla, NlasIterator},
+    Emitable,
+};
+use rtnetlink::Handle;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    netlink::{
+        parse_as_be16, parse_as_be32, parse_as_ipv4, parse_as_ipv6,
+        parse_as_u32, parse_as_u8,
+    },
+    NisporError,
+};
+
+const GENEVE_KIND: &str = "geneve";
+
+const IFLA_GENEVE_ID: u16 = 1;
+const IFLA_GENEVE_REMOTE: u16 = 2;
+const IFLA_GENEVE_TTL: u16 = 3;
+const IFLA_GENEVE_TOS: u16 = 4;
+const IFLA_GENEVE_PORT: u16 = 5;
+const IFLA_GENEVE_COLLECT_METADATA: u16 = 6;
+const IFLA_GENEVE_REMOTE6: u16 = 7;
+const IFLA_GENEVE_UDP_CSUM: u16 = 8;
+const IFLA_GENEVE_UDP_ZERO_CSUM6_TX: u16 = 9;
+const IFLA_GENEVE_UDP_ZERO_CSUM6_RX: u16 = 10;
+const IFLA_GENEVE_LABEL: u16 = 11;
+const IFLA_GENEVE_TTL_INHERIT: u16 = 12;
+const IFLA_GENEVE_DF: u16 = 13;
+const IFLA_GENEVE_INNER_PROTO_INHERIT: u16 = 14;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct GeneveInfo {
+    pub remote: String,
+    pub geneve_id: u32,
+    pub ttl: u8,
+    pub tos: u8,
+    pub dst_port: u16,
+    pub collect_metadata: bool,
+    pub udp_check_sum: bool,
+    pub udp6_zero_check_sum_tx: bool,
+    pub udp6_zero_check_sum_rx: bool,
+    pub label: u32,
+    pub ttl_inherit: bool,
+    pub df: u8,
+    pub inner_proto_inherit: bool,
+}
+
+pub(crate) fn get_geneve_info(
+    data: &InfoData,
+) -> Result<Option<GeneveInfo>, NisporError> {
+    if let InfoData::Other(raw) = data {
+        let mut geneve_info = GeneveInfo::default();
+        for nla in NlasIterator::new(raw.as_slice()) {
+            let nla = nla?;
+            let d = nla.value();
+            match nla.kind() {
+                IFLA_GENEVE_ID => geneve_info.geneve_id = parse_as_u32(d)?,
+                IFLA_GENEVE_REMOTE => {
+                    geneve_info.remote = parse_as_ipv4(d)?.to_string()
+                }
+                IFLA_GENEVE_REMOTE6 => {
+                    geneve_info.remote = parse_as_ipv6(d)?.to_string()
+                }
+                IFLA_GENEVE_TTL => geneve_info.ttl = parse_as_u8(d)?,
+                IFLA_GENEVE_TOS => geneve_info.tos = parse_as_u8(d)?,
+                IFLA_GENEVE_PORT => geneve_info.dst_port = parse_as_be16(d)?,
+                IFLA_GENEVE_COLLECT_METADATA => {
+                    geneve_info.collect_metadata = true
+                }
+                IFLA_GENEVE_UDP_CSUM => {
+                    geneve_info.udp_check_sum = parse_as_u8(d)? > 0
+                }
+                IFLA_GENEVE_UDP_ZERO_CSUM6_TX => {
+                    geneve_info.udp6_zero_check_sum_tx = parse_as_u8(d)? > 0
+                }
+                IFLA_GENEVE_UDP_ZERO_CSUM6_RX => {
+                    geneve_info.udp6_zero_check_sum_rx = parse_as_u8(d)? > 0
+                }
+                IFLA_GENEVE_LABEL => geneve_info.label = parse_as_be32(d)?,
+                IFLA_GENEVE_TTL_INHERIT => {
+                    geneve_info.ttl_inherit = parse_as_u8(d)? > 0
+                }
+                IFLA_GENEVE_DF => geneve_info.df = parse_as_u8(d)?,
+                IFLA_GENEVE_INNER_PROTO_INHERIT => {
+                    geneve_info.inner_proto_inherit = true
+                }
+                _ => log::warn!(
+                    "Unknown GENEVE info {} {:?}",
+                    nla.kind(),
+                    nla.value()
+                ),
+            }
+        }
+        Ok(Some(geneve_info))
+    } else {
+        Ok(None)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct GeneveConf {
+    pub geneve_id: u32,
+    /// Required unless `collect_metadata` is true
+    pub remote: Option<String>,
+    /// Default is 6081
+    pub dst_port: Option<u16>,
+    pub ttl: Option<u8>,
+    pub tos: Option<u8>,
+    pub collect_metadata: Option<bool>,
+    pub udp_check_sum: Option<bool>,
+}
+
+impl GeneveConf {
+    pub(crate) async fn create(
+        &self,
+        handle: &Handle,
+        name: &str,
+    ) -> Result<(), NisporError> {
+        let mut nlas = vec![DefaultNla::new(
+            IFLA_GENEVE_ID,
+            self.geneve_id.to_ne_bytes().to_vec(),
+        )];
+        if let Some(remote) = self.remote.as_ref() {
+            match remote.parse::<IpAddr>() {
+                Ok(IpAddr::V4(ip)) => nlas.push(DefaultNla::new(
+                    IFLA_GENEVE_REMOTE,
+                    ip.octets().to_vec(),
+                )),
+                Ok(IpAddr::V6(ip)) => nlas.push(DefaultNla::new(
+                    IFLA_GENEVE_REMOTE6,
+                    ip.octets().to_vec(),
+                )),
+                Err(_) => {
+                    let e = NisporError::invalid_argument(format!(
+                        "Invalid remote address {remote} for GENEVE {name}"
+                    ));
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        if let Some(dst_port) = self.dst_port {
+            nlas.push(DefaultNla::new(
+                IFLA_GENEVE_PORT,
+                dst_port.to_be_bytes().to_vec(),
+            ));
+        }
+        if let Some(ttl) = self.ttl {
+            nlas.push(DefaultNla::new(IFLA_GENEVE_TTL, vec![ttl]));
+        }
+        if let Some(tos) = self.tos {
+            nlas.push(DefaultNla::new(IFLA_GENEVE_TOS, vec![tos]));
+        }
+        if self.collect_metadata == Some(true) {
+            nlas.push(DefaultNla::new(IFLA_GENEVE_COLLECT_METADATA, vec![]));
+        }
+        if let Some(udp_check_sum) = self.udp_check_sum {
+            nlas.push(DefaultNla::new(
+                IFLA_GENEVE_UDP_CSUM,
+                vec![udp_check_sum as u8],
+            ));
+        }
+        let mut data = vec![0u8; nlas.as_slice().buffer_len()];
+        nlas.as_slice().emit(&mut data);
+
+        let mut req = handle.link().add();
+        req.message_mut().nlas.push(Nla::IfName(name.to_string()));
+        req.message_mut().nlas.push(Nla::Info(vec![
+            Info::Kind(InfoKind::Other(GENEVE_KIND.to_string())),
+            Info::Data(InfoData::Other(data)),
+        ]));
+        match req.execute().await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(NisporError::bug(format!(
+                "Failed to create new GENEVE '{name}': {e}"
+            ))),
+        }
+    }
+}
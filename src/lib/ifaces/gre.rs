@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use netlink_packet_route::rtnl::link::nlas::InfoData;
+use netlink_packet_utils::nla::NlasIterator;
+use serde::{Deserialize, Serialize};
+
+use super::ip_tunnel::{
+    parse_tunnel_addr, parse_tunnel_link, IpTunnelEncapType,
+    IP6_TNL_F_IGN_ENCAP_LIMIT,
+};
+use crate::{
+    netlink::{
+        parse_as_be16, parse_as_be32, parse_as_u16, parse_as_u32, parse_as_u8,
+    },
+    NisporError,
+};
+
+const IFLA_GRE_LINK: u16 = 1;
+const IFLA_GRE_IFLAGS: u16 = 2;
+const IFLA_GRE_OFLAGS: u16 = 3;
+const IFLA_GRE_IKEY: u16 = 4;
+const IFLA_GRE_OKEY: u16 = 5;
+const IFLA_GRE_LOCAL: u16 = 6;
+const IFLA_GRE_REMOTE: u16 = 7;
+const IFLA_GRE_TTL: u16 = 8;
+const IFLA_GRE_TOS: u16 = 9;
+const IFLA_GRE_PMTUDISC: u16 = 10;
+const IFLA_GRE_ENCAP_LIMIT: u16 = 11;
+const IFLA_GRE_FLAGS: u16 = 13;
+const IFLA_GRE_ENCAP_TYPE: u16 = 14;
+const IFLA_GRE_ENCAP_SPORT: u16 = 16;
+const IFLA_GRE_ENCAP_DPORT: u16 = 17;
+const IFLA_GRE_COLLECT_METADATA: u16 = 18;
+
+// The GRE flags are in network order
+const GRE_CSUM: u16 = 0x8000;
+const GRE_KEY: u16 = 0x2000;
+const GRE_SEQ: u16 = 0x1000;
+
+// Shared by gre, gretap, ip6gre and ip6gretap interfaces
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct GreInfo {
+    /// Interface used for tunnel traffic, None if not bound to any interface
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_iface: Option<String>,
+    pub local: String,
+    pub remote: String,
+    /// Key for incoming packets, None if key is not used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ikey: Option<u32>,
+    /// Key for outgoing packets, None if key is not used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub okey: Option<u32>,
+    pub icsum: bool,
+    pub ocsum: bool,
+    pub iseq: bool,
+    pub oseq: bool,
+    /// 0 means inherit from inner packet
+    pub ttl: u8,
+    /// Only for gre and gretap
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tos: Option<u8>,
+    /// Only for gre and gretap
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pmtudisc: Option<bool>,
+    /// Only for ip6gre and ip6gretap, None if encapsulation limit option is
+    /// not used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encap_limit: Option<u8>,
+    pub encap_type: IpTunnelEncapType,
+    pub encap_sport: u16,
+    pub encap_dport: u16,
+    pub collect_metadata: bool,
+}
+
+pub(crate) fn get_gre_info(
+    data: &InfoData,
+) -> Result<Option<GreInfo>, NisporError> {
+    let raw = match data {
+        InfoData::GreTun(d)
+        | InfoData::GreTap(d)
+        | InfoData::GreTun6(d)
+        | InfoData::GreTap6(d) => d,
+        _ => return Ok(None),
+    };
+    let mut info = GreInfo::default();
+    let mut iflags = 0u16;
+    let mut oflags = 0u16;
+    let mut ikey = 0u32;
+    let mut okey = 0u32;
+    let mut flags = 0u32;
+    for nla in NlasIterator::new(raw.as_slice()) {
+        let nla = nla?;
+        let d = nla.value();
+        match nla.kind() {
+            IFLA_GRE_LINK => info.base_iface = parse_tunnel_link(d)?,
+            IFLA_GRE_IFLAGS => iflags = parse_as_be16(d)?,
+            IFLA_GRE_OFLAGS => oflags = parse_as_be16(d)?,
+            IFLA_GRE_IKEY => ikey = parse_as_be32(d)?,
+            IFLA_GRE_OKEY => okey = parse_as_be32(d)?,
+            IFLA_GRE_LOCAL => info.local = parse_tunnel_addr(d)?,
+            IFLA_GRE_REMOTE => info.remote = parse_tunnel_addr(d)?,
+            IFLA_GRE_TTL => info.ttl = parse_as_u8(d)?,
+            IFLA_GRE_TOS => info.tos = Some(parse_as_u8(d)?),
+            IFLA_GRE_PMTUDISC => info.pmtudisc = Some(parse_as_u8(d)? > 0),
+            IFLA_GRE_ENCAP_LIMIT => info.encap_limit = Some(parse_as_u8(d)?),
+            IFLA_GRE_ENCAP_TYPE => info.encap_type = parse_as_u16(d)?.into(),
+            IFLA_GRE_ENCAP_SPORT => info.encap_sport = parse_as_be16(d)?,
+            IFLA_GRE_ENCAP_DPORT => info.encap_dport = parse_as_be16(d)?,
+            IFLA_GRE_COLLECT_METADATA => info.collect_metadata = true,
+            IFLA_GRE_FLAGS => flags = parse_as_u32(d)?,
+            _ => log::debug!(
+                "Unhandled GRE info {} {:?}",
+                nla.kind(),
+                nla.value()
+            ),
+        }
+    }
+    if flags & IP6_TNL_F_IGN_ENCAP_LIMIT > 0 {
+        info.encap_limit = None;
+    }
+    if iflags & GRE_KEY > 0 {
+        info.ikey = Some(ikey);
+    }
+    if oflags & GRE_KEY > 0 {
+        info.okey = Some(okey);
+    }
+    info.icsum = iflags & GRE_CSUM > 0;
+    info.ocsum = oflags & GRE_CSUM > 0;
+    info.iseq = iflags & GRE_SEQ > 0;
+    info.oseq = oflags & GRE_SEQ > 0;
+    Ok(Some(info))
+}
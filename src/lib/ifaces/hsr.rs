@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::rtnl::link::nlas::InfoData;
+use netlink_packet_utils::nla::NlasIterator;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    mac::{parse_as_mac, ETH_ALEN},
+    netlink::{parse_as_u16, parse_as_u32, parse_as_u8},
+    ControllerType, Iface, IfaceType, NisporError,
+};
+
+const IFLA_HSR_SLAVE1: u16 = 1;
+const IFLA_HSR_SLAVE2: u16 = 2;
+const IFLA_HSR_SUPERVISION_ADDR: u16 = 4;
+const IFLA_HSR_SEQ_NR: u16 = 5;
+const IFLA_HSR_VERSION: u16 = 6;
+const IFLA_HSR_PROTOCOL: u16 = 7;
+
+const HSR_PROTOCOL_HSR: u8 = 0;
+const HSR_PROTOCOL_PRP: u8 = 1;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum HsrProtocol {
+    /* High-availability Seamless Redundancy */
+    Hsr,
+    /* Parallel Redundancy Protocol */
+    Prp,
+    Other(u8),
+    Unknown,
+}
+
+impl Default for HsrProtocol {
+    fn default() -> Self {
+        HsrProtocol::Unknown
+    }
+}
+
+impl From<u8> for HsrProtocol {
+    fn from(d: u8) -> Self {
+        match d {
+            HSR_PROTOCOL_HSR => Self::Hsr,
+            HSR_PROTOCOL_PRP => Self::Prp,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct HsrInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slave1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slave2: Option<String>,
+    /// Multicast MAC address used for supervision frames
+    pub supervision_addr: String,
+    pub seq_nr: u16,
+    /// Only available when reported by kernel
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u8>,
+    pub protocol: HsrProtocol,
+}
+
+pub(crate) fn get_hsr_info(
+    data: &InfoData,
+) -> Result<Option<HsrInfo>, NisporError> {
+    if let InfoData::Other(raw) = data {
+        let mut hsr_info = HsrInfo::default();
+        for nla in NlasIterator::new(raw) {
+            let nla = nla?;
+            match nla.kind() {
+                IFLA_HSR_SLAVE1 => {
+                    hsr_info.slave1 =
+                        Some(format!("{}", parse_as_u32(nla.value())?));
+                }
+                IFLA_HSR_SLAVE2 => {
+                    hsr_info.slave2 =
+                        Some(format!("{}", parse_as_u32(nla.value())?));
+                }
+                IFLA_HSR_SUPERVISION_ADDR => {
+                    hsr_info.supervision_addr =
+                        parse_as_mac(ETH_ALEN, nla.value())?;
+                }
+                IFLA_HSR_SEQ_NR => {
+                    hsr_info.seq_nr = parse_as_u16(nla.value())?;
+                }
+                IFLA_HSR_VERSION => {
+                    hsr_info.version = Some(parse_as_u8(nla.value())?);
+                }
+                IFLA_HSR_PROTOCOL => {
+                    hsr_info.protocol = parse_as_u8(nla.value())?.into();
+                }
+                _ => log::debug!(
+                    "Unknown HSR info {} {:?}",
+                    nla.kind(),
+                    nla.value()
+                ),
+            }
+        }
+        Ok(Some(hsr_info))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn hsr_iface_tidy_up(iface_states: &mut HashMap<String, Iface>) {
+    convert_slave_index_to_name(iface_states);
+    mark_slaves_with_controller(iface_states);
+}
+
+fn convert_slave_index_to_name(iface_states: &mut HashMap<String, Iface>) {
+    let mut index_to_name = HashMap::new();
+    for iface in iface_states.values() {
+        index_to_name.insert(format!("{}", iface.index), iface.name.clone());
+    }
+    for iface in iface_states.values_mut() {
+        if iface.iface_type != IfaceType::Hsr {
+            continue;
+        }
+        if let Some(ref mut hsr_info) = iface.hsr {
+            for slave in
+                hsr_info.slave1.iter_mut().chain(hsr_info.slave2.iter_mut())
+            {
+                if let Some(slave_name) = index_to_name.get(slave) {
+                    *slave = slave_name.clone();
+                }
+            }
+        }
+    }
+}
+
+// The kernel does not set HSR interface as master of its slaves, hence
+// no IFLA_MASTER for them.
+fn mark_slaves_with_controller(iface_states: &mut HashMap<String, Iface>) {
+    let mut slave_to_controller = HashMap::new();
+    for iface in iface_states.values() {
+        if let Some(hsr_info) = &iface.hsr {
+            for slave in hsr_info.slave1.iter().chain(hsr_info.slave2.iter()) {
+                slave_to_controller.insert(slave.clone(), iface.name.clone());
+            }
+        }
+    }
+    for (slave, controller) in slave_to_controller {
+        if let Some(slave_iface) = iface_states.get_mut(&slave) {
+            slave_iface.controller = Some(controller);
+            slave_iface.controller_type = Some(ControllerType::Hsr);
+        }
+    }
+}
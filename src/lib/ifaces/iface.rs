@@ -7,13 +7,20 @@ use netlink_packet_route::{
     IFF_ALLMULTI, IFF_AUTOMEDIA, IFF_BROADCAST, IFF_DEBUG, IFF_DORMANT,
     IFF_LOOPBACK, IFF_LOWER_UP, IFF_MASTER, IFF_MULTICAST, IFF_NOARP,
     IFF_POINTOPOINT, IFF_PORTSEL, IFF_PROMISC, IFF_RUNNING, IFF_UP,
+    RTM_DELLINKPROP,
 };
+use netlink_packet_utils::{
+    nla::{Nla, NlasIterator},
+    Emitable,
+};
+use netlink_sys::protocols::NETLINK_ROUTE;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     ip::{fill_af_spec_inet_info, IpConf, Ipv4Info, Ipv6Info},
     mac::{mac_str_to_raw, parse_as_mac},
     mptcp::MptcpAddress,
+    netlink::{netlink_request, parse_as_u32},
     NisporError, VfInfo,
 };
 
@@ -26,11 +33,20 @@ use super::{
         BridgeConf, BridgeInfo, BridgePortInfo,
     },
     ethtool::EthtoolInfo,
+    ethtool_conf::EthtoolConf,
+    geneve::{get_geneve_info, GeneveConf, GeneveInfo},
+    gre::{get_gre_info, GreInfo},
+    hsr::{get_hsr_info, HsrInfo},
     inter_ifaces::change_ifaces,
+    ip_tunnel::{get_ip_tunnel_info, IpTunnelInfo},
+    ip_vlan::{get_ip_vlan_info, IpVlanConf, IpVlanInfo},
     ipoib::{get_ipoib_info, IpoibInfo},
     mac_vlan::{get_mac_vlan_info, MacVlanInfo},
     mac_vtap::{get_mac_vtap_info, MacVtapInfo},
-    sriov::{get_sriov_info, SriovInfo},
+    macsec::{get_macsec_info, MacsecInfo},
+    sriov::{get_sriov_info, SriovConf, SriovInfo},
+    stats::{parse_iface_stats64, IfaceStats},
+    tc::TcInfo,
     tun::{get_tun_info, TunInfo},
     veth::{VethConf, VethInfo},
     vlan::{get_vlan_info, VlanConf, VlanInfo},
@@ -38,10 +54,16 @@ use super::{
         get_vrf_info, get_vrf_subordinate_info, VrfInfo, VrfSubordinateInfo,
     },
     vxlan::{get_vxlan_info, VxlanInfo},
+    wireguard::{WireguardConf, WireguardInfo},
+    xdp::{get_xdp_info, XdpInfo},
+    xfrm::{get_xfrm_info, XfrmInfo},
 };
 
 const IFF_PORT: u32 = 0x800;
 
+const IFLA_GRO_MAX_SIZE: u16 = 58;
+const IFLA_PROTO_DOWN_REASON_VALUE: u16 = 2;
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 #[serde(rename_all = "snake_case")]
 #[non_exhaustive]
@@ -61,6 +83,20 @@ pub enum IfaceType {
     MacVtap,
     OpenvSwitch,
     Ipoib,
+    Wireguard,
+    Gre,
+    GreTap,
+    Ip6Gre,
+    Ip6GreTap,
+    Ipip,
+    Sit,
+    Ip6Tnl,
+    Geneve,
+    Macsec,
+    IpVlan,
+    IpVtap,
+    Xfrm,
+    Hsr,
     Unknown,
     Other(String),
 }
@@ -92,6 +128,20 @@ impl std::fmt::Display for IfaceType {
                 Self::MacVtap => "macvtap",
                 Self::OpenvSwitch => "openvswitch",
                 Self::Ipoib => "ipoib",
+                Self::Wireguard => "wireguard",
+                Self::Gre => "gre",
+                Self::GreTap => "gretap",
+                Self::Ip6Gre => "ip6gre",
+                Self::Ip6GreTap => "ip6gretap",
+                Self::Ipip => "ipip",
+                Self::Sit => "sit",
+                Self::Ip6Tnl => "ip6tnl",
+                Self::Geneve => "geneve",
+                Self::Macsec => "macsec",
+                Self::IpVlan => "ipvlan",
+                Self::IpVtap => "ipvtap",
+                Self::Xfrm => "xfrm",
+                Self::Hsr => "hsr",
                 Self::Unknown => "unknown",
                 Self::Other(s) => s,
             }
@@ -174,6 +224,7 @@ pub enum ControllerType {
     Bridge,
     Vrf,
     OpenvSwitch,
+    Hsr,
     Other(String),
     Unknown,
 }
@@ -185,6 +236,7 @@ impl From<&str> for ControllerType {
             "bridge" => ControllerType::Bridge,
             "vrf" => ControllerType::Vrf,
             "openvswitch" => ControllerType::OpenvSwitch,
+            "hsr" => ControllerType::Hsr,
             _ => ControllerType::Other(s.to_string()),
         }
     }
@@ -203,6 +255,40 @@ pub struct Iface {
     pub min_mtu: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_mtu: Option<i64>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub alt_names: Vec<String>,
+    /// Interface alias, `ifalias` in sysfs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub txqlen: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_tx_queues: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_rx_queues: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gso_max_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gso_max_segs: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gro_max_size: Option<u32>,
+    /// Name of root queue discipline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qdisc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proto_down: Option<bool>,
+    /// Bitmap of reasons for holding the interface down, only available
+    /// when not zero
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proto_down_reason: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub carrier_changes: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub carrier_up_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub carrier_down_count: Option<u32>,
     pub flags: Vec<IfaceFlags>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ipv4: Option<Ipv4Info>,
@@ -218,6 +304,10 @@ pub struct Iface {
     pub controller_type: Option<ControllerType>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub link_netnsid: Option<i32>,
+    /// Network namespace of `link_netnsid`, name for network namespace
+    /// created by `ip netns add`, path for others.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_netns: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ethtool: Option<EthtoolInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -235,8 +325,22 @@ pub struct Iface {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub vxlan: Option<VxlanInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub geneve: Option<GeneveInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub veth: Option<VethInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub wireguard: Option<WireguardInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gre: Option<GreInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_tunnel: Option<IpTunnelInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub macsec: Option<MacsecInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xfrm: Option<XfrmInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hsr: Option<HsrInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub vrf: Option<VrfInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub vrf_subordinate: Option<VrfSubordinateInfo>,
@@ -245,6 +349,10 @@ pub struct Iface {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mac_vtap: Option<MacVtapInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_vlan: Option<IpVlanInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_vtap: Option<IpVlanInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sriov: Option<SriovInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sriov_vf: Option<VfInfo>,
@@ -252,6 +360,14 @@ pub struct Iface {
     pub ipoib: Option<IpoibInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mptcp: Option<Vec<MptcpAddress>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<IfaceStats>,
+    /// Traffic control queue disciplines, classes and filters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tc: Option<TcInfo>,
+    /// XDP programs attached to this interface
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xdp: Option<XdpInfo>,
 }
 
 // TODO: impl From Iface to IfaceConf
@@ -307,6 +423,52 @@ pub(crate) fn parse_nl_msg_to_iface(
             iface_state.controller = Some(format!("{controller}"));
         } else if let nlas::Nla::Link(l) = nla {
             link = Some(*l);
+        } else if let nlas::Nla::PropList(props) = nla {
+            for prop in props {
+                if let nlas::Prop::AltIfName(alt_name) = prop {
+                    iface_state.alt_names.push(alt_name.clone());
+                }
+            }
+        } else if let nlas::Nla::IfAlias(alias) = nla {
+            iface_state.alias = Some(alias.clone());
+        } else if let nlas::Nla::Group(group) = nla {
+            iface_state.group = Some(*group);
+        } else if let nlas::Nla::TxQueueLen(d) = nla {
+            iface_state.txqlen = Some(*d);
+        } else if let nlas::Nla::NumTxQueues(d) = nla {
+            iface_state.num_tx_queues = Some(*d);
+        } else if let nlas::Nla::NumRxQueues(d) = nla {
+            iface_state.num_rx_queues = Some(*d);
+        } else if let nlas::Nla::GsoMaxSize(d) = nla {
+            iface_state.gso_max_size = Some(*d);
+        } else if let nlas::Nla::GsoMaxSegs(d) = nla {
+            iface_state.gso_max_segs = Some(*d);
+        } else if let nlas::Nla::Qdisc(qdisc) = nla {
+            iface_state.qdisc = Some(qdisc.clone());
+        } else if let nlas::Nla::ProtoDown(d) = nla {
+            iface_state.proto_down = Some(*d > 0);
+        } else if let nlas::Nla::ProtoDownReason(data) = nla {
+            for reason_nla in NlasIterator::new(data) {
+                let reason_nla = reason_nla?;
+                if reason_nla.kind() == IFLA_PROTO_DOWN_REASON_VALUE {
+                    iface_state.proto_down_reason =
+                        Some(parse_as_u32(reason_nla.value())?);
+                }
+            }
+        } else if let nlas::Nla::CarrierChanges(d) = nla {
+            iface_state.carrier_changes = Some(*d);
+        } else if let nlas::Nla::CarrierUpCount(data) = nla {
+            iface_state.carrier_up_count = Some(parse_as_u32(data)?);
+        } else if let nlas::Nla::CarrierDownCount(data) = nla {
+            iface_state.carrier_down_count = Some(parse_as_u32(data)?);
+        } else if let nlas::Nla::Xdp(xdp_nlas) = nla {
+            iface_state.xdp = get_xdp_info(xdp_nlas);
+        } else if let nlas::Nla::Other(other_nla) = nla {
+            if other_nla.kind() == IFLA_GRO_MAX_SIZE {
+                let mut data = vec![0u8; other_nla.value_len()];
+                other_nla.emit_value(&mut data);
+                iface_state.gro_max_size = Some(parse_as_u32(&data)?);
+            }
         } else if let nlas::Nla::Info(infos) = nla {
             for info in infos {
                 if let nlas::Info::Kind(t) = info {
@@ -322,8 +484,22 @@ pub(crate) fn parse_nl_msg_to_iface(
                         nlas::InfoKind::MacVlan => IfaceType::MacVlan,
                         nlas::InfoKind::MacVtap => IfaceType::MacVtap,
                         nlas::InfoKind::Ipoib => IfaceType::Ipoib,
+                        nlas::InfoKind::Wireguard => IfaceType::Wireguard,
+                        nlas::InfoKind::GreTun => IfaceType::Gre,
+                        nlas::InfoKind::GreTap => IfaceType::GreTap,
+                        nlas::InfoKind::GreTun6 => IfaceType::Ip6Gre,
+                        nlas::InfoKind::GreTap6 => IfaceType::Ip6GreTap,
+                        nlas::InfoKind::IpTun => IfaceType::Ipip,
+                        nlas::InfoKind::SitTun => IfaceType::Sit,
+                        nlas::InfoKind::MacSec => IfaceType::Macsec,
+                        nlas::InfoKind::IpVlan => IfaceType::IpVlan,
+                        nlas::InfoKind::Xfrm => IfaceType::Xfrm,
                         nlas::InfoKind::Other(s) => match s.as_ref() {
                             "openvswitch" => IfaceType::OpenvSwitch,
+                            "ip6tnl" => IfaceType::Ip6Tnl,
+                            "geneve" => IfaceType::Geneve,
+                            "ipvtap" => IfaceType::IpVtap,
+                            "hsr" => IfaceType::Hsr,
                             _ => IfaceType::Other(s.clone()),
                         },
                         _ => IfaceType::Other(format!("{t:?}")),
@@ -360,6 +536,9 @@ pub(crate) fn parse_nl_msg_to_iface(
                         IfaceType::Vxlan => {
                             iface_state.vxlan = get_vxlan_info(d)?
                         }
+                        IfaceType::Geneve => {
+                            iface_state.geneve = get_geneve_info(d)?
+                        }
                         IfaceType::Vrf => iface_state.vrf = get_vrf_info(d),
                         IfaceType::MacVlan => {
                             iface_state.mac_vlan = get_mac_vlan_info(d)?
@@ -370,6 +549,32 @@ pub(crate) fn parse_nl_msg_to_iface(
                         IfaceType::Ipoib => {
                             iface_state.ipoib = get_ipoib_info(d);
                         }
+                        IfaceType::Gre
+                        | IfaceType::GreTap
+                        | IfaceType::Ip6Gre
+                        | IfaceType::Ip6GreTap => {
+                            iface_state.gre = get_gre_info(d)?;
+                        }
+                        IfaceType::Ipip
+                        | IfaceType::Sit
+                        | IfaceType::Ip6Tnl => {
+                            iface_state.ip_tunnel = get_ip_tunnel_info(d)?;
+                        }
+                        IfaceType::Macsec => {
+                            iface_state.macsec = get_macsec_info(d);
+                        }
+                        IfaceType::Xfrm => {
+                            iface_state.xfrm = get_xfrm_info(d);
+                        }
+                        IfaceType::Hsr => {
+                            iface_state.hsr = get_hsr_info(d)?;
+                        }
+                        IfaceType::IpVlan => {
+                            iface_state.ip_vlan = get_ip_vlan_info(d)?
+                        }
+                        IfaceType::IpVtap => {
+                            iface_state.ip_vtap = get_ip_vlan_info(d)?
+                        }
                         _ => log::warn!(
                             "Unhandled IFLA_INFO_DATA for iface type {:?}",
                             iface_state.iface_type
@@ -436,6 +641,8 @@ pub(crate) fn parse_nl_msg_to_iface(
             iface_state.link_netnsid = Some(*id);
         } else if let nlas::Nla::AfSpecInet(inet_nla) = nla {
             fill_af_spec_inet_info(&mut iface_state, inet_nla.as_slice());
+        } else if let nlas::Nla::Stats64(data) = nla {
+            iface_state.stats = Some(parse_iface_stats64(data)?);
         } else {
             // Place holder for paring more Nla
         }
@@ -455,6 +662,7 @@ pub(crate) fn parse_nl_msg_to_iface(
             IfaceType::Veth => {
                 iface_state.veth = Some(VethInfo {
                     peer: format!("{iface_index}"),
+                    ..Default::default()
                 })
             }
             IfaceType::MacVlan => {
@@ -467,6 +675,21 @@ pub(crate) fn parse_nl_msg_to_iface(
                     mac_vtap_info.base_iface = format!("{iface_index}");
                 }
             }
+            IfaceType::IpVlan => {
+                if let Some(ref mut ip_vlan_info) = iface_state.ip_vlan {
+                    ip_vlan_info.base_iface = format!("{iface_index}");
+                }
+            }
+            IfaceType::IpVtap => {
+                if let Some(ref mut ip_vtap_info) = iface_state.ip_vtap {
+                    ip_vtap_info.base_iface = format!("{iface_index}");
+                }
+            }
+            IfaceType::Macsec => {
+                if let Some(ref mut macsec_info) = iface_state.macsec {
+                    macsec_info.base_iface = format!("{iface_index}");
+                }
+            }
             _ => (),
         }
     }
@@ -578,9 +801,26 @@ pub struct IfaceConf {
     pub ipv4: Option<IpConf>,
     pub ipv6: Option<IpConf>,
     pub mac_address: Option<String>,
+    pub mtu: Option<u32>,
+    /// Empty string to remove the alias
+    pub alias: Option<String>,
+    /// Full list of alternative names, existing alternative names not
+    /// listed will be removed
+    pub alt_names: Option<Vec<String>>,
+    pub group: Option<u32>,
+    pub txqlen: Option<u32>,
+    pub promisc: Option<bool>,
+    pub allmulti: Option<bool>,
+    pub noarp: Option<bool>,
     pub veth: Option<VethConf>,
     pub bridge: Option<BridgeConf>,
     pub vlan: Option<VlanConf>,
+    pub wireguard: Option<WireguardConf>,
+    pub geneve: Option<GeneveConf>,
+    pub ip_vlan: Option<IpVlanConf>,
+    pub ip_vtap: Option<IpVlanConf>,
+    pub ethtool: Option<EthtoolConf>,
+    pub sriov: Option<SriovConf>,
 }
 
 impl IfaceConf {
@@ -627,3 +867,111 @@ pub(crate) async fn change_iface_mac(
         .await?;
     Ok(())
 }
+
+// Only the properties different from current interface are changed
+pub(crate) async fn change_iface_link_props(
+    handle: &rtnetlink::Handle,
+    iface: &IfaceConf,
+    cur_iface: &Iface,
+) -> Result<(), NisporError> {
+    let mut req = handle.link().set(cur_iface.index);
+    let mut changed = false;
+    if let Some(mtu) = iface.mtu {
+        if cur_iface.mtu != mtu as i64 {
+            req = req.mtu(mtu);
+            changed = true;
+        }
+    }
+    if let Some(promisc) = iface.promisc {
+        if cur_iface.flags.contains(&IfaceFlags::Promisc) != promisc {
+            req = req.promiscuous(promisc);
+            changed = true;
+        }
+    }
+    if let Some(noarp) = iface.noarp {
+        if cur_iface.flags.contains(&IfaceFlags::NoArp) != noarp {
+            req = req.arp(!noarp);
+            changed = true;
+        }
+    }
+    let msg = req.message_mut();
+    if let Some(allmulti) = iface.allmulti {
+        if cur_iface.flags.contains(&IfaceFlags::AllMulti) != allmulti {
+            if allmulti {
+                msg.header.flags |= IFF_ALLMULTI;
+            } else {
+                msg.header.flags &= !IFF_ALLMULTI;
+            }
+            msg.header.change_mask |= IFF_ALLMULTI;
+            changed = true;
+        }
+    }
+    if let Some(alias) = iface.alias.as_ref() {
+        if cur_iface.alias.as_deref().unwrap_or("") != alias {
+            msg.nlas.push(nlas::Nla::IfAlias(alias.to_string()));
+            changed = true;
+        }
+    }
+    if let Some(group) = iface.group {
+        if cur_iface.group != Some(group) {
+            msg.nlas.push(nlas::Nla::Group(group));
+            changed = true;
+        }
+    }
+    if let Some(txqlen) = iface.txqlen {
+        if cur_iface.txqlen != Some(txqlen) {
+            msg.nlas.push(nlas::Nla::TxQueueLen(txqlen));
+            changed = true;
+        }
+    }
+    if changed {
+        req.execute().await?;
+    }
+
+    if let Some(alt_names) = iface.alt_names.as_ref() {
+        let to_del: Vec<&str> = cur_iface
+            .alt_names
+            .iter()
+            .filter(|n| !alt_names.contains(n))
+            .map(|n| n.as_str())
+            .collect();
+        let to_add: Vec<&str> = alt_names
+            .iter()
+            .filter(|n| !cur_iface.alt_names.contains(n))
+            .map(|n| n.as_str())
+            .collect();
+        if !to_del.is_empty() {
+            del_iface_alt_names(cur_iface.index, &to_del).await?;
+        }
+        if !to_add.is_empty() {
+            handle
+                .link()
+                .property_add(cur_iface.index)
+                .alt_ifname(&to_add)
+                .execute()
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+// The rtnetlink crate sends RTM_DELLINKPROP with NLM_F_EXCL which shares
+// the same value with NLM_F_BULK for delete requests, kernel will reject
+// it with EOPNOTSUPP.
+async fn del_iface_alt_names(
+    index: u32,
+    alt_names: &[&str],
+) -> Result<(), NisporError> {
+    let mut nl_msg = LinkMessage::default();
+    nl_msg.header.index = index;
+    nl_msg.nlas.push(nlas::Nla::PropList(
+        alt_names
+            .iter()
+            .map(|n| nlas::Prop::AltIfName(n.to_string()))
+            .collect(),
+    ));
+    let mut payload = vec![0u8; nl_msg.buffer_len()];
+    nl_msg.emit(&mut payload);
+    netlink_request(NETLINK_ROUTE, RTM_DELLINKPROP, &payload).await?;
+    Ok(())
+}
@@ -4,32 +4,44 @@ use std::collections::HashMap;
 
 use futures::stream::TryStreamExt;
 use netlink_packet_route::rtnl::{
-    constants::AF_BRIDGE, AF_UNSPEC, RTEXT_FILTER_BRVLAN_COMPRESSED,
-    RTEXT_FILTER_VF,
+    constants::AF_BRIDGE, link::nlas::Nla, AF_UNSPEC,
+    RTEXT_FILTER_BRVLAN_COMPRESSED, RTEXT_FILTER_VF,
 };
-use rtnetlink::new_connection;
+use rtnetlink::{new_connection, LinkGetRequest};
 
 use super::{
     bond::bond_iface_tidy_up,
     bridge::bridge_iface_tidy_up,
     ethtool::get_ethtool_infos,
     fill_bridge_vlan_info,
-    iface::{change_iface_mac, change_iface_state},
+    hsr::hsr_iface_tidy_up,
+    iface::{change_iface_link_props, change_iface_mac, change_iface_state},
+    ip_tunnel::ip_tunnel_iface_tidy_up,
+    ip_vlan::ip_vlan_iface_tidy_up,
     ipoib::ipoib_iface_tidy_up,
     mac_vlan::mac_vlan_iface_tidy_up,
+    macsec::{fill_macsec_info, macsec_iface_tidy_up},
     parse_nl_msg_to_iface, parse_nl_msg_to_name_and_index,
     sriov::sriov_vf_iface_tidy_up,
+    stats::fill_iface_offload_stats,
+    tc::fill_tc_info,
     veth::veth_iface_tidy_up,
     vlan::vlan_iface_tidy_up,
     vrf::vrf_iface_tidy_up,
     vxlan::vxlan_iface_tidy_up,
+    wireguard::fill_wireguard_info,
+    xdp::fill_xdp_prog_info,
+    xfrm::xfrm_iface_tidy_up,
 };
 use crate::{
-    ip::change_ips, netlink::fill_ip_addr, BondConf, BridgeConf, EthtoolInfo,
-    Iface, IfaceConf, IfaceState, IfaceType, NetStateIfaceFilter, NisporError,
-    VlanConf,
+    ip::{change_ips, ip_info_tidy_up},
+    netlink::{fill_bridge_mdb_info, fill_ip_addr},
+    BondConf, BridgeConf, EthtoolInfo, Iface, IfaceConf, IfaceState, IfaceType,
+    NetStateIfaceFilter, NisporError, VlanConf, WireguardConf,
 };
 
+const IFNAMSIZ: usize = 16;
+
 pub(crate) async fn get_ifaces(
     filter: Option<&NetStateIfaceFilter>,
 ) -> Result<HashMap<String, Iface>, NisporError> {
@@ -49,7 +61,7 @@ pub(crate) async fn get_ifaces(
             link_get_handle.set_filter_mask(AF_UNSPEC as u8, RTEXT_FILTER_VF);
     }
     if let Some(iface_name) = filter.iface_name.as_ref() {
-        link_get_handle = link_get_handle.match_name(iface_name.to_string());
+        link_get_handle = link_get_match_name(link_get_handle, iface_name);
     }
 
     let mut links = link_get_handle.execute();
@@ -59,10 +71,16 @@ pub(crate) async fn get_ifaces(
         }
     }
 
+    fill_xdp_prog_info(&mut iface_states);
+
     let iface_index = filter
         .iface_name
         .as_ref()
-        .and_then(|name| iface_states.get(name))
+        .and_then(|name| {
+            iface_states.get(name).or_else(|| {
+                iface_states.values().find(|i| i.alt_names.contains(name))
+            })
+        })
         .map(|i| i.index);
 
     if filter.iface_name.is_some() && iface_index.is_none() {
@@ -87,6 +105,8 @@ pub(crate) async fn get_ifaces(
         }
     }
 
+    ip_info_tidy_up(&mut iface_states, filter.include_ip_sysctl);
+
     if filter.include_bridge_vlan {
         let mut link_get_handle = handle
             .link()
@@ -94,8 +114,7 @@ pub(crate) async fn get_ifaces(
             .set_filter_mask(AF_BRIDGE as u8, RTEXT_FILTER_BRVLAN_COMPRESSED);
 
         if let Some(iface_name) = filter.iface_name.as_ref() {
-            link_get_handle =
-                link_get_handle.match_name(iface_name.to_string());
+            link_get_handle = link_get_match_name(link_get_handle, iface_name);
         }
 
         let mut br_vlan_links = link_get_handle.execute();
@@ -104,9 +123,65 @@ pub(crate) async fn get_ifaces(
         }
     }
 
+    if filter.include_stats {
+        if let Err(e) = fill_iface_offload_stats(&mut iface_states).await {
+            // Offload statistics is considered as optional
+            log::warn!("Failed to query interface offload statistics: {}", e);
+        }
+    } else {
+        for iface in iface_states.values_mut() {
+            iface.stats = None;
+        }
+    }
+
+    if filter.include_tc {
+        if let Err(e) = fill_tc_info(&mut iface_states, iface_index).await {
+            // Traffic control is considered as optional
+            log::warn!("Failed to query traffic control info: {}", e);
+            for iface in iface_states.values_mut() {
+                iface.tc = None;
+            }
+        }
+    }
+
+    if filter.include_wireguard {
+        if let Err(e) =
+            fill_wireguard_info(&mut iface_states, iface_index).await
+        {
+            // WireGuard information is considered as optional
+            log::warn!("Failed to query WireGuard info: {}", e);
+        }
+    }
+
+    if filter.include_macsec {
+        if let Err(e) = fill_macsec_info(&mut iface_states, iface_index).await {
+            // MACsec secure channels are considered as optional
+            log::warn!("Failed to query MACsec secure channels: {}", e);
+        }
+    }
+
+    if filter.include_bridge_mdb {
+        if let Err(e) = fill_bridge_mdb_info(&mut iface_states).await {
+            // Bridge multicast database is considered as optional
+            log::warn!("Failed to query bridge multicast database: {}", e);
+            for bridge_info in
+                iface_states.values_mut().filter_map(|i| i.bridge.as_mut())
+            {
+                bridge_info.mdb = None;
+                bridge_info.multicast_router_ports = None;
+            }
+        }
+    }
+
     if filter.include_ethtool {
-        // TODO: Apply interface filter to ethtool dump also
-        match get_ethtool_infos().await {
+        // The `filter.iface_name` might be alternative name
+        let iface_name = iface_index.and_then(|index| {
+            iface_states
+                .values()
+                .find(|i| i.index == index)
+                .map(|i| i.name.clone())
+        });
+        match get_ethtool_infos(filter, iface_name.as_deref()).await {
             Ok(mut ethtool_infos) => {
                 ifaces_merge_ethool_infos(
                     &mut iface_states,
@@ -132,11 +207,33 @@ fn tidy_up(iface_states: &mut HashMap<String, Iface>) {
     vxlan_iface_tidy_up(iface_states);
     veth_iface_tidy_up(iface_states);
     vrf_iface_tidy_up(iface_states);
+    hsr_iface_tidy_up(iface_states);
     mac_vlan_iface_tidy_up(iface_states);
+    ip_vlan_iface_tidy_up(iface_states);
     ipoib_iface_tidy_up(iface_states);
+    ip_tunnel_iface_tidy_up(iface_states);
+    macsec_iface_tidy_up(iface_states);
+    xfrm_iface_tidy_up(iface_states);
     sriov_vf_iface_tidy_up(iface_states);
 }
 
+// The kernel lookup on IFLA_IFNAME also matches alternative names, but
+// it is limited to IFNAMSIZ, longer ones have to use IFLA_ALT_IFNAME.
+fn link_get_match_name(
+    mut link_get_handle: LinkGetRequest,
+    iface_name: &str,
+) -> LinkGetRequest {
+    link_get_handle = link_get_handle.match_name(iface_name.to_string());
+    if iface_name.len() >= IFNAMSIZ {
+        for nla in link_get_handle.message_mut().nlas.iter_mut() {
+            if let Nla::IfName(_) = nla {
+                *nla = Nla::AltIfName(iface_name.to_string());
+            }
+        }
+    }
+    link_get_handle
+}
+
 fn controller_iface_index_to_name(iface_states: &mut HashMap<String, Iface>) {
     let mut index_to_name = HashMap::new();
     for iface in iface_states.values() {
@@ -234,6 +331,54 @@ pub(crate) async fn create_ifaces(
                     }
                 }
             }
+            Some(IfaceType::Geneve) => {
+                if let Some(geneve_conf) = &iface.geneve {
+                    geneve_conf.create(&handle, &iface.name).await?;
+                } else {
+                    return Err(NisporError::invalid_argument(format!(
+                        "No GENEVE configuration defined for new interface {}",
+                        iface.name
+                    )));
+                }
+            }
+            Some(IfaceType::IpVlan) | Some(IfaceType::IpVtap) => {
+                let iface_type = iface.iface_type.as_ref().unwrap();
+                let ip_vlan_conf = if iface_type == &IfaceType::IpVlan {
+                    iface.ip_vlan.as_ref()
+                } else {
+                    iface.ip_vtap.as_ref()
+                };
+                let ip_vlan_conf = match ip_vlan_conf {
+                    Some(c) => c,
+                    None => {
+                        return Err(NisporError::invalid_argument(format!(
+                            "No {iface_type} configuration defined for new \
+                            interface {}",
+                            iface.name
+                        )));
+                    }
+                };
+                if let Some(base_iface_index) =
+                    cur_iface_name_2_index.get(&ip_vlan_conf.base_iface)
+                {
+                    ip_vlan_conf
+                        .create(
+                            &handle,
+                            &iface.name,
+                            iface_type,
+                            *base_iface_index,
+                        )
+                        .await?;
+                } else {
+                    return Err(NisporError::invalid_argument(format!(
+                        "Base interface {} for {iface_type} {} not found",
+                        &ip_vlan_conf.base_iface, iface.name
+                    )));
+                }
+            }
+            Some(IfaceType::Wireguard) => {
+                WireguardConf::create(&handle, &iface.name).await?;
+            }
             Some(_) => {
                 return Err(NisporError::invalid_argument(format!(
                     "Cannot create unsupported interface {:?}",
@@ -260,8 +405,73 @@ pub(crate) async fn change_ifaces(
     tokio::spawn(connection);
     change_ifaces_mac(&handle, ifaces, cur_ifaces).await?;
     change_ifaces_controller(&handle, ifaces, cur_ifaces).await?;
+    change_ifaces_link_props(&handle, ifaces, cur_ifaces).await?;
     change_ifaces_state(&handle, ifaces, cur_ifaces).await?;
     change_ips(&handle, ifaces, cur_ifaces).await?;
+    change_ifaces_wireguard(ifaces, cur_ifaces).await?;
+    change_ifaces_ethtool(ifaces, cur_ifaces).await?;
+    change_ifaces_sriov(ifaces, cur_ifaces).await?;
+    Ok(())
+}
+
+async fn change_ifaces_sriov(
+    ifaces: &[&IfaceConf],
+    cur_ifaces: &HashMap<String, Iface>,
+) -> Result<(), NisporError> {
+    for iface in ifaces {
+        if let Some(sriov_conf) = iface.sriov.as_ref() {
+            if let Some(cur_iface) = cur_ifaces.get(&iface.name) {
+                sriov_conf.apply(cur_iface).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn change_ifaces_ethtool(
+    ifaces: &[&IfaceConf],
+    cur_ifaces: &HashMap<String, Iface>,
+) -> Result<(), NisporError> {
+    for iface in ifaces {
+        if let Some(ethtool_conf) = iface.ethtool.as_ref() {
+            if let Some(cur_iface) = cur_ifaces.get(&iface.name) {
+                ethtool_conf.apply(cur_iface).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn change_ifaces_wireguard(
+    ifaces: &[&IfaceConf],
+    cur_ifaces: &HashMap<String, Iface>,
+) -> Result<(), NisporError> {
+    for iface in ifaces {
+        if let Some(wg_conf) = iface.wireguard.as_ref() {
+            if let Some(cur_iface) = cur_ifaces.get(&iface.name) {
+                if cur_iface.iface_type != IfaceType::Wireguard {
+                    return Err(NisporError::invalid_argument(format!(
+                        "Interface {} is not WireGuard interface",
+                        iface.name
+                    )));
+                }
+                wg_conf.apply(cur_iface).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn change_ifaces_link_props(
+    handle: &rtnetlink::Handle,
+    ifaces: &[&IfaceConf],
+    cur_ifaces: &HashMap<String, Iface>,
+) -> Result<(), NisporError> {
+    for iface in ifaces {
+        if let Some(cur_iface) = cur_ifaces.get(&iface.name) {
+            change_iface_link_props(handle, iface, cur_iface).await?;
+        }
+    }
     Ok(())
 }
 
@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::rtnl::link::nlas::InfoData;
+use netlink_packet_utils::nla::NlasIterator;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    netlink::{
+        parse_as_be16, parse_as_ipv4, parse_as_ipv6, parse_as_u16,
+        parse_as_u32, parse_as_u8,
+    },
+    Iface, NisporError,
+};
+
+const IFLA_IPTUN_LINK: u16 = 1;
+const IFLA_IPTUN_LOCAL: u16 = 2;
+const IFLA_IPTUN_REMOTE: u16 = 3;
+const IFLA_IPTUN_TTL: u16 = 4;
+const IFLA_IPTUN_TOS: u16 = 5;
+const IFLA_IPTUN_ENCAP_LIMIT: u16 = 6;
+const IFLA_IPTUN_FLAGS: u16 = 8;
+const IFLA_IPTUN_PMTUDISC: u16 = 10;
+const IFLA_IPTUN_ENCAP_TYPE: u16 = 15;
+const IFLA_IPTUN_ENCAP_SPORT: u16 = 17;
+const IFLA_IPTUN_ENCAP_DPORT: u16 = 18;
+const IFLA_IPTUN_COLLECT_METADATA: u16 = 19;
+
+pub(crate) const IP6_TNL_F_IGN_ENCAP_LIMIT: u32 = 0x1;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum IpTunnelEncapType {
+    None,
+    /* Foo over UDP */
+    Fou,
+    /* Generic UDP Encapsulation */
+    Gue,
+    Mpls,
+    Other(u16),
+    Unknown,
+}
+
+impl Default for IpTunnelEncapType {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+const TUNNEL_ENCAP_NONE: u16 = 0;
+const TUNNEL_ENCAP_FOU: u16 = 1;
+const TUNNEL_ENCAP_GUE: u16 = 2;
+const TUNNEL_ENCAP_MPLS: u16 = 3;
+
+impl From<u16> for IpTunnelEncapType {
+    fn from(d: u16) -> Self {
+        match d {
+            TUNNEL_ENCAP_NONE => Self::None,
+            TUNNEL_ENCAP_FOU => Self::Fou,
+            TUNNEL_ENCAP_GUE => Self::Gue,
+            TUNNEL_ENCAP_MPLS => Self::Mpls,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+// Shared by ipip, sit and ip6tnl interfaces
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IpTunnelInfo {
+    /// Interface used for tunnel traffic, None if not bound to any interface
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_iface: Option<String>,
+    pub local: String,
+    pub remote: String,
+    /// 0 means inherit from inner packet
+    pub ttl: u8,
+    /// Only for ipip and sit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tos: Option<u8>,
+    /// Only for ipip and sit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pmtudisc: Option<bool>,
+    /// Only for ip6tnl, None if encapsulation limit option is not used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encap_limit: Option<u8>,
+    pub encap_type: IpTunnelEncapType,
+    pub encap_sport: u16,
+    pub encap_dport: u16,
+    pub collect_metadata: bool,
+}
+
+pub(crate) fn get_ip_tunnel_info(
+    data: &InfoData,
+) -> Result<Option<IpTunnelInfo>, NisporError> {
+    let raw = match data {
+        InfoData::IpTun(d) | InfoData::SitTun(d) | InfoData::Other(d) => d,
+        _ => return Ok(None),
+    };
+    let mut info = IpTunnelInfo::default();
+    let mut flags = None;
+    for nla in NlasIterator::new(raw.as_slice()) {
+        let nla = nla?;
+        let d = nla.value();
+        match nla.kind() {
+            IFLA_IPTUN_LINK => info.base_iface = parse_tunnel_link(d)?,
+            IFLA_IPTUN_LOCAL => info.local = parse_tunnel_addr(d)?,
+            IFLA_IPTUN_REMOTE => info.remote = parse_tunnel_addr(d)?,
+            IFLA_IPTUN_TTL => info.ttl = parse_as_u8(d)?,
+            IFLA_IPTUN_TOS => info.tos = Some(parse_as_u8(d)?),
+            IFLA_IPTUN_PMTUDISC => info.pmtudisc = Some(parse_as_u8(d)? > 0),
+            IFLA_IPTUN_ENCAP_LIMIT => info.encap_limit = Some(parse_as_u8(d)?),
+            IFLA_IPTUN_ENCAP_TYPE => info.encap_type = parse_as_u16(d)?.into(),
+            IFLA_IPTUN_ENCAP_SPORT => info.encap_sport = parse_as_be16(d)?,
+            IFLA_IPTUN_ENCAP_DPORT => info.encap_dport = parse_as_be16(d)?,
+            IFLA_IPTUN_COLLECT_METADATA => info.collect_metadata = true,
+            // The sit is using u16 flags for ISATAP
+            IFLA_IPTUN_FLAGS if d.len() == 4 => flags = Some(parse_as_u32(d)?),
+            _ => log::debug!(
+                "Unhandled IP tunnel info {} {:?}",
+                nla.kind(),
+                nla.value()
+            ),
+        }
+    }
+    if flags.map(|f| f & IP6_TNL_F_IGN_ENCAP_LIMIT > 0) == Some(true) {
+        info.encap_limit = None;
+    }
+    Ok(Some(info))
+}
+
+// Link index 0 means not bound to any interface
+pub(crate) fn parse_tunnel_link(
+    data: &[u8],
+) -> Result<Option<String>, NisporError> {
+    Ok(match parse_as_u32(data)? {
+        0 => None,
+        i => Some(format!("{i}")),
+    })
+}
+
+pub(crate) fn parse_tunnel_addr(data: &[u8]) -> Result<String, NisporError> {
+    Ok(match data.len() {
+        4 => parse_as_ipv4(data)?.to_string(),
+        16 => parse_as_ipv6(data)?.to_string(),
+        _ => format!("{data:?}"),
+    })
+}
+
+pub(crate) fn ip_tunnel_iface_tidy_up(
+    iface_states: &mut HashMap<String, Iface>,
+) {
+    let mut index_to_name = HashMap::new();
+    for iface in iface_states.values() {
+        index_to_name.insert(format!("{}", iface.index), iface.name.clone());
+    }
+    for iface in iface_states.values_mut() {
+        let base_iface = if let Some(info) = iface.ip_tunnel.as_mut() {
+            &mut info.base_iface
+        } else if let Some(info) = iface.gre.as_mut() {
+            &mut info.base_iface
+        } else {
+            continue;
+        };
+        if let Some(name) =
+            base_iface.as_ref().and_then(|i| index_to_name.get(i))
+        {
+            *base_iface = Some(name.clone());
+        }
+    }
+}
@@ -0,0 +1,227 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::rtnl::link::nlas::{
+    Info, InfoData, InfoIpVlan, InfoKind, Nla,
+};
+use netlink_packet_utils::{nla::NlasIterator, Emitable};
+use rtnetlink::Handle;
+use serde::{Deserialize, Serialize};
+
+use crate::{netlink::parse_as_u16, Iface, IfaceType, NisporError};
+
+const IFLA_IPVLAN_MODE: u16 = 1;
+const IFLA_IPVLAN_FLAGS: u16 = 2;
+
+const IPVLAN_MODE_L2: u16 = 0;
+const IPVLAN_MODE_L3: u16 = 1;
+const IPVLAN_MODE_L3S: u16 = 2;
+
+const IPVLAN_F_PRIVATE: u16 = 0x01;
+const IPVLAN_F_VEPA: u16 = 0x02;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum IpVlanMode {
+    /* switch on L2 header of the packet */
+    L2,
+    /* switch on L3 header of the packet, the default */
+    L3,
+    /* L3 with netfilter hooks on the ingress path */
+    L3s,
+    Other(u16),
+    Unknown,
+}
+
+impl Default for IpVlanMode {
+    fn default() -> Self {
+        IpVlanMode::Unknown
+    }
+}
+
+impl From<u16> for IpVlanMode {
+    fn from(d: u16) -> Self {
+        match d {
+            IPVLAN_MODE_L2 => Self::L2,
+            IPVLAN_MODE_L3 => Self::L3,
+            IPVLAN_MODE_L3S => Self::L3s,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<IpVlanMode> for u16 {
+    fn from(v: IpVlanMode) -> u16 {
+        match v {
+            IpVlanMode::L2 => IPVLAN_MODE_L2,
+            IpVlanMode::L3 | IpVlanMode::Unknown => IPVLAN_MODE_L3,
+            IpVlanMode::L3s => IPVLAN_MODE_L3S,
+            IpVlanMode::Other(d) => d,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum IpVlanFlag {
+    /* talk to other ipvlans directly, the default */
+    Bridge,
+    /* don't talk to other ipvlans */
+    Private,
+    /* talk to other ipvlans through ext bridge */
+    Vepa,
+    Other(u16),
+    Unknown,
+}
+
+impl Default for IpVlanFlag {
+    fn default() -> Self {
+        IpVlanFlag::Unknown
+    }
+}
+
+impl From<u16> for IpVlanFlag {
+    fn from(d: u16) -> Self {
+        match d {
+            0 => Self::Bridge,
+            IPVLAN_F_PRIVATE => Self::Private,
+            IPVLAN_F_VEPA => Self::Vepa,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<IpVlanFlag> for u16 {
+    fn from(v: IpVlanFlag) -> u16 {
+        match v {
+            IpVlanFlag::Bridge | IpVlanFlag::Unknown => 0,
+            IpVlanFlag::Private => IPVLAN_F_PRIVATE,
+            IpVlanFlag::Vepa => IPVLAN_F_VEPA,
+            IpVlanFlag::Other(d) => d,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IpVlanInfo {
+    pub base_iface: String,
+    pub mode: IpVlanMode,
+    pub flag: IpVlanFlag,
+}
+
+// The ipvtap share the same netlink attributes with ipvlan, but
+// netlink-packet-route only parse the ipvlan one.
+pub(crate) fn get_ip_vlan_info(
+    data: &InfoData,
+) -> Result<Option<IpVlanInfo>, NisporError> {
+    let mut ip_vlan_info = IpVlanInfo::default();
+    match data {
+        InfoData::IpVlan(infos) => {
+            for info in infos {
+                match info {
+                    InfoIpVlan::Mode(d) => ip_vlan_info.mode = (*d).into(),
+                    InfoIpVlan::Flags(d) => ip_vlan_info.flag = (*d).into(),
+                    _ => log::warn!("Unknown IPVLAN info {:?}", info),
+                }
+            }
+            Ok(Some(ip_vlan_info))
+        }
+        InfoData::Other(raw) => {
+            for nla in NlasIterator::new(raw) {
+                let nla = nla?;
+                match nla.kind() {
+                    IFLA_IPVLAN_MODE => {
+                        ip_vlan_info.mode = parse_as_u16(nla.value())?.into()
+                    }
+                    IFLA_IPVLAN_FLAGS => {
+                        ip_vlan_info.flag = parse_as_u16(nla.value())?.into()
+                    }
+                    _ => log::warn!(
+                        "Unknown IPVTAP info {} {:?}",
+                        nla.kind(),
+                        nla.value()
+                    ),
+                }
+            }
+            Ok(Some(ip_vlan_info))
+        }
+        _ => Ok(None),
+    }
+}
+
+pub(crate) fn ip_vlan_iface_tidy_up(iface_states: &mut HashMap<String, Iface>) {
+    convert_base_iface_index_to_name(iface_states);
+}
+
+fn convert_base_iface_index_to_name(iface_states: &mut HashMap<String, Iface>) {
+    let mut index_to_name = HashMap::new();
+    for iface in iface_states.values() {
+        index_to_name.insert(format!("{}", iface.index), iface.name.clone());
+    }
+    for iface in iface_states.values_mut() {
+        if iface.iface_type != IfaceType::IpVlan
+            && iface.iface_type != IfaceType::IpVtap
+        {
+            continue;
+        }
+        if let Some(ref mut info) = iface.ip_vlan {
+            if let Some(base_iface_name) = index_to_name.get(&info.base_iface) {
+                info.base_iface = base_iface_name.clone();
+            }
+        } else if let Some(ref mut info) = iface.ip_vtap {
+            if let Some(base_iface_name) = index_to_name.get(&info.base_iface) {
+                info.base_iface = base_iface_name.clone();
+            }
+        }
+    }
+}
+
+/// Configuration used for both `IfaceType::IpVlan` and `IfaceType::IpVtap`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IpVlanConf {
+    pub base_iface: String,
+    /// Default to `IpVlanMode::L3`
+    pub mode: Option<IpVlanMode>,
+    /// Default to `IpVlanFlag::Bridge`
+    pub flag: Option<IpVlanFlag>,
+}
+
+impl IpVlanConf {
+    pub(crate) async fn create(
+        &self,
+        handle: &Handle,
+        name: &str,
+        iface_type: &IfaceType,
+        base_iface_index: u32,
+    ) -> Result<(), NisporError> {
+        let mut infos =
+            vec![InfoIpVlan::Mode(self.mode.unwrap_or(IpVlanMode::L3).into())];
+        if let Some(flag) = self.flag {
+            infos.push(InfoIpVlan::Flags(flag.into()));
+        }
+        let (kind, data) = if iface_type == &IfaceType::IpVtap {
+            let mut raw = vec![0u8; infos.as_slice().buffer_len()];
+            infos.as_slice().emit(&mut raw);
+            (InfoKind::Other("ipvtap".to_string()), InfoData::Other(raw))
+        } else {
+            (InfoKind::IpVlan, InfoData::IpVlan(infos))
+        };
+
+        let mut req = handle.link().add();
+        let nlas = &mut req.message_mut().nlas;
+        nlas.push(Nla::IfName(name.to_string()));
+        nlas.push(Nla::Link(base_iface_index));
+        nlas.push(Nla::Info(vec![Info::Kind(kind), Info::Data(data)]));
+        match req.execute().await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(NisporError::bug(format!(
+                "Failed to create new {iface_type} '{name}': {e}"
+            ))),
+        }
+    }
+}
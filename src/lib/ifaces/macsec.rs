@@ -0,0 +1,366 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::rtnl::link::nlas::{
+    InfoData, InfoMacSec, MacSecCipherId, MacSecOffload, MacSecValidation,
+};
+use netlink_packet_utils::nla::NlasIterator;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::ErrorKind,
+    netlink::{
+        genl_dump, genl_family_id, parse_as_u32, parse_as_u64, parse_as_u8,
+    },
+    Iface, IfaceType, NisporError,
+};
+
+const MACSEC_GENL_NAME: &str = "macsec";
+const MACSEC_GENL_VERSION: u8 = 1;
+
+const MACSEC_CMD_GET_TXSC: u8 = 0;
+
+const MACSEC_ATTR_IFINDEX: u16 = 1;
+const MACSEC_ATTR_TXSA_LIST: u16 = 5;
+const MACSEC_ATTR_RXSC_LIST: u16 = 6;
+
+const MACSEC_RXSC_ATTR_SCI: u16 = 1;
+const MACSEC_RXSC_ATTR_ACTIVE: u16 = 2;
+const MACSEC_RXSC_ATTR_SA_LIST: u16 = 3;
+
+const MACSEC_SA_ATTR_AN: u16 = 1;
+const MACSEC_SA_ATTR_ACTIVE: u16 = 2;
+const MACSEC_SA_ATTR_PN: u16 = 3;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum MacsecCipherSuite {
+    GcmAes128,
+    GcmAes256,
+    GcmAesXpn128,
+    GcmAesXpn256,
+    Other(u64),
+    Unknown,
+}
+
+impl Default for MacsecCipherSuite {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<&MacSecCipherId> for MacsecCipherSuite {
+    fn from(d: &MacSecCipherId) -> Self {
+        #[allow(deprecated)]
+        match d {
+            MacSecCipherId::DefaultGcmAes128 | MacSecCipherId::GcmAes128 => {
+                Self::GcmAes128
+            }
+            MacSecCipherId::GcmAes256 => Self::GcmAes256,
+            MacSecCipherId::GcmAesXpn128 => Self::GcmAesXpn128,
+            MacSecCipherId::GcmAesXpn256 => Self::GcmAesXpn256,
+            MacSecCipherId::Other(d) => Self::Other(*d),
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum MacsecValidate {
+    Disabled,
+    Check,
+    Strict,
+    Other(u8),
+    Unknown,
+}
+
+impl Default for MacsecValidate {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<&MacSecValidation> for MacsecValidate {
+    fn from(d: &MacSecValidation) -> Self {
+        match d {
+            MacSecValidation::Disabled => Self::Disabled,
+            MacSecValidation::Check => Self::Check,
+            MacSecValidation::Strict => Self::Strict,
+            MacSecValidation::Other(d) => Self::Other(*d),
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum MacsecOffload {
+    Off,
+    Phy,
+    Mac,
+    Other(u8),
+    Unknown,
+}
+
+impl Default for MacsecOffload {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<&MacSecOffload> for MacsecOffload {
+    fn from(d: &MacSecOffload) -> Self {
+        match d {
+            MacSecOffload::Off => Self::Off,
+            MacSecOffload::Phy => Self::Phy,
+            MacSecOffload::Mac => Self::Mac,
+            MacSecOffload::Other(d) => Self::Other(*d),
+            _ => Self::Unknown,
+        }
+    }
+}
+
+// No key material is included.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct MacsecInfo {
+    pub base_iface: String,
+    /// Secure Channel Identifier in hex, MAC address followed by port
+    pub sci: String,
+    pub port: u16,
+    pub cipher_suite: MacsecCipherSuite,
+    pub icv_len: u8,
+    pub encoding_sa: u8,
+    pub validate: MacsecValidate,
+    pub encrypt: bool,
+    pub protect: bool,
+    pub include_sci: bool,
+    pub end_station: bool,
+    pub scb: bool,
+    pub replay_protect: bool,
+    /// Replay window, only set when `replay_protect` is true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window: Option<u32>,
+    pub offload: MacsecOffload,
+    /// Transmit secure associations, require the macsec generic netlink
+    /// family
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_sa: Option<Vec<MacsecSa>>,
+    /// Receive secure channels, require the macsec generic netlink family
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rx_sc: Option<Vec<MacsecRxSc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct MacsecRxSc {
+    /// Secure Channel Identifier in hex
+    pub sci: String,
+    pub active: bool,
+    pub sa: Vec<MacsecSa>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct MacsecSa {
+    /// Association Number
+    pub an: u8,
+    pub active: bool,
+    /// Next packet number
+    pub next_pn: u64,
+}
+
+pub(crate) fn get_macsec_info(data: &InfoData) -> Option<MacsecInfo> {
+    if let InfoData::MacSec(infos) = data {
+        let mut macsec_info = MacsecInfo::default();
+        for info in infos {
+            match info {
+                // The SCI is in network order
+                InfoMacSec::Sci(d) => {
+                    let sci = u64::from_be(*d);
+                    macsec_info.sci = format!("{sci:016x}");
+                    macsec_info.port = sci as u16;
+                }
+                InfoMacSec::Port(d) => macsec_info.port = *d,
+                InfoMacSec::IcvLen(d) => macsec_info.icv_len = *d,
+                InfoMacSec::CipherSuite(d) => {
+                    macsec_info.cipher_suite = d.into()
+                }
+                InfoMacSec::Window(d) => macsec_info.window = Some(*d),
+                InfoMacSec::EncodingSa(d) => macsec_info.encoding_sa = *d,
+                InfoMacSec::Encrypt(d) => macsec_info.encrypt = *d > 0,
+                InfoMacSec::Protect(d) => macsec_info.protect = *d > 0,
+                InfoMacSec::IncSci(d) => macsec_info.include_sci = *d > 0,
+                InfoMacSec::Es(d) => macsec_info.end_station = *d > 0,
+                InfoMacSec::Scb(d) => macsec_info.scb = *d > 0,
+                InfoMacSec::ReplayProtect(d) => {
+                    macsec_info.replay_protect = *d > 0
+                }
+                InfoMacSec::Validation(d) => macsec_info.validate = d.into(),
+                InfoMacSec::Offload(d) => macsec_info.offload = d.into(),
+                _ => log::debug!("Unknown MACsec info {:?}", info),
+            }
+        }
+        if !macsec_info.replay_protect {
+            macsec_info.window = None;
+        }
+        Some(macsec_info)
+    } else {
+        None
+    }
+}
+
+// The kernel does not support filtering the dump by interface index.
+pub(crate) async fn fill_macsec_info(
+    iface_states: &mut HashMap<String, Iface>,
+    iface_index: Option<u32>,
+) -> Result<(), NisporError> {
+    if !iface_states.values().any(|i| {
+        i.iface_type == IfaceType::Macsec
+            && (iface_index.is_none() || iface_index == Some(i.index))
+    }) {
+        return Ok(());
+    }
+    let family_id = match genl_family_id(MACSEC_GENL_NAME).await? {
+        Some(i) => i,
+        None => {
+            log::debug!("MACsec generic netlink family not found");
+            return Ok(());
+        }
+    };
+    let replies = match genl_dump(
+        family_id,
+        MACSEC_CMD_GET_TXSC,
+        MACSEC_GENL_VERSION,
+        &[],
+    )
+    .await
+    {
+        Ok(r) => r,
+        Err(NisporError {
+            kind: ErrorKind::PermissionDeny,
+            ..
+        }) => {
+            log::info!(
+                "No permission to query MACsec secure channels, ignoring"
+            );
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+
+    let mut index_to_name = HashMap::new();
+    for iface in iface_states.values() {
+        index_to_name.insert(format!("{}", iface.index), iface.name.clone());
+    }
+
+    for reply in replies {
+        let mut index = None;
+        let mut tx_sa = Vec::new();
+        let mut rx_sc = Vec::new();
+        for nla in NlasIterator::new(reply.as_slice()) {
+            let nla = nla?;
+            match nla.kind() {
+                MACSEC_ATTR_IFINDEX => {
+                    index = Some(parse_as_u32(nla.value())?);
+                }
+                MACSEC_ATTR_TXSA_LIST => {
+                    for sa_nla in NlasIterator::new(nla.value()) {
+                        tx_sa.push(parse_macsec_sa(sa_nla?.value())?);
+                    }
+                }
+                MACSEC_ATTR_RXSC_LIST => {
+                    for sc_nla in NlasIterator::new(nla.value()) {
+                        rx_sc.push(parse_macsec_rx_sc(sc_nla?.value())?);
+                    }
+                }
+                _ => (),
+            }
+        }
+        if iface_index.is_some() && index != iface_index {
+            continue;
+        }
+        if let Some(macsec_info) = index
+            .and_then(|i| index_to_name.get(&format!("{i}")))
+            .and_then(|name| iface_states.get_mut(name))
+            .and_then(|iface| iface.macsec.as_mut())
+        {
+            macsec_info.tx_sa = Some(tx_sa);
+            macsec_info.rx_sc = Some(rx_sc);
+        }
+    }
+    Ok(())
+}
+
+fn parse_macsec_rx_sc(data: &[u8]) -> Result<MacsecRxSc, NisporError> {
+    let mut rx_sc = MacsecRxSc::default();
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        match nla.kind() {
+            MACSEC_RXSC_ATTR_SCI => {
+                let sci = u64::from_be(parse_as_u64(nla.value())?);
+                rx_sc.sci = format!("{sci:016x}");
+            }
+            MACSEC_RXSC_ATTR_ACTIVE => {
+                rx_sc.active = parse_as_u8(nla.value())? > 0;
+            }
+            MACSEC_RXSC_ATTR_SA_LIST => {
+                for sa_nla in NlasIterator::new(nla.value()) {
+                    rx_sc.sa.push(parse_macsec_sa(sa_nla?.value())?);
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(rx_sc)
+}
+
+// The key and key ID are never included.
+fn parse_macsec_sa(data: &[u8]) -> Result<MacsecSa, NisporError> {
+    let mut sa = MacsecSa::default();
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        match nla.kind() {
+            MACSEC_SA_ATTR_AN => {
+                sa.an = parse_as_u8(nla.value())?;
+            }
+            MACSEC_SA_ATTR_ACTIVE => {
+                sa.active = parse_as_u8(nla.value())? > 0;
+            }
+            // u32 for non-XPN cipher suites, u64 for XPN
+            MACSEC_SA_ATTR_PN => {
+                sa.next_pn = if nla.value().len() == 8 {
+                    parse_as_u64(nla.value())?
+                } else {
+                    parse_as_u32(nla.value())? as u64
+                };
+            }
+            _ => (),
+        }
+    }
+    Ok(sa)
+}
+
+pub(crate) fn macsec_iface_tidy_up(iface_states: &mut HashMap<String, Iface>) {
+    let mut index_to_name = HashMap::new();
+    for iface in iface_states.values() {
+        index_to_name.insert(format!("{}", iface.index), iface.name.clone());
+    }
+    for iface in iface_states.values_mut() {
+        if iface.iface_type != IfaceType::Macsec {
+            continue;
+        }
+        if let Some(ref mut macsec_info) = iface.macsec {
+            if let Some(base_iface_name) =
+                index_to_name.get(&macsec_info.base_iface)
+            {
+                macsec_info.base_iface = base_iface_name.clone();
+            }
+        }
+    }
+}
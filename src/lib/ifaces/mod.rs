@@ -3,31 +3,72 @@
 mod bond;
 mod bridge;
 mod ethtool;
+mod ethtool_conf;
+mod ethtool_stats;
+mod geneve;
+mod gre;
+mod hsr;
 mod iface;
 mod inter_ifaces;
+mod ip_tunnel;
+mod ip_vlan;
 mod ipoib;
 mod mac_vlan;
 mod mac_vtap;
+mod macsec;
 mod sriov;
+mod stats;
+mod tc;
 mod tun;
 mod veth;
 mod vlan;
 mod vrf;
 mod vxlan;
+mod wireguard;
+mod xdp;
+mod xfrm;
 
 pub use crate::ifaces::bond::*;
 pub use crate::ifaces::bridge::*;
 pub use crate::ifaces::ethtool::*;
+pub use crate::ifaces::ethtool_conf::{
+    EthtoolCoalesceConf, EthtoolConf, EthtoolLinkModeConf, EthtoolPauseConf,
+    EthtoolRingConf,
+};
+pub use crate::ifaces::ethtool_stats::{
+    EthtoolStatsHistBucket, EthtoolStatsInfo,
+};
+pub use crate::ifaces::geneve::*;
+pub use crate::ifaces::gre::GreInfo;
+pub use crate::ifaces::hsr::{HsrInfo, HsrProtocol};
 pub use crate::ifaces::iface::*;
+pub use crate::ifaces::ip_tunnel::{IpTunnelEncapType, IpTunnelInfo};
+pub use crate::ifaces::ip_vlan::{
+    IpVlanConf, IpVlanFlag, IpVlanInfo, IpVlanMode,
+};
 pub use crate::ifaces::ipoib::{IpoibInfo, IpoibMode};
 pub use crate::ifaces::mac_vlan::*;
 pub use crate::ifaces::mac_vtap::*;
+pub use crate::ifaces::macsec::{
+    MacsecCipherSuite, MacsecInfo, MacsecOffload, MacsecRxSc, MacsecSa,
+    MacsecValidate,
+};
 pub use crate::ifaces::sriov::*;
+pub use crate::ifaces::stats::{IfaceHwStats, IfaceOffloadStats, IfaceStats};
+pub use crate::ifaces::tc::{
+    TcClass, TcFilter, TcFqCodelInfo, TcHtbClassInfo, TcHtbInfo, TcInfo,
+    TcNetemInfo, TcQdisc, TcStats, TcTbfInfo,
+};
 pub use crate::ifaces::tun::*;
 pub use crate::ifaces::veth::*;
 pub use crate::ifaces::vlan::*;
 pub use crate::ifaces::vrf::*;
 pub use crate::ifaces::vxlan::*;
+pub use crate::ifaces::wireguard::{
+    WireguardConf, WireguardInfo, WireguardPeerConf, WireguardPeerInfo,
+};
+pub use crate::ifaces::xdp::{BpfProgType, XdpAttachMode, XdpInfo, XdpProgram};
+pub use crate::ifaces::xfrm::XfrmInfo;
 
 pub(crate) use crate::ifaces::inter_ifaces::{
     change_ifaces, create_ifaces, delete_ifaces, get_iface_name2index,
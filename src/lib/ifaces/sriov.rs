@@ -2,14 +2,20 @@
 
 use std::collections::HashMap;
 
-use netlink_packet_utils::nla::NlasIterator;
+use netlink_packet_route::{link::nlas, LinkMessage, RTM_SETLINK};
+use netlink_packet_utils::{
+    nla::{DefaultNla, NlasIterator},
+    Emitable,
+};
+use netlink_sys::protocols::NETLINK_ROUTE;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    mac::{parse_as_mac, ETH_ALEN, INFINIBAND_ALEN},
+    mac::{mac_str_to_raw, parse_as_mac, ETH_ALEN, INFINIBAND_ALEN},
     netlink::parse_as_u32,
     netlink::parse_as_u64,
-    Iface, IfaceType, NisporError,
+    netlink::{emit_nlas, netlink_request},
+    Iface, IfaceConf, IfaceType, NisporError, VlanProtocol,
 };
 
 const IFLA_VF_MAC: u16 = 1;
@@ -26,6 +32,9 @@ const IFLA_VF_IB_PORT_GUID: u16 = 11;
 const IFLA_VF_VLAN_LIST: u16 = 12;
 const IFLA_VF_BROADCAST: u16 = 13;
 
+const IFLA_VF_INFO: u16 = 1;
+const IFLA_VF_VLAN_INFO: u16 = 1;
+
 const IFLA_VF_LINK_STATE_AUTO: u32 = 0;
 const IFLA_VF_LINK_STATE_ENABLE: u32 = 1;
 const IFLA_VF_LINK_STATE_DISABLE: u32 = 2;
@@ -42,6 +51,15 @@ const IFLA_VF_STATS_TX_DROPPED: u16 = 8;
 
 const MAX_ADDR_LEN: usize = 32;
 
+const ETH_P_8021Q: u16 = 0x8100;
+const ETH_P_8021AD: u16 = 0x88A8;
+
+// Wait up to 10 seconds for VF interfaces to show up after changing
+// `sriov_numvfs`.
+const VF_IFACE_WAIT_RETRY: u32 = 100;
+const VF_IFACE_WAIT_INTERVAL: std::time::Duration =
+    std::time::Duration::from_millis(100);
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 #[serde(rename_all = "snake_case")]
 #[non_exhaustive]
@@ -118,6 +136,341 @@ pub struct VfInfo {
     pub ib_port_guid: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct SriovConf {
+    /// Number of VFs to enable, 0 disables SR-IOV. Changing it removes all
+    /// existing VFs before creating new ones.
+    pub total_vfs: Option<u32>,
+    pub vfs: Option<Vec<VfConf>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct VfConf {
+    pub id: u32,
+    pub mac: Option<String>,
+    /// 0 disables VLAN filter
+    pub vlan_id: Option<u32>,
+    pub qos: Option<u32>,
+    /// By default: 802.1q
+    pub vlan_protocol: Option<VlanProtocol>,
+    pub spoof_check: Option<bool>,
+    pub trust: Option<bool>,
+    pub link_state: Option<VfLinkState>,
+    /// Min Bandwidth in Mbps
+    pub min_tx_rate: Option<u32>,
+    /// Max Bandwidth in Mbps, 0 disables throttling
+    pub max_tx_rate: Option<u32>,
+    pub query_rss: Option<bool>,
+}
+
+impl SriovConf {
+    // The VF IDs are checked against the desired `total_vfs`, or current VF
+    // count when not defined.
+    fn validate_vf_ids(&self, cur_iface: &Iface) -> Result<(), NisporError> {
+        let total_vfs = self.total_vfs.unwrap_or_else(|| {
+            cur_iface
+                .sriov
+                .as_ref()
+                .map(|s| s.vfs.len() as u32)
+                .unwrap_or_default()
+        });
+        for vf in self.vfs.as_deref().unwrap_or_default() {
+            if vf.id >= total_vfs {
+                return Err(NisporError::invalid_argument(format!(
+                    "VF {} not found in interface {}, total VFs: {}",
+                    vf.id, cur_iface.name, total_vfs
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    // Only the changed VF properties are sent to kernel.
+    // The `total_vfs` should be applied by `apply_sriov_total_vfs()`
+    // beforehand.
+    pub(crate) async fn apply(
+        &self,
+        cur_iface: &Iface,
+    ) -> Result<(), NisporError> {
+        let cur_vfs: &[VfInfo] = cur_iface
+            .sriov
+            .as_ref()
+            .map(|s| s.vfs.as_slice())
+            .unwrap_or_default();
+        self.validate_vf_ids(cur_iface)?;
+
+        for vf in self.vfs.as_deref().unwrap_or_default() {
+            let nlas =
+                vf.gen_nlas(cur_vfs.iter().find(|cur_vf| cur_vf.id == vf.id))?;
+            if nlas.is_empty() {
+                continue;
+            }
+            if let Err(e) = set_vf_info(cur_iface.index, vf.id, &nlas).await {
+                log::error!(
+                    "Failed to set VF {} of interface {}: {}",
+                    vf.id,
+                    cur_iface.name,
+                    e
+                );
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl VfConf {
+    fn gen_nlas(
+        &self,
+        cur_vf: Option<&VfInfo>,
+    ) -> Result<Vec<DefaultNla>, NisporError> {
+        let mut nlas = Vec::new();
+        let vf_id = self.id.to_ne_bytes();
+
+        if let Some(mac) = self.mac.as_ref() {
+            if cur_vf.map(|v| v.mac.to_uppercase()) != Some(mac.to_uppercase())
+            {
+                let mac_raw = mac_str_to_raw(mac)?;
+                if mac_raw.len() > MAX_ADDR_LEN {
+                    return Err(NisporError::invalid_argument(format!(
+                        "Invalid VF MAC address {mac}"
+                    )));
+                }
+                // struct ifla_vf_mac
+                let mut data = vec![0u8; 4 + MAX_ADDR_LEN];
+                data[..4].copy_from_slice(&vf_id);
+                data[4..4 + mac_raw.len()].copy_from_slice(&mac_raw);
+                nlas.push(DefaultNla::new(IFLA_VF_MAC, data));
+            }
+        }
+
+        if self.vlan_id.is_some()
+            || self.qos.is_some()
+            || self.vlan_protocol.is_some()
+        {
+            let vlan_id = self
+                .vlan_id
+                .unwrap_or_else(|| cur_vf.map(|v| v.vlan_id).unwrap_or(0));
+            let qos = self
+                .qos
+                .unwrap_or_else(|| cur_vf.map(|v| v.qos).unwrap_or(0));
+            // The kernel does not report VLAN protocol, always apply it when
+            // defined.
+            if self.vlan_protocol.is_some()
+                || cur_vf.map(|v| (v.vlan_id, v.qos)) != Some((vlan_id, qos))
+            {
+                // struct ifla_vf_vlan
+                let mut data = vf_id.to_vec();
+                data.extend_from_slice(&vlan_id.to_ne_bytes());
+                data.extend_from_slice(&qos.to_ne_bytes());
+                match self.vlan_protocol {
+                    None => nlas.push(DefaultNla::new(IFLA_VF_VLAN, data)),
+                    Some(protocol) => {
+                        let protocol: u16 = match protocol {
+                            VlanProtocol::Ieee8021Q => ETH_P_8021Q,
+                            VlanProtocol::Ieee8021AD => ETH_P_8021AD,
+                            _ => {
+                                return Err(NisporError::invalid_argument(
+                                    format!(
+                                        "Unsupported VF VLAN protocol \
+                                        {protocol:?}"
+                                    ),
+                                ));
+                            }
+                        };
+                        // struct ifla_vf_vlan_info, the protocol is in
+                        // network byte order and followed by 2 bytes padding.
+                        data.extend_from_slice(&protocol.to_be_bytes());
+                        data.extend_from_slice(&[0u8; 2]);
+                        nlas.push(DefaultNla::new(
+                            IFLA_VF_VLAN_LIST,
+                            emit_nlas(&[DefaultNla::new(
+                                IFLA_VF_VLAN_INFO,
+                                data,
+                            )]),
+                        ));
+                    }
+                }
+            }
+        }
+
+        for (kind, value, cur_value) in [
+            (
+                IFLA_VF_SPOOFCHK,
+                self.spoof_check,
+                cur_vf.map(|v| v.spoof_check),
+            ),
+            (IFLA_VF_TRUST, self.trust, cur_vf.map(|v| v.trust)),
+            (
+                IFLA_VF_RSS_QUERY_EN,
+                self.query_rss,
+                cur_vf.map(|v| v.query_rss),
+            ),
+        ] {
+            if let Some(value) = value {
+                if cur_value != Some(value) {
+                    // struct ifla_vf_spoofchk, ifla_vf_trust and
+                    // ifla_vf_rss_query_en
+                    let mut data = vf_id.to_vec();
+                    data.extend_from_slice(&(value as u32).to_ne_bytes());
+                    nlas.push(DefaultNla::new(kind, data));
+                }
+            }
+        }
+
+        if let Some(link_state) = self.link_state.as_ref() {
+            if cur_vf.map(|v| &v.link_state) != Some(link_state) {
+                let link_state: u32 = match link_state {
+                    VfLinkState::Auto => IFLA_VF_LINK_STATE_AUTO,
+                    VfLinkState::Enable => IFLA_VF_LINK_STATE_ENABLE,
+                    VfLinkState::Disable => IFLA_VF_LINK_STATE_DISABLE,
+                    VfLinkState::Other(d) => *d,
+                    VfLinkState::Unknown => {
+                        return Err(NisporError::invalid_argument(
+                            "Unknown VF link state is not allowed".into(),
+                        ));
+                    }
+                };
+                // struct ifla_vf_link_state
+                let mut data = vf_id.to_vec();
+                data.extend_from_slice(&link_state.to_ne_bytes());
+                nlas.push(DefaultNla::new(IFLA_VF_LINK_STATE, data));
+            }
+        }
+
+        if self.min_tx_rate.is_some() || self.max_tx_rate.is_some() {
+            let min_tx_rate = self
+                .min_tx_rate
+                .unwrap_or_else(|| cur_vf.map(|v| v.min_tx_rate).unwrap_or(0));
+            let max_tx_rate = self
+                .max_tx_rate
+                .unwrap_or_else(|| cur_vf.map(|v| v.max_tx_rate).unwrap_or(0));
+            if cur_vf.map(|v| (v.min_tx_rate, v.max_tx_rate))
+                != Some((min_tx_rate, max_tx_rate))
+            {
+                // struct ifla_vf_rate
+                let mut data = vf_id.to_vec();
+                data.extend_from_slice(&min_tx_rate.to_ne_bytes());
+                data.extend_from_slice(&max_tx_rate.to_ne_bytes());
+                nlas.push(DefaultNla::new(IFLA_VF_RATE, data));
+            }
+        }
+        Ok(nlas)
+    }
+}
+
+async fn set_vf_info(
+    pf_index: u32,
+    vf_id: u32,
+    nlas: &[DefaultNla],
+) -> Result<(), NisporError> {
+    let mut nl_msg = LinkMessage::default();
+    nl_msg.header.index = pf_index;
+    nl_msg
+        .nlas
+        .push(nlas::Nla::VfInfoList(emit_nlas(&[DefaultNla::new(
+            IFLA_VF_INFO,
+            emit_nlas(nlas),
+        )])));
+    let mut payload = vec![0u8; nl_msg.buffer_len()];
+    nl_msg.emit(&mut payload);
+    log::debug!("Setting VF {} of interface index {}", vf_id, pf_index);
+    netlink_request(NETLINK_ROUTE, RTM_SETLINK, &payload).await?;
+    Ok(())
+}
+
+fn get_sriov_numvfs(pf_name: &str) -> Result<u32, NisporError> {
+    let path = format!("/sys/class/net/{pf_name}/device/sriov_numvfs");
+    let content = std::fs::read_to_string(&path).map_err(|e| {
+        NisporError::invalid_argument(format!(
+            "Interface {pf_name} does not support SR-IOV: {e}"
+        ))
+    })?;
+    content.trim().parse::<u32>().map_err(|e| {
+        NisporError::bug(format!("Invalid content of {path}: {content} {e}"))
+    })
+}
+
+// Changing `sriov_numvfs` is done via sysfs and the waiting on VF interfaces
+// blocks the thread up to 10 seconds, hence this function is not async and
+// should be invoked outside of tokio runtime.
+// All VF IDs are validated before any change to `sriov_numvfs`.
+// Return true if any interface has its total VFs changed.
+pub(crate) fn apply_sriov_total_vfs(
+    ifaces: &[&IfaceConf],
+    cur_ifaces: &HashMap<String, Iface>,
+) -> Result<bool, NisporError> {
+    for iface in ifaces {
+        if let (Some(sriov_conf), Some(cur_iface)) =
+            (iface.sriov.as_ref(), cur_ifaces.get(&iface.name))
+        {
+            sriov_conf.validate_vf_ids(cur_iface)?;
+        }
+    }
+
+    let mut changed = false;
+    for iface in ifaces {
+        let total_vfs = match iface.sriov.as_ref().and_then(|s| s.total_vfs) {
+            Some(t) => t,
+            None => continue,
+        };
+        if !cur_ifaces.contains_key(&iface.name) {
+            continue;
+        }
+        let cur_total_vfs = get_sriov_numvfs(&iface.name)?;
+        if total_vfs != cur_total_vfs {
+            set_sriov_numvfs(&iface.name, cur_total_vfs, total_vfs)?;
+            wait_vf_ifaces(&iface.name, total_vfs);
+            changed = true;
+        }
+    }
+    Ok(changed)
+}
+
+// Kernel refuses to change non-zero `sriov_numvfs` to another non-zero
+// value, hence set it to 0 first.
+fn set_sriov_numvfs(
+    pf_name: &str,
+    cur_total_vfs: u32,
+    total_vfs: u32,
+) -> Result<(), NisporError> {
+    let path = format!("/sys/class/net/{pf_name}/device/sriov_numvfs");
+    let write_numvfs = |count: u32| {
+        std::fs::write(&path, format!("{count}")).map_err(|e| {
+            NisporError::invalid_argument(format!(
+                "Failed to set total VFs of interface {pf_name} to \
+                {total_vfs}: {e}"
+            ))
+        })
+    };
+    if cur_total_vfs != 0 && total_vfs != 0 {
+        write_numvfs(0)?;
+    }
+    write_numvfs(total_vfs)
+}
+
+// VF bound to userspace driver like vfio-pci has no interface, hence only
+// log warning on timeout.
+fn wait_vf_ifaces(pf_name: &str, total_vfs: u32) {
+    for _ in 0..VF_IFACE_WAIT_RETRY {
+        if (0..total_vfs).all(|vf_id| is_vf_iface_ready(pf_name, vf_id)) {
+            return;
+        }
+        std::thread::sleep(VF_IFACE_WAIT_INTERVAL);
+    }
+    log::warn!("Timeout on waiting VF interfaces of {} to show up", pf_name);
+}
+
+// Device without `virtfn<sriov_id>` link in sysfs(e.g. netdevsim) does not
+// create interface for VF.
+fn is_vf_iface_ready(pf_name: &str, vf_id: u32) -> bool {
+    let virtfn_path = format!("/sys/class/net/{pf_name}/device/virtfn{vf_id}");
+    !std::path::Path::new(&virtfn_path).exists()
+        || get_vf_iface_name(pf_name, &vf_id).is_some()
+}
+
 pub(crate) fn get_sriov_info(
     pf_iface_name: &str,
     raw: &[u8],
@@ -317,6 +670,22 @@ fn read_folder(folder_path: &str) -> Vec<String> {
     folder_contents
 }
 
+// The sysfs is bound to the network namespace of mount namespace, the VF
+// interface names read from it are unreliable when querying other network
+// namespace.
+pub(crate) fn sriov_remove_sysfs_info(
+    iface_states: &mut HashMap<String, Iface>,
+) {
+    for iface in iface_states.values_mut() {
+        if let Some(sriov_info) = iface.sriov.as_mut() {
+            for vf_info in sriov_info.vfs.iter_mut() {
+                vf_info.iface_name = None;
+            }
+        }
+        iface.sriov_vf = None;
+    }
+}
+
 // Fill the VfInfo base PF state
 pub(crate) fn sriov_vf_iface_tidy_up(
     iface_states: &mut HashMap<String, Iface>,
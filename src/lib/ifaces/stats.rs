@@ -0,0 +1,206 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::{
+    link::nlas::{Stats64, Stats64Buffer},
+    RTM_GETSTATS, RTM_NEWSTATS,
+};
+use netlink_packet_utils::{nla::NlasIterator, traits::Parseable};
+use netlink_sys::protocols::NETLINK_ROUTE;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    netlink::{netlink_dump, parse_as_u32, parse_as_u64},
+    Iface, NisporError,
+};
+
+// struct if_stats_msg
+const IF_STATS_MSG_LEN: usize = 12;
+
+const IFLA_STATS_LINK_OFFLOAD_XSTATS: u16 = 4;
+const IFLA_OFFLOAD_XSTATS_CPU_HIT: u16 = 1;
+const IFLA_OFFLOAD_XSTATS_L3_STATS: u16 = 3;
+
+// struct rtnl_hw_stats64
+const RTNL_HW_STATS64_LEN: usize = 72;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IfaceStats {
+    pub rx_packets: u64,
+    pub tx_packets: u64,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+    pub rx_dropped: u64,
+    pub tx_dropped: u64,
+    pub multicast: u64,
+    pub collisions: u64,
+    // Detailed rx_errors
+    pub rx_length_errors: u64,
+    pub rx_over_errors: u64,
+    pub rx_crc_errors: u64,
+    pub rx_frame_errors: u64,
+    pub rx_fifo_errors: u64,
+    pub rx_missed_errors: u64,
+    // Detailed tx_errors
+    pub tx_aborted_errors: u64,
+    pub tx_carrier_errors: u64,
+    pub tx_fifo_errors: u64,
+    pub tx_heartbeat_errors: u64,
+    pub tx_window_errors: u64,
+    // For cslip etc
+    pub rx_compressed: u64,
+    pub tx_compressed: u64,
+    pub rx_nohandler: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offload: Option<IfaceOffloadStats>,
+}
+
+impl From<Stats64> for IfaceStats {
+    fn from(d: Stats64) -> Self {
+        Self {
+            rx_packets: d.rx_packets,
+            tx_packets: d.tx_packets,
+            rx_bytes: d.rx_bytes,
+            tx_bytes: d.tx_bytes,
+            rx_errors: d.rx_errors,
+            tx_errors: d.tx_errors,
+            rx_dropped: d.rx_dropped,
+            tx_dropped: d.tx_dropped,
+            multicast: d.multicast,
+            collisions: d.collisions,
+            rx_length_errors: d.rx_length_errors,
+            rx_over_errors: d.rx_over_errors,
+            rx_crc_errors: d.rx_crc_errors,
+            rx_frame_errors: d.rx_frame_errors,
+            rx_fifo_errors: d.rx_fifo_errors,
+            rx_missed_errors: d.rx_missed_errors,
+            tx_aborted_errors: d.tx_aborted_errors,
+            tx_carrier_errors: d.tx_carrier_errors,
+            tx_fifo_errors: d.tx_fifo_errors,
+            tx_heartbeat_errors: d.tx_heartbeat_errors,
+            tx_window_errors: d.tx_window_errors,
+            rx_compressed: d.rx_compressed,
+            tx_compressed: d.tx_compressed,
+            rx_nohandler: d.rx_nohandler,
+            offload: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IfaceOffloadStats {
+    /// Traffic handled by CPU instead of being offloaded to hardware
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_hit: Option<IfaceHwStats>,
+    /// L3 traffic counted by hardware
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub l3_stats: Option<IfaceHwStats>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IfaceHwStats {
+    pub rx_packets: u64,
+    pub tx_packets: u64,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+    pub rx_dropped: u64,
+    pub tx_dropped: u64,
+    pub multicast: u64,
+}
+
+pub(crate) fn parse_iface_stats64(
+    data: &[u8],
+) -> Result<IfaceStats, NisporError> {
+    Ok(Stats64::parse(&Stats64Buffer::new_checked(data)?)?.into())
+}
+
+// Both struct rtnl_link_stats64 and struct rtnl_hw_stats64 are sharing the
+// same layout for these properties.
+fn parse_iface_hw_stats(data: &[u8]) -> Result<IfaceHwStats, NisporError> {
+    if data.len() < RTNL_HW_STATS64_LEN {
+        return Err(NisporError::bug(format!(
+            "Got invalid offload hardware stats {data:?}"
+        )));
+    }
+    Ok(IfaceHwStats {
+        rx_packets: parse_as_u64(&data[0..8])?,
+        tx_packets: parse_as_u64(&data[8..16])?,
+        rx_bytes: parse_as_u64(&data[16..24])?,
+        tx_bytes: parse_as_u64(&data[24..32])?,
+        rx_errors: parse_as_u64(&data[32..40])?,
+        tx_errors: parse_as_u64(&data[40..48])?,
+        rx_dropped: parse_as_u64(&data[48..56])?,
+        tx_dropped: parse_as_u64(&data[56..64])?,
+        multicast: parse_as_u64(&data[64..72])?,
+    })
+}
+
+pub(crate) async fn fill_iface_offload_stats(
+    iface_states: &mut HashMap<String, Iface>,
+) -> Result<(), NisporError> {
+    let mut index_to_name = HashMap::new();
+    for iface in iface_states.values() {
+        index_to_name.insert(format!("{}", iface.index), iface.name.clone());
+    }
+
+    let mut if_stats_msg = [0u8; IF_STATS_MSG_LEN];
+    if_stats_msg[8..12].copy_from_slice(
+        &(1u32 << (IFLA_STATS_LINK_OFFLOAD_XSTATS - 1)).to_ne_bytes(),
+    );
+
+    for nl_msg in
+        netlink_dump(NETLINK_ROUTE, RTM_GETSTATS, &if_stats_msg, false).await?
+    {
+        if nl_msg.msg_type != RTM_NEWSTATS
+            || nl_msg.payload.len() < IF_STATS_MSG_LEN
+        {
+            continue;
+        }
+        let index = parse_as_u32(&nl_msg.payload[4..8])?;
+        let stats = match index_to_name
+            .get(&format!("{index}"))
+            .and_then(|n| iface_states.get_mut(n))
+            .and_then(|i| i.stats.as_mut())
+        {
+            Some(s) => s,
+            None => continue,
+        };
+        for nla in NlasIterator::new(&nl_msg.payload[IF_STATS_MSG_LEN..]) {
+            let nla = nla?;
+            if nla.kind() != IFLA_STATS_LINK_OFFLOAD_XSTATS {
+                continue;
+            }
+            let mut offload_stats = IfaceOffloadStats::default();
+            for xstats_nla in NlasIterator::new(nla.value()) {
+                let xstats_nla = xstats_nla?;
+                match xstats_nla.kind() {
+                    IFLA_OFFLOAD_XSTATS_CPU_HIT => {
+                        offload_stats.cpu_hit =
+                            Some(parse_iface_hw_stats(xstats_nla.value())?);
+                    }
+                    IFLA_OFFLOAD_XSTATS_L3_STATS => {
+                        offload_stats.l3_stats =
+                            Some(parse_iface_hw_stats(xstats_nla.value())?);
+                    }
+                    _ => log::debug!(
+                        "Unknown IFLA_STATS_LINK_OFFLOAD_XSTATS NLA {} {:?}",
+                        xstats_nla.kind(),
+                        xstats_nla.value()
+                    ),
+                }
+            }
+            if offload_stats != IfaceOffloadStats::default() {
+                stats.offload = Some(offload_stats);
+            }
+        }
+    }
+    Ok(())
+}
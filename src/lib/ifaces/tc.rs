@@ -0,0 +1,841 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// The netlink-packet-route is parsing TCA_OPTIONS as netlink attributes which
+// fails on qdisc like netem using C struct as option, hence we parse the raw
+// traffic control messages here.
+
+use std::collections::HashMap;
+
+use netlink_packet_route::{
+    RTM_GETQDISC, RTM_GETTCLASS, RTM_GETTFILTER, RTM_NEWQDISC, RTM_NEWTCLASS,
+    RTM_NEWTFILTER, TCA_CHAIN, TCA_KIND, TCA_OPTIONS, TCA_STATS, TCA_STATS2,
+};
+use netlink_packet_utils::nla::NlasIterator;
+use netlink_sys::protocols::NETLINK_ROUTE;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    netlink::{
+        netlink_dump, parse_as_i32, parse_as_string, parse_as_u32, parse_as_u64,
+    },
+    Iface, NisporError,
+};
+
+// struct tcmsg
+const TCMSG_LEN: usize = 20;
+
+const TC_H_UNSPEC: u32 = 0;
+const TC_H_ROOT: u32 = 0xFFFFFFFF;
+const TC_H_INGRESS: u32 = 0xFFFFFFF1;
+const TC_H_CLSACT: u32 = TC_H_INGRESS;
+const TC_H_MIN_INGRESS: u32 = 0xFFF2;
+const TC_H_MIN_EGRESS: u32 = 0xFFF3;
+
+const TCA_STATS_BASIC: u16 = 1;
+const TCA_STATS_RATE_EST: u16 = 2;
+const TCA_STATS_QUEUE: u16 = 3;
+const TCA_STATS_RATE_EST64: u16 = 5;
+
+const TCA_FQ_CODEL_TARGET: u16 = 1;
+const TCA_FQ_CODEL_LIMIT: u16 = 2;
+const TCA_FQ_CODEL_INTERVAL: u16 = 3;
+const TCA_FQ_CODEL_ECN: u16 = 4;
+const TCA_FQ_CODEL_FLOWS: u16 = 5;
+const TCA_FQ_CODEL_QUANTUM: u16 = 6;
+const TCA_FQ_CODEL_CE_THRESHOLD: u16 = 7;
+const TCA_FQ_CODEL_DROP_BATCH_SIZE: u16 = 8;
+const TCA_FQ_CODEL_MEMORY_LIMIT: u16 = 9;
+
+const TCA_HTB_PARMS: u16 = 1;
+const TCA_HTB_INIT: u16 = 2;
+const TCA_HTB_CEIL64: u16 = 4;
+const TCA_HTB_RATE64: u16 = 5;
+const TCA_HTB_DIRECT_QLEN: u16 = 6;
+
+const TCA_TBF_PARMS: u16 = 1;
+const TCA_TBF_RATE64: u16 = 4;
+const TCA_TBF_PRATE64: u16 = 5;
+
+const TCA_NETEM_CORRUPT: u16 = 4;
+const TCA_NETEM_REORDER: u16 = 3;
+const TCA_NETEM_RATE: u16 = 6;
+const TCA_NETEM_ECN: u16 = 7;
+const TCA_NETEM_RATE64: u16 = 8;
+const TCA_NETEM_LATENCY64: u16 = 10;
+const TCA_NETEM_JITTER64: u16 = 11;
+
+// Most classifiers are using 1 for TCA_XXX_CLASSID, the bpf classifier is
+// using 3 for TCA_BPF_CLASSID.
+const TCA_CLS_CLASSID: u16 = 1;
+const TCA_BPF_CLASSID: u16 = 3;
+
+// struct tc_ratespec
+const TC_RATESPEC_LEN: usize = 12;
+// struct tc_htb_glob
+const TC_HTB_GLOB_LEN: usize = 20;
+// struct tc_htb_opt
+const TC_HTB_OPT_LEN: usize = 44;
+// struct tc_tbf_qopt
+const TC_TBF_QOPT_LEN: usize = 36;
+// struct tc_netem_qopt
+const TC_NETEM_QOPT_LEN: usize = 24;
+// struct tc_netem_reorder and struct tc_netem_corrupt
+const TC_NETEM_PROBABILITY_LEN: usize = 8;
+// struct gnet_stats_basic
+const GNET_STATS_BASIC_LEN: usize = 12;
+// struct gnet_stats_queue
+const GNET_STATS_QUEUE_LEN: usize = 20;
+// struct gnet_stats_rate_est
+const GNET_STATS_RATE_EST_LEN: usize = 8;
+// struct tc_stats
+const TC_STATS_LEN: usize = 36;
+
+// Kernel is using PSCHED_SHIFT 6 for converting nanoseconds to psched ticks.
+const PSCHED_SHIFT: u64 = 6;
+const NSEC_PER_SEC: u128 = 1_000_000_000;
+const NSEC_PER_USEC: u64 = 1000;
+
+// Queue disciplines without classes or filter blocks, no need to query
+// classes or filters for them.
+const TC_QDISC_KINDS_WITHOUT_CLASS: [&str; 6] = [
+    "noqueue",
+    "pfifo_fast",
+    "pfifo",
+    "bfifo",
+    "clsact",
+    "ingress",
+];
+const TC_QDISC_KINDS_WITHOUT_FILTER: [&str; 7] = [
+    "noqueue",
+    "pfifo_fast",
+    "pfifo",
+    "bfifo",
+    "mq",
+    "tbf",
+    "netem",
+];
+
+const ETH_P_ALL: u16 = 0x0003;
+const ETH_P_IP: u16 = 0x0800;
+const ETH_P_ARP: u16 = 0x0806;
+const ETH_P_8021Q: u16 = 0x8100;
+const ETH_P_IPV6: u16 = 0x86DD;
+const ETH_P_8021AD: u16 = 0x88A8;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root: Option<TcQdisc>,
+    /// The ingress or clsact queue discipline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingress: Option<TcQdisc>,
+    /// Queue disciplines attached to classes or queues of other queue
+    /// disciplines
+    pub child_qdiscs: Vec<TcQdisc>,
+    pub classes: Vec<TcClass>,
+    pub filters: Vec<TcFilter>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcQdisc {
+    pub kind: String,
+    /// Handle in the format of `major:`, e.g. `1:`
+    pub handle: String,
+    /// Parent in the format of `major:minor`, or `root` or `ingress`
+    pub parent: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fq_codel: Option<TcFqCodelInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub htb: Option<TcHtbInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tbf: Option<TcTbfInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub netem: Option<TcNetemInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<TcStats>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcFqCodelInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flows: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantum: Option<u32>,
+    /// Target queue delay in microseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<u32>,
+    /// Width of moving time window in microseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ecn: Option<bool>,
+    /// ECN marking threshold in microseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ce_threshold: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drop_batch_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcHtbInfo {
+    pub rate2quantum: u32,
+    /// Minor number of the default class
+    pub default_class: u32,
+    pub direct_packets_stat: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direct_qlen: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcTbfInfo {
+    /// Bytes per second
+    pub rate: u64,
+    /// Bucket size in bytes
+    pub burst: u64,
+    /// Bytes per second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_rate: Option<u64>,
+    /// Queue size in bytes
+    pub limit: u32,
+    pub mtu: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcNetemInfo {
+    /// Queue size in packets
+    pub limit: u32,
+    /// Delay in microseconds
+    pub delay: u64,
+    /// Delay jitter in microseconds
+    pub jitter: u64,
+    /// Probability of packet loss scaled to u32, `u32::MAX` means 100%.
+    pub loss: u32,
+    /// Probability of packet duplication, scaled as `loss`.
+    pub duplicate: u32,
+    /// Probability of packet re-ordering, scaled as `loss`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reorder: Option<u32>,
+    /// Probability of packet corruption, scaled as `loss`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub corrupt: Option<u32>,
+    /// Re-order every `gap` packets
+    pub gap: u32,
+    /// Bytes per second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate: Option<u64>,
+    pub ecn: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcClass {
+    pub kind: String,
+    /// Handle in the format of `major:minor`, e.g. `1:10`
+    pub handle: String,
+    /// Parent in the format of `major:minor`, or `root`
+    pub parent: String,
+    /// Handle of the queue discipline attached to this class
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub leaf: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub htb: Option<TcHtbClassInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<TcStats>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcHtbClassInfo {
+    /// Bytes per second
+    pub rate: u64,
+    /// Bytes per second
+    pub ceil: u64,
+    /// Bucket size of `rate` in bytes
+    pub burst: u64,
+    /// Bucket size of `ceil` in bytes
+    pub cburst: u64,
+    pub quantum: u32,
+    pub level: u32,
+    pub prio: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcFilter {
+    pub kind: String,
+    /// Parent in the format of `major:minor`, or `root`, `ingress` or
+    /// `egress`
+    pub parent: String,
+    /// Protocol name like `ip`, `ipv6`, `all` or hex string of
+    /// ethernet protocol number
+    pub protocol: String,
+    pub priority: u16,
+    /// Classifier specific handle in hex string, e.g. `0x80000800`
+    pub handle: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<u32>,
+    /// Class the matched packet should be sent to, in the format of
+    /// `major:minor`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcStats {
+    pub bytes: u64,
+    pub packets: u32,
+    pub drops: u32,
+    pub overlimits: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requeues: Option<u32>,
+    pub qlen: u32,
+    pub backlog: u32,
+    /// Estimated bytes per second
+    pub bps: u64,
+    /// Estimated packets per second
+    pub pps: u64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+struct TcMsg {
+    iface_index: u32,
+    handle: u32,
+    parent: u32,
+    info: u32,
+    kind: String,
+    options: Option<Vec<u8>>,
+    stats: Option<TcStats>,
+    chain: Option<u32>,
+}
+
+pub(crate) async fn fill_tc_info(
+    iface_states: &mut HashMap<String, Iface>,
+    iface_index: Option<u32>,
+) -> Result<(), NisporError> {
+    let mut index_to_name = HashMap::new();
+    for iface in iface_states.values() {
+        index_to_name.insert(format!("{}", iface.index), iface.name.clone());
+    }
+
+    for tc_msg in dump_tc_msgs(RTM_GETQDISC, iface_index, TC_H_UNSPEC).await? {
+        let iface = match index_to_name
+            .get(&format!("{}", tc_msg.iface_index))
+            .and_then(|n| iface_states.get_mut(n))
+        {
+            Some(i) => i,
+            None => continue,
+        };
+        let tc_info = iface.tc.get_or_insert_with(TcInfo::default);
+        let parent = tc_msg.parent;
+        let qdisc = parse_tc_qdisc(tc_msg)?;
+        match parent {
+            TC_H_ROOT => tc_info.root = Some(qdisc),
+            TC_H_INGRESS => tc_info.ingress = Some(qdisc),
+            _ => tc_info.child_qdiscs.push(qdisc),
+        }
+    }
+
+    for iface in iface_states.values_mut() {
+        if let Some(tc_info) = iface.tc.as_mut() {
+            fill_tc_classes_and_filters(iface.index, tc_info).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn fill_tc_classes_and_filters(
+    iface_index: u32,
+    tc_info: &mut TcInfo,
+) -> Result<(), NisporError> {
+    let qdiscs: Vec<&TcQdisc> = tc_info
+        .root
+        .iter()
+        .chain(tc_info.child_qdiscs.iter())
+        .collect();
+
+    if qdiscs
+        .iter()
+        .any(|q| !TC_QDISC_KINDS_WITHOUT_CLASS.contains(&q.kind.as_str()))
+    {
+        for tc_msg in
+            dump_tc_msgs(RTM_GETTCLASS, Some(iface_index), TC_H_UNSPEC).await?
+        {
+            tc_info.classes.push(parse_tc_class(tc_msg)?);
+        }
+    }
+
+    // Kernel only dump filters attached to the specified parent.
+    let mut filter_parents = Vec::new();
+    for qdisc in tc_info.root.iter().chain(tc_info.child_qdiscs.iter()) {
+        if !TC_QDISC_KINDS_WITHOUT_FILTER.contains(&qdisc.kind.as_str()) {
+            if let Some(handle) = tc_handle_from_str(&qdisc.handle) {
+                filter_parents.push(handle);
+            }
+        }
+    }
+    for class in tc_info.classes.iter() {
+        if !TC_QDISC_KINDS_WITHOUT_FILTER.contains(&class.kind.as_str()) {
+            if let Some(handle) = tc_handle_from_str(&class.handle) {
+                filter_parents.push(handle);
+            }
+        }
+    }
+    match tc_info.ingress.as_ref().map(|q| q.kind.as_str()) {
+        Some("clsact") => {
+            filter_parents.push(tc_h_make(TC_H_CLSACT, TC_H_MIN_INGRESS));
+            filter_parents.push(tc_h_make(TC_H_CLSACT, TC_H_MIN_EGRESS));
+        }
+        Some(_) => filter_parents.push(TC_H_INGRESS),
+        None => (),
+    }
+
+    for parent in filter_parents {
+        for tc_msg in
+            dump_tc_msgs(RTM_GETTFILTER, Some(iface_index), parent).await?
+        {
+            tc_info.filters.push(parse_tc_filter(tc_msg)?);
+        }
+    }
+    Ok(())
+}
+
+async fn dump_tc_msgs(
+    msg_type: u16,
+    iface_index: Option<u32>,
+    parent: u32,
+) -> Result<Vec<TcMsg>, NisporError> {
+    let mut tcmsg = [0u8; TCMSG_LEN];
+    if let Some(iface_index) = iface_index {
+        tcmsg[4..8].copy_from_slice(&iface_index.to_ne_bytes());
+    }
+    tcmsg[12..16].copy_from_slice(&parent.to_ne_bytes());
+
+    let reply_type = match msg_type {
+        RTM_GETQDISC => RTM_NEWQDISC,
+        RTM_GETTCLASS => RTM_NEWTCLASS,
+        _ => RTM_NEWTFILTER,
+    };
+
+    let mut tc_msgs = Vec::new();
+    for nl_msg in netlink_dump(NETLINK_ROUTE, msg_type, &tcmsg, true).await? {
+        if nl_msg.msg_type != reply_type || nl_msg.payload.len() < TCMSG_LEN {
+            continue;
+        }
+        tc_msgs.push(parse_tc_msg(&nl_msg.payload)?);
+    }
+    Ok(tc_msgs)
+}
+
+fn parse_tc_msg(data: &[u8]) -> Result<TcMsg, NisporError> {
+    let mut tc_msg = TcMsg {
+        iface_index: parse_as_i32(&data[4..8])? as u32,
+        handle: parse_as_u32(&data[8..12])?,
+        parent: parse_as_u32(&data[12..16])?,
+        info: parse_as_u32(&data[16..20])?,
+        ..Default::default()
+    };
+    let mut stats = None;
+    let mut stats2 = None;
+    for nla in NlasIterator::new(&data[TCMSG_LEN..]) {
+        let nla = nla?;
+        match nla.kind() {
+            TCA_KIND => {
+                tc_msg.kind = parse_as_string(nla.value());
+            }
+            TCA_OPTIONS => {
+                tc_msg.options = Some(nla.value().to_vec());
+            }
+            TCA_STATS => {
+                stats = Some(parse_tc_stats(nla.value())?);
+            }
+            TCA_STATS2 => {
+                stats2 = Some(parse_tc_stats2(nla.value())?);
+            }
+            TCA_CHAIN => {
+                tc_msg.chain = Some(parse_as_u32(nla.value())?);
+            }
+            _ => (),
+        }
+    }
+    // The TCA_STATS2 is preferred as it provides requeues
+    tc_msg.stats = stats2.or(stats);
+    Ok(tc_msg)
+}
+
+fn parse_tc_qdisc(tc_msg: TcMsg) -> Result<TcQdisc, NisporError> {
+    let mut qdisc = TcQdisc {
+        handle: tc_handle_to_string(tc_msg.handle),
+        parent: tc_parent_to_string(tc_msg.parent),
+        stats: tc_msg.stats,
+        ..Default::default()
+    };
+    if let Some(opts) = tc_msg.options.as_ref() {
+        match tc_msg.kind.as_str() {
+            "fq_codel" => qdisc.fq_codel = Some(parse_fq_codel_opts(opts)?),
+            "htb" => qdisc.htb = Some(parse_htb_opts(opts)?),
+            "tbf" => qdisc.tbf = Some(parse_tbf_opts(opts)?),
+            "netem" => qdisc.netem = Some(parse_netem_opts(opts)?),
+            _ => (),
+        }
+    }
+    qdisc.kind = tc_msg.kind;
+    Ok(qdisc)
+}
+
+fn parse_tc_class(tc_msg: TcMsg) -> Result<TcClass, NisporError> {
+    let mut class = TcClass {
+        handle: tc_handle_to_string(tc_msg.handle),
+        parent: tc_parent_to_string(tc_msg.parent),
+        stats: tc_msg.stats,
+        ..Default::default()
+    };
+    if tc_msg.info != 0 {
+        class.leaf = Some(tc_handle_to_string(tc_msg.info));
+    }
+    if let Some(opts) = tc_msg.options.as_ref() {
+        if tc_msg.kind == "htb" {
+            class.htb = parse_htb_class_opts(opts)?;
+        }
+    }
+    class.kind = tc_msg.kind;
+    Ok(class)
+}
+
+fn parse_tc_filter(tc_msg: TcMsg) -> Result<TcFilter, NisporError> {
+    let mut filter = TcFilter {
+        parent: tc_filter_parent_to_string(tc_msg.parent),
+        // The tcm_info is holding priority at major and protocol at minor
+        priority: (tc_msg.info >> 16) as u16,
+        protocol: eth_protocol_to_string(u16::from_be(
+            (tc_msg.info & 0xFFFF) as u16,
+        )),
+        handle: format!("{:#x}", tc_msg.handle),
+        chain: tc_msg.chain,
+        ..Default::default()
+    };
+    if let Some(opts) = tc_msg.options.as_ref() {
+        let classid_nla_kind = if tc_msg.kind == "bpf" {
+            TCA_BPF_CLASSID
+        } else {
+            TCA_CLS_CLASSID
+        };
+        for nla in NlasIterator::new(opts) {
+            let nla = nla?;
+            if nla.kind() == classid_nla_kind {
+                filter.class_id =
+                    Some(tc_handle_to_string(parse_as_u32(nla.value())?));
+            }
+        }
+    }
+    filter.kind = tc_msg.kind;
+    Ok(filter)
+}
+
+fn parse_fq_codel_opts(data: &[u8]) -> Result<TcFqCodelInfo, NisporError> {
+    let mut info = TcFqCodelInfo::default();
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        let d = nla.value();
+        match nla.kind() {
+            TCA_FQ_CODEL_TARGET => info.target = Some(parse_as_u32(d)?),
+            TCA_FQ_CODEL_LIMIT => info.limit = Some(parse_as_u32(d)?),
+            TCA_FQ_CODEL_INTERVAL => info.interval = Some(parse_as_u32(d)?),
+            TCA_FQ_CODEL_ECN => info.ecn = Some(parse_as_u32(d)? > 0),
+            TCA_FQ_CODEL_FLOWS => info.flows = Some(parse_as_u32(d)?),
+            TCA_FQ_CODEL_QUANTUM => info.quantum = Some(parse_as_u32(d)?),
+            TCA_FQ_CODEL_CE_THRESHOLD => {
+                info.ce_threshold = Some(parse_as_u32(d)?)
+            }
+            TCA_FQ_CODEL_DROP_BATCH_SIZE => {
+                info.drop_batch_size = Some(parse_as_u32(d)?)
+            }
+            TCA_FQ_CODEL_MEMORY_LIMIT => {
+                info.memory_limit = Some(parse_as_u32(d)?)
+            }
+            _ => log::debug!(
+                "Unknown fq_codel option NLA {} {:?}",
+                nla.kind(),
+                d
+            ),
+        }
+    }
+    Ok(info)
+}
+
+fn parse_htb_opts(data: &[u8]) -> Result<TcHtbInfo, NisporError> {
+    let mut info = TcHtbInfo::default();
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        let d = nla.value();
+        match nla.kind() {
+            TCA_HTB_INIT if d.len() >= TC_HTB_GLOB_LEN => {
+                info.rate2quantum = parse_as_u32(&d[4..8])?;
+                info.default_class = parse_as_u32(&d[8..12])?;
+                info.direct_packets_stat = parse_as_u32(&d[16..20])?;
+            }
+            TCA_HTB_DIRECT_QLEN => info.direct_qlen = Some(parse_as_u32(d)?),
+            _ => log::debug!("Unknown htb option NLA {} {:?}", nla.kind(), d),
+        }
+    }
+    Ok(info)
+}
+
+fn parse_htb_class_opts(
+    data: &[u8],
+) -> Result<Option<TcHtbClassInfo>, NisporError> {
+    let mut info = None;
+    let mut rate64 = None;
+    let mut ceil64 = None;
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        let d = nla.value();
+        match nla.kind() {
+            TCA_HTB_PARMS if d.len() >= TC_HTB_OPT_LEN => {
+                let rate = parse_ratespec_rate(&d[0..TC_RATESPEC_LEN])?;
+                let ceil = parse_ratespec_rate(
+                    &d[TC_RATESPEC_LEN..2 * TC_RATESPEC_LEN],
+                )?;
+                info = Some(TcHtbClassInfo {
+                    rate,
+                    ceil,
+                    burst: psched_ticks_to_bytes(
+                        parse_as_u32(&d[24..28])?,
+                        rate,
+                    ),
+                    cburst: psched_ticks_to_bytes(
+                        parse_as_u32(&d[28..32])?,
+                        ceil,
+                    ),
+                    quantum: parse_as_u32(&d[32..36])?,
+                    level: parse_as_u32(&d[36..40])?,
+                    prio: parse_as_u32(&d[40..44])?,
+                });
+            }
+            TCA_HTB_RATE64 => rate64 = Some(parse_as_u64(d)?),
+            TCA_HTB_CEIL64 => ceil64 = Some(parse_as_u64(d)?),
+            _ => log::debug!(
+                "Unknown htb class option NLA {} {:?}",
+                nla.kind(),
+                d
+            ),
+        }
+    }
+    // Kernel only provide TCA_HTB_RATE64 and TCA_HTB_CEIL64 when rate is
+    // bigger than u32::MAX
+    if let Some(info) = info.as_mut() {
+        if let Some(rate) = rate64 {
+            info.rate = rate;
+        }
+        if let Some(ceil) = ceil64 {
+            info.ceil = ceil;
+        }
+    }
+    Ok(info)
+}
+
+fn parse_tbf_opts(data: &[u8]) -> Result<TcTbfInfo, NisporError> {
+    let mut info = TcTbfInfo::default();
+    let mut buffer = 0;
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        let d = nla.value();
+        match nla.kind() {
+            TCA_TBF_PARMS if d.len() >= TC_TBF_QOPT_LEN => {
+                info.rate = parse_ratespec_rate(&d[0..TC_RATESPEC_LEN])?;
+                let peak_rate = parse_ratespec_rate(
+                    &d[TC_RATESPEC_LEN..2 * TC_RATESPEC_LEN],
+                )?;
+                if peak_rate != 0 {
+                    info.peak_rate = Some(peak_rate);
+                }
+                info.limit = parse_as_u32(&d[24..28])?;
+                buffer = parse_as_u32(&d[28..32])?;
+                info.mtu = parse_as_u32(&d[32..36])?;
+            }
+            TCA_TBF_RATE64 => info.rate = parse_as_u64(d)?,
+            TCA_TBF_PRATE64 => info.peak_rate = Some(parse_as_u64(d)?),
+            _ => log::debug!("Unknown tbf option NLA {} {:?}", nla.kind(), d),
+        }
+    }
+    info.burst = psched_ticks_to_bytes(buffer, info.rate);
+    Ok(info)
+}
+
+fn parse_netem_opts(data: &[u8]) -> Result<TcNetemInfo, NisporError> {
+    if data.len() < TC_NETEM_QOPT_LEN {
+        return Err(NisporError::bug(format!(
+            "Got invalid netem option {data:?}"
+        )));
+    }
+    let mut info = TcNetemInfo {
+        delay: psched_ticks_to_ns(parse_as_u32(&data[0..4])?) / NSEC_PER_USEC,
+        limit: parse_as_u32(&data[4..8])?,
+        loss: parse_as_u32(&data[8..12])?,
+        gap: parse_as_u32(&data[12..16])?,
+        duplicate: parse_as_u32(&data[16..20])?,
+        jitter: psched_ticks_to_ns(parse_as_u32(&data[20..24])?)
+            / NSEC_PER_USEC,
+        ..Default::default()
+    };
+    // The struct tc_netem_qopt is followed by netlink attributes
+    for nla in NlasIterator::new(&data[TC_NETEM_QOPT_LEN..]) {
+        let nla = nla?;
+        let d = nla.value();
+        match nla.kind() {
+            TCA_NETEM_REORDER if d.len() >= TC_NETEM_PROBABILITY_LEN => {
+                info.reorder = Some(parse_as_u32(&d[0..4])?);
+            }
+            TCA_NETEM_CORRUPT if d.len() >= TC_NETEM_PROBABILITY_LEN => {
+                info.corrupt = Some(parse_as_u32(&d[0..4])?);
+            }
+            TCA_NETEM_RATE => {
+                if info.rate.is_none() {
+                    info.rate = Some(parse_as_u32(d)?.into());
+                }
+            }
+            TCA_NETEM_RATE64 => info.rate = Some(parse_as_u64(d)?),
+            TCA_NETEM_ECN => info.ecn = parse_as_u32(d)? > 0,
+            TCA_NETEM_LATENCY64 => {
+                info.delay = parse_as_u64(d)? / NSEC_PER_USEC;
+            }
+            TCA_NETEM_JITTER64 => {
+                info.jitter = parse_as_u64(d)? / NSEC_PER_USEC;
+            }
+            _ => log::debug!("Unknown netem option NLA {} {:?}", nla.kind(), d),
+        }
+    }
+    Ok(info)
+}
+
+fn parse_tc_stats(data: &[u8]) -> Result<TcStats, NisporError> {
+    if data.len() < TC_STATS_LEN {
+        return Err(NisporError::bug(format!(
+            "Got invalid struct tc_stats {data:?}"
+        )));
+    }
+    Ok(TcStats {
+        bytes: parse_as_u64(&data[0..8])?,
+        packets: parse_as_u32(&data[8..12])?,
+        drops: parse_as_u32(&data[12..16])?,
+        overlimits: parse_as_u32(&data[16..20])?,
+        bps: parse_as_u32(&data[20..24])?.into(),
+        pps: parse_as_u32(&data[24..28])?.into(),
+        qlen: parse_as_u32(&data[28..32])?,
+        backlog: parse_as_u32(&data[32..36])?,
+        requeues: None,
+    })
+}
+
+fn parse_tc_stats2(data: &[u8]) -> Result<TcStats, NisporError> {
+    let mut stats = TcStats::default();
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        let d = nla.value();
+        match nla.kind() {
+            TCA_STATS_BASIC if d.len() >= GNET_STATS_BASIC_LEN => {
+                stats.bytes = parse_as_u64(&d[0..8])?;
+                stats.packets = parse_as_u32(&d[8..12])?;
+            }
+            TCA_STATS_QUEUE if d.len() >= GNET_STATS_QUEUE_LEN => {
+                stats.qlen = parse_as_u32(&d[0..4])?;
+                stats.backlog = parse_as_u32(&d[4..8])?;
+                stats.drops = parse_as_u32(&d[8..12])?;
+                stats.requeues = Some(parse_as_u32(&d[12..16])?);
+                stats.overlimits = parse_as_u32(&d[16..20])?;
+            }
+            TCA_STATS_RATE_EST if d.len() >= GNET_STATS_RATE_EST_LEN => {
+                stats.bps = parse_as_u32(&d[0..4])?.into();
+                stats.pps = parse_as_u32(&d[4..8])?.into();
+            }
+            TCA_STATS_RATE_EST64 if d.len() >= 2 * GNET_STATS_RATE_EST_LEN => {
+                stats.bps = parse_as_u64(&d[0..8])?;
+                stats.pps = parse_as_u64(&d[8..16])?;
+            }
+            _ => (),
+        }
+    }
+    Ok(stats)
+}
+
+// The rate of struct tc_ratespec is at the last 4 bytes.
+fn parse_ratespec_rate(data: &[u8]) -> Result<u64, NisporError> {
+    Ok(parse_as_u32(&data[8..12])?.into())
+}
+
+fn psched_ticks_to_ns(ticks: u32) -> u64 {
+    u64::from(ticks) << PSCHED_SHIFT
+}
+
+fn psched_ticks_to_bytes(ticks: u32, rate: u64) -> u64 {
+    (u128::from(psched_ticks_to_ns(ticks)) * u128::from(rate) / NSEC_PER_SEC)
+        as u64
+}
+
+fn tc_h_make(major: u32, minor: u32) -> u32 {
+    (major & 0xFFFF0000) | (minor & 0x0000FFFF)
+}
+
+fn tc_handle_to_string(handle: u32) -> String {
+    let major = handle >> 16;
+    let minor = handle & 0xFFFF;
+    if minor == 0 {
+        format!("{major:x}:")
+    } else {
+        format!("{major:x}:{minor:x}")
+    }
+}
+
+fn tc_handle_from_str(handle: &str) -> Option<u32> {
+    let (major, minor) = handle.split_once(':')?;
+    let major = u32::from_str_radix(major, 16).ok()?;
+    let minor = if minor.is_empty() {
+        0
+    } else {
+        u32::from_str_radix(minor, 16).ok()?
+    };
+    Some(tc_h_make(major << 16, minor))
+}
+
+fn tc_parent_to_string(parent: u32) -> String {
+    match parent {
+        TC_H_ROOT => "root".to_string(),
+        TC_H_INGRESS => "ingress".to_string(),
+        _ => tc_handle_to_string(parent),
+    }
+}
+
+fn tc_filter_parent_to_string(parent: u32) -> String {
+    if parent == tc_h_make(TC_H_CLSACT, TC_H_MIN_INGRESS)
+        || parent == TC_H_INGRESS
+        || parent == tc_h_make(TC_H_INGRESS, 0)
+    {
+        "ingress".to_string()
+    } else if parent == tc_h_make(TC_H_CLSACT, TC_H_MIN_EGRESS) {
+        "egress".to_string()
+    } else {
+        tc_parent_to_string(parent)
+    }
+}
+
+fn eth_protocol_to_string(protocol: u16) -> String {
+    match protocol {
+        ETH_P_ALL => "all".to_string(),
+        ETH_P_IP => "ip".to_string(),
+        ETH_P_ARP => "arp".to_string(),
+        ETH_P_8021Q => "802.1q".to_string(),
+        ETH_P_IPV6 => "ipv6".to_string(),
+        ETH_P_8021AD => "802.1ad".to_string(),
+        _ => format!("0x{protocol:04x}"),
+    }
+}
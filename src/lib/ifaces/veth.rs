@@ -11,8 +11,13 @@ use crate::{Iface, IfaceType, NisporError};
 #[non_exhaustive]
 pub struct VethInfo {
     // Interface name of peer.
-    // Use interface index number when peer interface is in other namespace.
+    // Use interface index number when peer interface is in other namespace
+    // and its name cannot be resolved.
     pub peer: String,
+    /// Network namespace of peer, name for network namespace created by
+    /// `ip netns add`, path for others.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peer_netns: Option<String>,
 }
 
 pub type VethConf = VethInfo;
@@ -55,10 +60,11 @@ pub(crate) fn veth_iface_tidy_up(iface_states: &mut HashMap<String, Iface>) {
             continue;
         }
 
-        if let Some(VethInfo { peer }) = &iface.veth {
+        if let Some(VethInfo { peer, .. }) = &iface.veth {
             if let Some(peer_iface_name) = index_to_name.get(peer) {
                 iface.veth = Some(VethInfo {
                     peer: peer_iface_name.clone(),
+                    ..Default::default()
                 })
             }
         }
@@ -0,0 +1,638 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
+
+use netlink_packet_route::rtnl::link::nlas::{Info, InfoKind, Nla};
+use netlink_packet_utils::nla::{DefaultNla, NlasIterator};
+use rtnetlink::Handle;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::ErrorKind,
+    netlink::{
+        emit_nlas, genl_dump, genl_family_id, genl_request, parse_as_ipv4,
+        parse_as_ipv6, parse_as_u16, parse_as_u32, parse_as_u64, parse_as_u8,
+    },
+    Iface, IfaceType, NisporError,
+};
+
+const WG_GENL_NAME: &str = "wireguard";
+const WG_GENL_VERSION: u8 = 1;
+
+const WG_CMD_GET_DEVICE: u8 = 0;
+const WG_CMD_SET_DEVICE: u8 = 1;
+
+const WG_KEY_LEN: usize = 32;
+
+const NLA_F_NESTED: u16 = 0x8000;
+
+const WGDEVICE_A_IFINDEX: u16 = 1;
+const WGDEVICE_A_PRIVATE_KEY: u16 = 3;
+const WGDEVICE_A_PUBLIC_KEY: u16 = 4;
+const WGDEVICE_A_LISTEN_PORT: u16 = 6;
+const WGDEVICE_A_FWMARK: u16 = 7;
+const WGDEVICE_A_PEERS: u16 = 8;
+
+const WGPEER_F_REMOVE_ME: u32 = 1 << 0;
+const WGPEER_F_REPLACE_ALLOWEDIPS: u32 = 1 << 1;
+
+const WGPEER_A_PUBLIC_KEY: u16 = 1;
+const WGPEER_A_FLAGS: u16 = 3;
+const WGPEER_A_ENDPOINT: u16 = 4;
+const WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL: u16 = 5;
+const WGPEER_A_LAST_HANDSHAKE_TIME: u16 = 6;
+const WGPEER_A_RX_BYTES: u16 = 7;
+const WGPEER_A_TX_BYTES: u16 = 8;
+const WGPEER_A_ALLOWEDIPS: u16 = 9;
+
+const WGALLOWEDIP_A_FAMILY: u16 = 1;
+const WGALLOWEDIP_A_IPADDR: u16 = 2;
+const WGALLOWEDIP_A_CIDR_MASK: u16 = 3;
+
+// The private key and preshared key are never included.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct WireguardInfo {
+    /// Base64 encoded public key, None if private key is not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    pub listen_port: u16,
+    pub fwmark: u32,
+    pub peers: Vec<WireguardPeerInfo>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct WireguardPeerInfo {
+    /// Base64 encoded public key
+    pub public_key: String,
+    /// In the format of `192.0.2.1:51820` or `[2001:db8::1]:51820`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// In the format of `198.51.100.0/24`
+    pub allowed_ips: Vec<String>,
+    /// Seconds since UNIX epoch, None if no handshake happened yet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_handshake_time: Option<u64>,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    /// Seconds, 0 means disabled
+    pub persistent_keepalive_interval: u16,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct WireguardConf {
+    /// Path to the file holding the base64 encoded private key, the output
+    /// format of `wg genkey`.
+    pub private_key_file: Option<String>,
+    pub listen_port: Option<u16>,
+    pub fwmark: Option<u32>,
+    /// Full list of desired peers, existing peers not listed will be
+    /// removed. When set to None, peers are not changed.
+    pub peers: Option<Vec<WireguardPeerConf>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct WireguardPeerConf {
+    /// Base64 encoded public key
+    pub public_key: String,
+    /// In the format of `192.0.2.1:51820` or `[2001:db8::1]:51820`
+    pub endpoint: Option<String>,
+    /// Replace the allowed IPs of this peer. In the format of
+    /// `198.51.100.0/24`. When set to None, allowed IPs are not changed.
+    pub allowed_ips: Option<Vec<String>>,
+    /// Seconds, 0 means disabled
+    pub persistent_keepalive_interval: Option<u16>,
+}
+
+impl WireguardConf {
+    pub(crate) async fn create(
+        handle: &Handle,
+        name: &str,
+    ) -> Result<(), NisporError> {
+        let mut req = handle.link().add();
+        req.message_mut().nlas.push(Nla::IfName(name.to_string()));
+        req.message_mut()
+            .nlas
+            .push(Nla::Info(vec![Info::Kind(InfoKind::Wireguard)]));
+        match req.execute().await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(NisporError::bug(format!(
+                "Failed to create new WireGuard interface '{name}': {e}"
+            ))),
+        }
+    }
+
+    // Only the changed properties and peers are sent to kernel.
+    pub(crate) async fn apply(
+        &self,
+        cur_iface: &Iface,
+    ) -> Result<(), NisporError> {
+        let default_wg_info = WireguardInfo::default();
+        let cur_wg_info =
+            cur_iface.wireguard.as_ref().unwrap_or(&default_wg_info);
+
+        let mut nlas = vec![DefaultNla::new(
+            WGDEVICE_A_IFINDEX,
+            cur_iface.index.to_ne_bytes().to_vec(),
+        )];
+        if let Some(path) = self.private_key_file.as_ref() {
+            nlas.push(DefaultNla::new(
+                WGDEVICE_A_PRIVATE_KEY,
+                read_key_file(path)?,
+            ));
+        }
+        if let Some(listen_port) = self.listen_port {
+            if listen_port != cur_wg_info.listen_port {
+                nlas.push(DefaultNla::new(
+                    WGDEVICE_A_LISTEN_PORT,
+                    listen_port.to_ne_bytes().to_vec(),
+                ));
+            }
+        }
+        if let Some(fwmark) = self.fwmark {
+            if fwmark != cur_wg_info.fwmark {
+                nlas.push(DefaultNla::new(
+                    WGDEVICE_A_FWMARK,
+                    fwmark.to_ne_bytes().to_vec(),
+                ));
+            }
+        }
+        if let Some(peers) = self.peers.as_ref() {
+            let peer_nlas = gen_peers_nlas(peers, &cur_wg_info.peers)?;
+            if !peer_nlas.is_empty() {
+                nlas.push(DefaultNla::new(
+                    WGDEVICE_A_PEERS | NLA_F_NESTED,
+                    emit_nlas(&peer_nlas),
+                ));
+            }
+        }
+        if nlas.len() == 1 {
+            return Ok(());
+        }
+
+        let family_id = match genl_family_id(WG_GENL_NAME).await? {
+            Some(i) => i,
+            None => {
+                let e = NisporError::bug(
+                    "WireGuard generic netlink family not found".into(),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        };
+        if let Err(e) =
+            genl_request(family_id, WG_CMD_SET_DEVICE, WG_GENL_VERSION, &nlas)
+                .await
+        {
+            log::error!(
+                "Failed to set WireGuard configure of {}: {}",
+                cur_iface.name,
+                e
+            );
+            return Err(e);
+        }
+        Ok(())
+    }
+}
+
+fn gen_peers_nlas(
+    peers: &[WireguardPeerConf],
+    cur_peers: &[WireguardPeerInfo],
+) -> Result<Vec<DefaultNla>, NisporError> {
+    let mut peer_nlas = Vec::new();
+    let mut cur_peer_map = HashMap::new();
+    for cur_peer in cur_peers {
+        cur_peer_map.insert(cur_peer.public_key.as_str(), cur_peer);
+    }
+    let mut desired_keys = HashSet::new();
+
+    for peer in peers {
+        let public_key = decode_key(&peer.public_key)?;
+        desired_keys.insert(base64_encode(&public_key));
+        let cur_peer = cur_peer_map.get(base64_encode(&public_key).as_str());
+
+        let mut nlas = Vec::new();
+        let mut flags = 0u32;
+        if let Some(endpoint) = peer.endpoint.as_ref() {
+            let endpoint = parse_endpoint(endpoint)?;
+            if cur_peer.and_then(|p| p.endpoint.as_ref())
+                != Some(&endpoint.to_string())
+            {
+                nlas.push(DefaultNla::new(
+                    WGPEER_A_ENDPOINT,
+                    gen_sockaddr(&endpoint),
+                ));
+            }
+        }
+        if let Some(interval) = peer.persistent_keepalive_interval {
+            if cur_peer.map(|p| p.persistent_keepalive_interval)
+                != Some(interval)
+            {
+                nlas.push(DefaultNla::new(
+                    WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL,
+                    interval.to_ne_bytes().to_vec(),
+                ));
+            }
+        }
+        if let Some(allowed_ips) = peer.allowed_ips.as_ref() {
+            let mut ips = Vec::new();
+            for ip in allowed_ips {
+                ips.push(parse_allowed_ip_conf(ip)?);
+            }
+            let mut ip_strs: Vec<String> =
+                ips.iter().map(|(a, p)| format!("{a}/{p}")).collect();
+            let mut cur_ip_strs =
+                cur_peer.map(|p| p.allowed_ips.clone()).unwrap_or_default();
+            ip_strs.sort_unstable();
+            cur_ip_strs.sort_unstable();
+            if cur_peer.is_none() || ip_strs != cur_ip_strs {
+                flags |= WGPEER_F_REPLACE_ALLOWEDIPS;
+                let ip_nlas: Vec<DefaultNla> = ips
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (addr, prefix_len))| {
+                        DefaultNla::new(
+                            i as u16 | NLA_F_NESTED,
+                            gen_allowed_ip_nlas(addr, *prefix_len),
+                        )
+                    })
+                    .collect();
+                nlas.push(DefaultNla::new(
+                    WGPEER_A_ALLOWEDIPS | NLA_F_NESTED,
+                    emit_nlas(&ip_nlas),
+                ));
+            }
+        }
+        if cur_peer.is_none() || !nlas.is_empty() {
+            nlas.insert(0, DefaultNla::new(WGPEER_A_PUBLIC_KEY, public_key));
+            if flags != 0 {
+                nlas.push(DefaultNla::new(
+                    WGPEER_A_FLAGS,
+                    flags.to_ne_bytes().to_vec(),
+                ));
+            }
+            peer_nlas.push(DefaultNla::new(
+                peer_nlas.len() as u16 | NLA_F_NESTED,
+                emit_nlas(&nlas),
+            ));
+        }
+    }
+
+    for cur_peer in cur_peers {
+        if !desired_keys.contains(&cur_peer.public_key) {
+            let nlas = vec![
+                DefaultNla::new(
+                    WGPEER_A_PUBLIC_KEY,
+                    decode_key(&cur_peer.public_key)?,
+                ),
+                DefaultNla::new(
+                    WGPEER_A_FLAGS,
+                    WGPEER_F_REMOVE_ME.to_ne_bytes().to_vec(),
+                ),
+            ];
+            peer_nlas.push(DefaultNla::new(
+                peer_nlas.len() as u16 | NLA_F_NESTED,
+                emit_nlas(&nlas),
+            ));
+        }
+    }
+    Ok(peer_nlas)
+}
+
+fn gen_allowed_ip_nlas(addr: &IpAddr, prefix_len: u8) -> Vec<u8> {
+    let (family, addr_bytes) = match addr {
+        IpAddr::V4(a) => (libc::AF_INET as u16, a.octets().to_vec()),
+        IpAddr::V6(a) => (libc::AF_INET6 as u16, a.octets().to_vec()),
+    };
+    emit_nlas(&[
+        DefaultNla::new(WGALLOWEDIP_A_FAMILY, family.to_ne_bytes().to_vec()),
+        DefaultNla::new(WGALLOWEDIP_A_IPADDR, addr_bytes),
+        DefaultNla::new(WGALLOWEDIP_A_CIDR_MASK, vec![prefix_len]),
+    ])
+}
+
+fn parse_allowed_ip_conf(ip: &str) -> Result<(IpAddr, u8), NisporError> {
+    let (addr_str, prefix_len_str) = match ip.split_once('/') {
+        Some((a, p)) => (a, Some(p)),
+        None => (ip, None),
+    };
+    let addr = addr_str
+        .parse::<IpAddr>()
+        .map_err(|_| invalid_allowed_ip(ip))?;
+    let max_prefix_len = if addr.is_ipv4() { 32 } else { 128 };
+    let prefix_len = match prefix_len_str {
+        Some(p) => p.parse::<u8>().map_err(|_| invalid_allowed_ip(ip))?,
+        None => max_prefix_len,
+    };
+    if prefix_len > max_prefix_len {
+        return Err(invalid_allowed_ip(ip));
+    }
+    Ok((addr, prefix_len))
+}
+
+fn invalid_allowed_ip(ip: &str) -> NisporError {
+    let e = NisporError::invalid_argument(format!(
+        "Invalid WireGuard allowed IP {ip}"
+    ));
+    log::error!("{}", e);
+    e
+}
+
+fn parse_endpoint(endpoint: &str) -> Result<SocketAddr, NisporError> {
+    endpoint.parse::<SocketAddr>().map_err(|_| {
+        let e = NisporError::invalid_argument(format!(
+            "Invalid WireGuard endpoint {endpoint}, should be in the format \
+            of IP:PORT"
+        ));
+        log::error!("{}", e);
+        e
+    })
+}
+
+// Generate struct sockaddr_in or sockaddr_in6
+fn gen_sockaddr(addr: &SocketAddr) -> Vec<u8> {
+    let mut ret = Vec::new();
+    match addr {
+        SocketAddr::V4(a) => {
+            ret.extend_from_slice(&(libc::AF_INET as u16).to_ne_bytes());
+            ret.extend_from_slice(&a.port().to_be_bytes());
+            ret.extend_from_slice(&a.ip().octets());
+            ret.extend_from_slice(&[0u8; 8]);
+        }
+        SocketAddr::V6(a) => {
+            ret.extend_from_slice(&(libc::AF_INET6 as u16).to_ne_bytes());
+            ret.extend_from_slice(&a.port().to_be_bytes());
+            ret.extend_from_slice(&a.flowinfo().to_be_bytes());
+            ret.extend_from_slice(&a.ip().octets());
+            ret.extend_from_slice(&a.scope_id().to_ne_bytes());
+        }
+    }
+    ret
+}
+
+fn read_key_file(path: &str) -> Result<Vec<u8>, NisporError> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => decode_key(content.trim()),
+        Err(e) => {
+            let e = NisporError::invalid_argument(format!(
+                "Failed to read WireGuard private key file {path}: {e}"
+            ));
+            log::error!("{}", e);
+            Err(e)
+        }
+    }
+}
+
+// Do not include the key in error message as it might be private key
+fn decode_key(key: &str) -> Result<Vec<u8>, NisporError> {
+    match base64_decode(key) {
+        Some(k) if k.len() == WG_KEY_LEN => Ok(k),
+        _ => {
+            let e = NisporError::invalid_argument(
+                "Invalid WireGuard key, should be 32 bytes encoded in base64"
+                    .into(),
+            );
+            log::error!("{}", e);
+            Err(e)
+        }
+    }
+}
+
+pub(crate) async fn fill_wireguard_info(
+    iface_states: &mut HashMap<String, Iface>,
+    iface_index: Option<u32>,
+) -> Result<(), NisporError> {
+    let wg_iface_indexes: Vec<u32> = iface_states
+        .values()
+        .filter(|i| {
+            i.iface_type == IfaceType::Wireguard
+                && (iface_index.is_none() || iface_index == Some(i.index))
+        })
+        .map(|i| i.index)
+        .collect();
+    if wg_iface_indexes.is_empty() {
+        return Ok(());
+    }
+    let family_id = match genl_family_id(WG_GENL_NAME).await? {
+        Some(i) => i,
+        None => {
+            log::debug!("WireGuard generic netlink family not found");
+            return Ok(());
+        }
+    };
+
+    let mut index_to_name = HashMap::new();
+    for iface in iface_states.values() {
+        index_to_name.insert(format!("{}", iface.index), iface.name.clone());
+    }
+
+    for index in wg_iface_indexes {
+        let nlas = vec![DefaultNla::new(
+            WGDEVICE_A_IFINDEX,
+            index.to_ne_bytes().to_vec(),
+        )];
+        let replies = match genl_dump(
+            family_id,
+            WG_CMD_GET_DEVICE,
+            WG_GENL_VERSION,
+            &nlas,
+        )
+        .await
+        {
+            Ok(r) => r,
+            Err(NisporError {
+                kind: ErrorKind::PermissionDeny,
+                ..
+            }) => {
+                log::info!(
+                    "Querying WireGuard information requires \
+                    CAP_NET_ADMIN, ignoring"
+                );
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+        let mut wg_info = WireguardInfo::default();
+        // Large peer list is split into multiple messages
+        for reply in replies {
+            parse_wireguard_device(&reply, &mut wg_info)?;
+        }
+        if let Some(iface) = index_to_name
+            .get(&format!("{index}"))
+            .and_then(|name| iface_states.get_mut(name))
+        {
+            iface.wireguard = Some(wg_info);
+        }
+    }
+    Ok(())
+}
+
+fn parse_wireguard_device(
+    data: &[u8],
+    wg_info: &mut WireguardInfo,
+) -> Result<(), NisporError> {
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        match nla.kind() {
+            WGDEVICE_A_PUBLIC_KEY => {
+                wg_info.public_key = Some(base64_encode(nla.value()));
+            }
+            WGDEVICE_A_LISTEN_PORT => {
+                wg_info.listen_port = parse_as_u16(nla.value())?;
+            }
+            WGDEVICE_A_FWMARK => {
+                wg_info.fwmark = parse_as_u32(nla.value())?;
+            }
+            WGDEVICE_A_PEERS => {
+                for peer_nla in NlasIterator::new(nla.value()) {
+                    let peer_nla = peer_nla?;
+                    wg_info.peers.push(parse_wireguard_peer(peer_nla.value())?);
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+fn parse_wireguard_peer(data: &[u8]) -> Result<WireguardPeerInfo, NisporError> {
+    let mut peer = WireguardPeerInfo::default();
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        match nla.kind() {
+            WGPEER_A_PUBLIC_KEY => {
+                peer.public_key = base64_encode(nla.value());
+            }
+            WGPEER_A_ENDPOINT => {
+                peer.endpoint = parse_sockaddr(nla.value())?;
+            }
+            WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL => {
+                peer.persistent_keepalive_interval = parse_as_u16(nla.value())?;
+            }
+            WGPEER_A_LAST_HANDSHAKE_TIME => {
+                // struct __kernel_timespec
+                let sec = parse_as_u64(nla.value())?;
+                if sec != 0 {
+                    peer.last_handshake_time = Some(sec);
+                }
+            }
+            WGPEER_A_RX_BYTES => {
+                peer.rx_bytes = parse_as_u64(nla.value())?;
+            }
+            WGPEER_A_TX_BYTES => {
+                peer.tx_bytes = parse_as_u64(nla.value())?;
+            }
+            WGPEER_A_ALLOWEDIPS => {
+                for ip_nla in NlasIterator::new(nla.value()) {
+                    let ip_nla = ip_nla?;
+                    if let Some(ip) = parse_allowed_ip(ip_nla.value())? {
+                        peer.allowed_ips.push(ip);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(peer)
+}
+
+fn parse_allowed_ip(data: &[u8]) -> Result<Option<String>, NisporError> {
+    let mut addr = None;
+    let mut prefix_len = None;
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        match nla.kind() {
+            WGALLOWEDIP_A_IPADDR => {
+                let d = nla.value();
+                addr = Some(match d.len() {
+                    4 => parse_as_ipv4(d)?.to_string(),
+                    16 => parse_as_ipv6(d)?.to_string(),
+                    _ => format!("{d:?}"),
+                });
+            }
+            WGALLOWEDIP_A_CIDR_MASK => {
+                prefix_len = Some(parse_as_u8(nla.value())?);
+            }
+            _ => (),
+        }
+    }
+    Ok(match (addr, prefix_len) {
+        (Some(addr), Some(prefix_len)) => Some(format!("{addr}/{prefix_len}")),
+        _ => None,
+    })
+}
+
+// The port of struct sockaddr_in and sockaddr_in6 is in network order
+fn parse_sockaddr(data: &[u8]) -> Result<Option<String>, NisporError> {
+    if data.len() < 4 {
+        return Ok(None);
+    }
+    let port = u16::from_be_bytes([data[2], data[3]]);
+    match parse_as_u16(data)? as i32 {
+        libc::AF_INET if data.len() >= 8 => {
+            Ok(Some(format!("{}:{port}", parse_as_ipv4(&data[4..8])?)))
+        }
+        libc::AF_INET6 if data.len() >= 24 => {
+            Ok(Some(format!("[{}]:{port}", parse_as_ipv6(&data[8..24])?)))
+        }
+        _ => Ok(None),
+    }
+}
+
+const BASE64_CHARS: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(data: &[u8]) -> String {
+    let mut ret = String::new();
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                ret.push(
+                    BASE64_CHARS[(n >> (18 - 6 * i) & 0x3f) as usize] as char,
+                );
+            } else {
+                ret.push('=');
+            }
+        }
+    }
+    ret
+}
+
+fn base64_decode(data: &str) -> Option<Vec<u8>> {
+    let data = data.as_bytes();
+    if data.len() % 4 != 0 {
+        return None;
+    }
+    let mut ret = Vec::new();
+    for chunk in data.chunks(4) {
+        let mut n = 0u32;
+        let mut pad_count = 0;
+        for c in chunk {
+            let v = if *c == b'=' {
+                pad_count += 1;
+                0
+            } else if pad_count > 0 {
+                return None;
+            } else {
+                BASE64_CHARS.iter().position(|b| b == c)? as u32
+            };
+            n = n << 6 | v;
+        }
+        if pad_count > 2 {
+            return None;
+        }
+        let bytes = n.to_be_bytes();
+        ret.extend_from_slice(&bytes[1..4 - pad_count]);
+    }
+    Some(ret)
+}
@@ -0,0 +1,235 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::rtnl::link::nlas::{Xdp, XdpAttached};
+use serde::{Deserialize, Serialize};
+
+use crate::{netlink::parse_as_u32, Iface, NisporError};
+
+const BPF_PROG_GET_FD_BY_ID: i64 = 13;
+const BPF_OBJ_GET_INFO_BY_FD: i64 = 15;
+
+const BPF_PROG_TYPE_XDP: u32 = 6;
+
+const BPF_TAG_SIZE: usize = 8;
+const BPF_OBJ_NAME_LEN: usize = 16;
+
+// Offsets in `struct bpf_prog_info`, we only need the fields before
+// `name`.
+const BPF_PROG_INFO_TYPE_OFFSET: usize = 0;
+const BPF_PROG_INFO_TAG_OFFSET: usize = 8;
+const BPF_PROG_INFO_NAME_OFFSET: usize = 64;
+const BPF_PROG_INFO_LEN: usize = BPF_PROG_INFO_NAME_OFFSET + BPF_OBJ_NAME_LEN;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum XdpAttachMode {
+    /* XDP_ATTACHED_SKB */
+    Generic,
+    /* XDP_ATTACHED_DRV */
+    Driver,
+    /* XDP_ATTACHED_HW */
+    Offload,
+    /* XDP_ATTACHED_MULTI, programs attached in more than one mode */
+    Multi,
+    Other(u8),
+    Unknown,
+}
+
+impl Default for XdpAttachMode {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<&XdpAttached> for XdpAttachMode {
+    fn from(d: &XdpAttached) -> Self {
+        match d {
+            XdpAttached::SocketBuffer => Self::Generic,
+            XdpAttached::Driver => Self::Driver,
+            XdpAttached::Hardware => Self::Offload,
+            XdpAttached::Multiple => Self::Multi,
+            XdpAttached::Other(d) => Self::Other(*d),
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum BpfProgType {
+    Xdp,
+    Other(u32),
+    Unknown,
+}
+
+impl Default for BpfProgType {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<u32> for BpfProgType {
+    fn from(d: u32) -> Self {
+        match d {
+            BPF_PROG_TYPE_XDP => Self::Xdp,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct XdpInfo {
+    pub mode: XdpAttachMode,
+    pub programs: Vec<XdpProgram>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct XdpProgram {
+    pub id: u32,
+    /// Never be `XdpAttachMode::Multi`
+    pub mode: XdpAttachMode,
+    /// The name, tag and type of program require CAP_SYS_ADMIN
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Hash of the program instructions in hex
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prog_type: Option<BpfProgType>,
+}
+
+pub(crate) fn get_xdp_info(nlas: &[Xdp]) -> Option<XdpInfo> {
+    let mut xdp_info = XdpInfo::default();
+    let mut prog_id = None;
+    for nla in nlas {
+        match nla {
+            Xdp::Attached(XdpAttached::None) => return None,
+            Xdp::Attached(d) => xdp_info.mode = d.into(),
+            Xdp::ProgId(d) => prog_id = Some(*d),
+            Xdp::SkbProgId(d) => xdp_info
+                .programs
+                .push(new_xdp_prog(*d, XdpAttachMode::Generic)),
+            Xdp::DrvProgId(d) => xdp_info
+                .programs
+                .push(new_xdp_prog(*d, XdpAttachMode::Driver)),
+            Xdp::HwProgId(d) => xdp_info
+                .programs
+                .push(new_xdp_prog(*d, XdpAttachMode::Offload)),
+            _ => log::debug!("Unknown XDP info {:?}", nla),
+        }
+    }
+    // Old kernel only reports IFLA_XDP_PROG_ID for single mode
+    if xdp_info.programs.is_empty() {
+        if let Some(prog_id) = prog_id {
+            xdp_info.programs.push(new_xdp_prog(prog_id, xdp_info.mode));
+        }
+    }
+    Some(xdp_info)
+}
+
+fn new_xdp_prog(id: u32, mode: XdpAttachMode) -> XdpProgram {
+    XdpProgram {
+        id,
+        mode,
+        ..Default::default()
+    }
+}
+
+pub(crate) fn fill_xdp_prog_info(iface_states: &mut HashMap<String, Iface>) {
+    for xdp_prog in iface_states
+        .values_mut()
+        .filter_map(|iface| iface.xdp.as_mut())
+        .flat_map(|xdp_info| xdp_info.programs.iter_mut())
+    {
+        match get_bpf_prog_info(xdp_prog.id) {
+            Ok(info) => {
+                xdp_prog.name = Some(parse_bpf_prog_name(
+                    &info[BPF_PROG_INFO_NAME_OFFSET..BPF_PROG_INFO_LEN],
+                ));
+                xdp_prog.tag = Some(
+                    info[BPF_PROG_INFO_TAG_OFFSET
+                        ..BPF_PROG_INFO_TAG_OFFSET + BPF_TAG_SIZE]
+                        .iter()
+                        .map(|b| format!("{b:02x}"))
+                        .collect(),
+                );
+                xdp_prog.prog_type = parse_as_u32(
+                    &info[BPF_PROG_INFO_TYPE_OFFSET
+                        ..BPF_PROG_INFO_TYPE_OFFSET + 4],
+                )
+                .ok()
+                .map(BpfProgType::from);
+            }
+            Err(e) => {
+                log::info!(
+                    "Failed to query BPF program {}, ignoring: {}",
+                    xdp_prog.id,
+                    e
+                );
+            }
+        }
+    }
+}
+
+fn parse_bpf_prog_name(data: &[u8]) -> String {
+    let end = data.iter().position(|c| *c == 0).unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..end]).to_string()
+}
+
+fn get_bpf_prog_info(
+    prog_id: u32,
+) -> Result<[u8; BPF_PROG_INFO_LEN], NisporError> {
+    // union bpf_attr for BPF_PROG_GET_FD_BY_ID: prog_id, next_id, open_flags
+    let mut attr = [0u32; 3];
+    attr[0] = prog_id;
+    let fd = unsafe {
+        libc::syscall(
+            libc::SYS_bpf,
+            BPF_PROG_GET_FD_BY_ID,
+            attr.as_ptr(),
+            std::mem::size_of_val(&attr),
+        )
+    };
+    if fd < 0 {
+        return Err(bpf_syscall_error("BPF_PROG_GET_FD_BY_ID"));
+    }
+
+    let mut info = [0u8; BPF_PROG_INFO_LEN];
+    // union bpf_attr for BPF_OBJ_GET_INFO_BY_FD: bpf_fd, info_len, info
+    let mut attr = [0u8; 16];
+    attr[..4].copy_from_slice(&(fd as u32).to_ne_bytes());
+    attr[4..8].copy_from_slice(&(BPF_PROG_INFO_LEN as u32).to_ne_bytes());
+    attr[8..16].copy_from_slice(&(info.as_mut_ptr() as u64).to_ne_bytes());
+    let rc = unsafe {
+        libc::syscall(
+            libc::SYS_bpf,
+            BPF_OBJ_GET_INFO_BY_FD,
+            attr.as_ptr(),
+            attr.len(),
+        )
+    };
+    let ret = if rc < 0 {
+        Err(bpf_syscall_error("BPF_OBJ_GET_INFO_BY_FD"))
+    } else {
+        Ok(info)
+    };
+    unsafe {
+        libc::close(fd as i32);
+    }
+    ret
+}
+
+fn bpf_syscall_error(cmd: &str) -> NisporError {
+    let e = std::io::Error::last_os_error();
+    if e.raw_os_error() == Some(libc::EPERM) {
+        NisporError::permission_deny(format!("{cmd} failed: {e}"))
+    } else {
+        NisporError::bug(format!("{cmd} failed: {e}"))
+    }
+}
@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::rtnl::link::nlas::{InfoData, InfoXfrmTun};
+use netlink_packet_utils::nla::Nla;
+use serde::{Deserialize, Serialize};
+
+use crate::{Iface, IfaceType};
+
+const IFLA_XFRM_COLLECT_METADATA: u16 = 3;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct XfrmInfo {
+    /// Underlying interface used for the lookup of encapsulated packets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_iface: Option<String>,
+    /// Matches the `if_id` of IPsec states and policies
+    pub if_id: u32,
+    pub collect_metadata: bool,
+}
+
+pub(crate) fn get_xfrm_info(data: &InfoData) -> Option<XfrmInfo> {
+    if let InfoData::Xfrm(infos) = data {
+        let mut xfrm_info = XfrmInfo::default();
+        for info in infos {
+            match info {
+                InfoXfrmTun::Link(d) => {
+                    if *d != 0 {
+                        xfrm_info.base_iface = Some(format!("{d}"));
+                    }
+                }
+                InfoXfrmTun::IfId(d) => xfrm_info.if_id = *d,
+                InfoXfrmTun::Other(nla)
+                    if nla.kind() == IFLA_XFRM_COLLECT_METADATA =>
+                {
+                    xfrm_info.collect_metadata = true;
+                }
+                _ => log::warn!("Unknown XFRM info {:?}", info),
+            }
+        }
+        Some(xfrm_info)
+    } else {
+        None
+    }
+}
+
+pub(crate) fn xfrm_iface_tidy_up(iface_states: &mut HashMap<String, Iface>) {
+    let mut index_to_name = HashMap::new();
+    for iface in iface_states.values() {
+        index_to_name.insert(format!("{}", iface.index), iface.name.clone());
+    }
+    for iface in iface_states.values_mut() {
+        if iface.iface_type != IfaceType::Xfrm {
+            continue;
+        }
+        if let Some(base_iface) = iface
+            .xfrm
+            .as_mut()
+            .and_then(|xfrm_info| xfrm_info.base_iface.as_mut())
+        {
+            if let Some(base_iface_name) = index_to_name.get(base_iface) {
+                *base_iface = base_iface_name.clone();
+            }
+        }
+    }
+}
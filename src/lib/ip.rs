@@ -6,18 +6,24 @@ use std::str::FromStr;
 
 use netlink_packet_route::rtnl::{
     address::nlas::{CacheInfo, Nla, ADDRESSS_CACHE_INFO_LEN},
-    link::nlas::{AfSpecInet, Inet6},
+    link::nlas::{AfSpecInet, Inet, Inet6},
     AddressMessage,
 };
 use netlink_packet_utils::Emitable;
 use serde::{Deserialize, Serialize};
 
-use crate::{Iface, IfaceConf, NisporError};
+use crate::{
+    ip_sysctl::{parse_ipv4_sysctl, parse_ipv6_sysctl},
+    Iface, IfaceConf, Ipv4SysctlInfo, Ipv6SysctlInfo, NisporError,
+};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
 #[non_exhaustive]
 pub struct Ipv4Info {
     pub addresses: Vec<Ipv4AddrInfo>,
+    /// Per-interface sysctl values of `net.ipv4.conf.<iface>`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sysctl: Option<Ipv4SysctlInfo>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
@@ -39,6 +45,62 @@ pub struct Ipv6Info {
     pub addresses: Vec<Ipv6AddrInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub addr_gen_mode: Option<Ipv6AddrGenMode>,
+    /// Router advertisement received
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ra_received: Option<bool>,
+    /// Managed address configuration flag of received router advertisement
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub managed: Option<bool>,
+    /// Other configuration flag of received router advertisement
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub other_config: Option<bool>,
+    /// Per-interface sysctl values of `net.ipv6.conf.<iface>`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sysctl: Option<Ipv6SysctlInfo>,
+}
+
+const IN6_ADDR_GEN_MODE_EUI64: u8 = 0;
+const IN6_ADDR_GEN_MODE_NONE: u8 = 1;
+const IN6_ADDR_GEN_MODE_STABLE_PRIVACY: u8 = 2;
+const IN6_ADDR_GEN_MODE_RANDOM: u8 = 3;
+
+const IF_RA_OTHERCONF: u32 = 0x80;
+const IF_RA_MANAGED: u32 = 0x40;
+const IF_RA_RCVD: u32 = 0x20;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Ipv6AddrGenMode {
+    /* Link-local address generated from MAC address */
+    Eui64,
+    /* No link-local address generated */
+    None,
+    /* RFC 7217 */
+    StablePrivacy,
+    Random,
+    Other(u8),
+    Unknown,
+}
+
+impl Default for Ipv6AddrGenMode {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<u8> for Ipv6AddrGenMode {
+    fn from(d: u8) -> Self {
+        match d {
+            IN6_ADDR_GEN_MODE_EUI64 => Self::Eui64,
+            IN6_ADDR_GEN_MODE_NONE => Self::None,
+            IN6_ADDR_GEN_MODE_STABLE_PRIVACY => Self::StablePrivacy,
+            IN6_ADDR_GEN_MODE_RANDOM => Self::Random,
+            _ => Self::Other(d),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
@@ -310,20 +372,76 @@ pub(crate) fn parse_ip_net_addr_str(
 
 pub(crate) fn fill_af_spec_inet_info(iface: &mut Iface, nlas: &[AfSpecInet]) {
     for nla in nlas {
-        if let AfSpecInet::Inet6(nlas) = nla {
-            for nla in nlas {
-                if let Inet6::Token(raw) = nla {
-                    // Kernel set all zero as default value
-                    if raw != &[0; 16] {
-                        if iface.ipv6.is_none() {
-                            iface.ipv6 = Some(Ipv6Info::default());
-                        }
-                        if let Some(ipv6_info) = iface.ipv6.as_mut() {
+        match nla {
+            AfSpecInet::Inet(nlas) => {
+                for nla in nlas {
+                    if let Inet::DevConf(raw) = nla {
+                        iface
+                            .ipv4
+                            .get_or_insert_with(Ipv4Info::default)
+                            .sysctl = Some(parse_ipv4_sysctl(raw));
+                    }
+                }
+            }
+            AfSpecInet::Inet6(nlas) => {
+                let ipv6_info =
+                    iface.ipv6.get_or_insert_with(Ipv6Info::default);
+                for nla in nlas {
+                    match nla {
+                        // Kernel set all zero as default value
+                        Inet6::Token(raw) if raw != &[0; 16] => {
                             ipv6_info.token = Some(ipv6_token_to_string(*raw));
                         }
+                        Inet6::AddrGenMode(d) => {
+                            ipv6_info.addr_gen_mode = Some((*d).into());
+                        }
+                        Inet6::Flags(d) => {
+                            ipv6_info.ra_received = Some(d & IF_RA_RCVD > 0);
+                            ipv6_info.managed = Some(d & IF_RA_MANAGED > 0);
+                            ipv6_info.other_config =
+                                Some(d & IF_RA_OTHERCONF > 0);
+                        }
+                        Inet6::DevConf(raw) => {
+                            ipv6_info.sysctl = Some(parse_ipv6_sysctl(raw));
+                        }
+                        _ => (),
                     }
                 }
             }
+            _ => (),
+        }
+    }
+}
+
+// The IFLA_AF_SPEC is parsed before IP addresses are filled, hence we can
+// only remove the empty IPv4/IPv6 information afterwards.
+pub(crate) fn ip_info_tidy_up(
+    iface_states: &mut HashMap<String, Iface>,
+    include_ip_sysctl: bool,
+) {
+    for iface in iface_states.values_mut() {
+        if let Some(ipv4_info) = iface.ipv4.as_mut() {
+            if !include_ip_sysctl {
+                ipv4_info.sysctl = None;
+            }
+            if ipv4_info.addresses.is_empty() && ipv4_info.sysctl.is_none() {
+                iface.ipv4 = None;
+            }
+        }
+        if let Some(ipv6_info) = iface.ipv6.as_mut() {
+            if !include_ip_sysctl {
+                ipv6_info.sysctl = None;
+            }
+            if ipv6_info.addresses.is_empty()
+                && ipv6_info.sysctl.is_none()
+                && ipv6_info.token.is_none()
+                && ipv6_info.addr_gen_mode.is_none()
+                && ipv6_info.ra_received.is_none()
+                && ipv6_info.managed.is_none()
+                && ipv6_info.other_config.is_none()
+            {
+                iface.ipv6 = None;
+            }
         }
     }
 }
@@ -0,0 +1,456 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Per-interface IPv4/IPv6 sysctl values(`net.ipv4.conf.<iface>.*` and
+// `net.ipv6.conf.<iface>.*`) reported by kernel in IFLA_INET_CONF and
+// IFLA_INET6_CONF of IFLA_AF_SPEC.
+// Older kernel might send shorter array, the unknown values are set to None.
+
+use serde::{Deserialize, Serialize};
+
+// IPV4_DEVCONF_* in linux/ip.h, the array index is the value minus 1.
+const IPV4_DEVCONF_FORWARDING: usize = 1;
+const IPV4_DEVCONF_MC_FORWARDING: usize = 2;
+const IPV4_DEVCONF_PROXY_ARP: usize = 3;
+const IPV4_DEVCONF_ACCEPT_REDIRECTS: usize = 4;
+const IPV4_DEVCONF_SECURE_REDIRECTS: usize = 5;
+const IPV4_DEVCONF_SEND_REDIRECTS: usize = 6;
+const IPV4_DEVCONF_SHARED_MEDIA: usize = 7;
+const IPV4_DEVCONF_RP_FILTER: usize = 8;
+const IPV4_DEVCONF_ACCEPT_SOURCE_ROUTE: usize = 9;
+const IPV4_DEVCONF_BOOTP_RELAY: usize = 10;
+const IPV4_DEVCONF_LOG_MARTIANS: usize = 11;
+const IPV4_DEVCONF_TAG: usize = 12;
+const IPV4_DEVCONF_ARPFILTER: usize = 13;
+const IPV4_DEVCONF_MEDIUM_ID: usize = 14;
+const IPV4_DEVCONF_NOXFRM: usize = 15;
+const IPV4_DEVCONF_NOPOLICY: usize = 16;
+const IPV4_DEVCONF_FORCE_IGMP_VERSION: usize = 17;
+const IPV4_DEVCONF_ARP_ANNOUNCE: usize = 18;
+const IPV4_DEVCONF_ARP_IGNORE: usize = 19;
+const IPV4_DEVCONF_PROMOTE_SECONDARIES: usize = 20;
+const IPV4_DEVCONF_ARP_ACCEPT: usize = 21;
+const IPV4_DEVCONF_ARP_NOTIFY: usize = 22;
+const IPV4_DEVCONF_ACCEPT_LOCAL: usize = 23;
+const IPV4_DEVCONF_SRC_VMARK: usize = 24;
+const IPV4_DEVCONF_PROXY_ARP_PVLAN: usize = 25;
+const IPV4_DEVCONF_ROUTE_LOCALNET: usize = 26;
+const IPV4_DEVCONF_IGMPV2_UNSOLICITED_REPORT_INTERVAL: usize = 27;
+const IPV4_DEVCONF_IGMPV3_UNSOLICITED_REPORT_INTERVAL: usize = 28;
+const IPV4_DEVCONF_IGNORE_ROUTES_WITH_LINKDOWN: usize = 29;
+const IPV4_DEVCONF_DROP_UNICAST_IN_L2_MULTICAST: usize = 30;
+const IPV4_DEVCONF_DROP_GRATUITOUS_ARP: usize = 31;
+const IPV4_DEVCONF_BC_FORWARDING: usize = 32;
+const IPV4_DEVCONF_ARP_EVICT_NOCARRIER: usize = 33;
+
+// DEVCONF_* in linux/ipv6.h, the array index is the value.
+const DEVCONF_FORWARDING: usize = 0;
+const DEVCONF_HOPLIMIT: usize = 1;
+const DEVCONF_MTU6: usize = 2;
+const DEVCONF_ACCEPT_RA: usize = 3;
+const DEVCONF_ACCEPT_REDIRECTS: usize = 4;
+const DEVCONF_AUTOCONF: usize = 5;
+const DEVCONF_DAD_TRANSMITS: usize = 6;
+const DEVCONF_RTR_SOLICITS: usize = 7;
+const DEVCONF_RTR_SOLICIT_INTERVAL: usize = 8;
+const DEVCONF_RTR_SOLICIT_DELAY: usize = 9;
+const DEVCONF_USE_TEMPADDR: usize = 10;
+const DEVCONF_TEMP_VALID_LFT: usize = 11;
+const DEVCONF_TEMP_PREFERED_LFT: usize = 12;
+const DEVCONF_REGEN_MAX_RETRY: usize = 13;
+const DEVCONF_MAX_DESYNC_FACTOR: usize = 14;
+const DEVCONF_MAX_ADDRESSES: usize = 15;
+const DEVCONF_FORCE_MLD_VERSION: usize = 16;
+const DEVCONF_ACCEPT_RA_DEFRTR: usize = 17;
+const DEVCONF_ACCEPT_RA_PINFO: usize = 18;
+const DEVCONF_ACCEPT_RA_RTR_PREF: usize = 19;
+const DEVCONF_RTR_PROBE_INTERVAL: usize = 20;
+const DEVCONF_ACCEPT_RA_RT_INFO_MAX_PLEN: usize = 21;
+const DEVCONF_PROXY_NDP: usize = 22;
+const DEVCONF_OPTIMISTIC_DAD: usize = 23;
+const DEVCONF_ACCEPT_SOURCE_ROUTE: usize = 24;
+const DEVCONF_MC_FORWARDING: usize = 25;
+const DEVCONF_DISABLE_IPV6: usize = 26;
+const DEVCONF_ACCEPT_DAD: usize = 27;
+const DEVCONF_FORCE_TLLAO: usize = 28;
+const DEVCONF_NDISC_NOTIFY: usize = 29;
+const DEVCONF_MLDV1_UNSOLICITED_REPORT_INTERVAL: usize = 30;
+const DEVCONF_MLDV2_UNSOLICITED_REPORT_INTERVAL: usize = 31;
+const DEVCONF_SUPPRESS_FRAG_NDISC: usize = 32;
+const DEVCONF_ACCEPT_RA_FROM_LOCAL: usize = 33;
+const DEVCONF_USE_OPTIMISTIC: usize = 34;
+const DEVCONF_ACCEPT_RA_MTU: usize = 35;
+// DEVCONF_STABLE_SECRET(36) is always reported as 0 by kernel
+const DEVCONF_USE_OIF_ADDRS_ONLY: usize = 37;
+const DEVCONF_ACCEPT_RA_MIN_HOP_LIMIT: usize = 38;
+const DEVCONF_IGNORE_ROUTES_WITH_LINKDOWN: usize = 39;
+const DEVCONF_DROP_UNICAST_IN_L2_MULTICAST: usize = 40;
+const DEVCONF_DROP_UNSOLICITED_NA: usize = 41;
+const DEVCONF_KEEP_ADDR_ON_DOWN: usize = 42;
+const DEVCONF_RTR_SOLICIT_MAX_INTERVAL: usize = 43;
+const DEVCONF_SEG6_ENABLED: usize = 44;
+const DEVCONF_SEG6_REQUIRE_HMAC: usize = 45;
+const DEVCONF_ENHANCED_DAD: usize = 46;
+// DEVCONF_ADDR_GEN_MODE(47) is stored in `Ipv6Info.addr_gen_mode`
+const DEVCONF_DISABLE_POLICY: usize = 48;
+const DEVCONF_ACCEPT_RA_RT_INFO_MIN_PLEN: usize = 49;
+const DEVCONF_NDISC_TCLASS: usize = 50;
+const DEVCONF_RPL_SEG_ENABLED: usize = 51;
+const DEVCONF_RA_DEFRTR_METRIC: usize = 52;
+const DEVCONF_IOAM6_ENABLED: usize = 53;
+const DEVCONF_IOAM6_ID: usize = 54;
+const DEVCONF_IOAM6_ID_WIDE: usize = 55;
+const DEVCONF_NDISC_EVICT_NOCARRIER: usize = 56;
+const DEVCONF_ACCEPT_UNTRACKED_NA: usize = 57;
+const DEVCONF_ACCEPT_RA_MIN_LFT: usize = 58;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct Ipv4SysctlInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forwarding: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mc_forwarding: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_arp: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_redirects: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secure_redirects: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send_redirects: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared_media: Option<bool>,
+    /// 0: no source validation, 1: strict mode, 2: loose mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rp_filter: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_source_route: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bootp_relay: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_martians: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arp_filter: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub medium_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_xfrm: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_policy: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_igmp_version: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arp_announce: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arp_ignore: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub promote_secondaries: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arp_accept: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arp_notify: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_local: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub src_valid_mark: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_arp_pvlan: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub route_localnet: Option<bool>,
+    /// In milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub igmpv2_unsolicited_report_interval: Option<u32>,
+    /// In milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub igmpv3_unsolicited_report_interval: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_routes_with_linkdown: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drop_unicast_in_l2_multicast: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drop_gratuitous_arp: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bc_forwarding: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arp_evict_nocarrier: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct Ipv6SysctlInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forwarding: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hop_limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtu: Option<u32>,
+    /// 0: do not accept, 1: accept when forwarding disabled,
+    /// 2: always accept
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_ra: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_redirects: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autoconf: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dad_transmits: Option<u32>,
+    /// -1 means sending router solicitations until got reply
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub router_solicitations: Option<i32>,
+    /// In milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub router_solicitation_interval: Option<u32>,
+    /// In milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub router_solicitation_delay: Option<u32>,
+    /// In milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub router_solicitation_max_interval: Option<u32>,
+    /// <= 0: disabled, 1: enabled but prefer public address,
+    /// > 1: enabled and prefer temporary address
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_tempaddr: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temp_valid_lft: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temp_prefered_lft: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regen_max_retry: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_desync_factor: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_addresses: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_mld_version: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_ra_defrtr: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_ra_pinfo: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_ra_rtr_pref: Option<bool>,
+    /// In milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub router_probe_interval: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_ra_rt_info_min_plen: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_ra_rt_info_max_plen: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_ndp: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optimistic_dad: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_source_route: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mc_forwarding: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_ipv6: Option<bool>,
+    /// 0: disable DAD, 1: enable DAD, 2: also disable IPv6 when
+    /// MAC-based link-local address is duplicated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_dad: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_tllao: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ndisc_notify: Option<bool>,
+    /// In milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mldv1_unsolicited_report_interval: Option<u32>,
+    /// In milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mldv2_unsolicited_report_interval: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suppress_frag_ndisc: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_ra_from_local: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_optimistic: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_ra_mtu: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_oif_addrs_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_ra_min_hop_limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_routes_with_linkdown: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drop_unicast_in_l2_multicast: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drop_unsolicited_na: Option<bool>,
+    /// 0: use global setting, 1: keep addresses, -1: remove addresses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep_addr_on_down: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seg6_enabled: Option<bool>,
+    /// -1: ignore HMAC, 0: accept without HMAC, 1: require HMAC
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seg6_require_hmac: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enhanced_dad: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_policy: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ndisc_tclass: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpl_seg_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ra_defrtr_metric: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ioam6_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ioam6_id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ioam6_id_wide: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ndisc_evict_nocarrier: Option<bool>,
+    /// 0: ignore, 1: create neighbor entry, 2: create neighbor entry only
+    /// when source IP is in the same subnet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_untracked_na: Option<u32>,
+    /// In seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_ra_min_lft: Option<u32>,
+}
+
+// The array is in host order, one 32 bits value per sysctl.
+struct DevConfArray<'a>(&'a [u8]);
+
+impl<'a> DevConfArray<'a> {
+    fn get_i32(&self, index: usize) -> Option<i32> {
+        self.0.get(index * 4..index * 4 + 4).map(|d| {
+            let mut raw = [0u8; 4];
+            raw.copy_from_slice(d);
+            i32::from_ne_bytes(raw)
+        })
+    }
+
+    fn get_u32(&self, index: usize) -> Option<u32> {
+        self.get_i32(index).map(|d| d as u32)
+    }
+
+    fn get_bool(&self, index: usize) -> Option<bool> {
+        self.get_i32(index).map(|d| d > 0)
+    }
+}
+
+pub(crate) fn parse_ipv4_sysctl(data: &[u8]) -> Ipv4SysctlInfo {
+    let conf = DevConfArray(data);
+    // IPV4_DEVCONF_* starts from 1
+    let idx = |i: usize| i - 1;
+    Ipv4SysctlInfo {
+        forwarding: conf.get_bool(idx(IPV4_DEVCONF_FORWARDING)),
+        mc_forwarding: conf.get_bool(idx(IPV4_DEVCONF_MC_FORWARDING)),
+        proxy_arp: conf.get_bool(idx(IPV4_DEVCONF_PROXY_ARP)),
+        accept_redirects: conf.get_bool(idx(IPV4_DEVCONF_ACCEPT_REDIRECTS)),
+        secure_redirects: conf.get_bool(idx(IPV4_DEVCONF_SECURE_REDIRECTS)),
+        send_redirects: conf.get_bool(idx(IPV4_DEVCONF_SEND_REDIRECTS)),
+        shared_media: conf.get_bool(idx(IPV4_DEVCONF_SHARED_MEDIA)),
+        rp_filter: conf.get_u32(idx(IPV4_DEVCONF_RP_FILTER)),
+        accept_source_route: conf
+            .get_bool(idx(IPV4_DEVCONF_ACCEPT_SOURCE_ROUTE)),
+        bootp_relay: conf.get_bool(idx(IPV4_DEVCONF_BOOTP_RELAY)),
+        log_martians: conf.get_bool(idx(IPV4_DEVCONF_LOG_MARTIANS)),
+        tag: conf.get_u32(idx(IPV4_DEVCONF_TAG)),
+        arp_filter: conf.get_bool(idx(IPV4_DEVCONF_ARPFILTER)),
+        medium_id: conf.get_i32(idx(IPV4_DEVCONF_MEDIUM_ID)),
+        disable_xfrm: conf.get_bool(idx(IPV4_DEVCONF_NOXFRM)),
+        disable_policy: conf.get_bool(idx(IPV4_DEVCONF_NOPOLICY)),
+        force_igmp_version: conf.get_u32(idx(IPV4_DEVCONF_FORCE_IGMP_VERSION)),
+        arp_announce: conf.get_u32(idx(IPV4_DEVCONF_ARP_ANNOUNCE)),
+        arp_ignore: conf.get_u32(idx(IPV4_DEVCONF_ARP_IGNORE)),
+        promote_secondaries: conf
+            .get_bool(idx(IPV4_DEVCONF_PROMOTE_SECONDARIES)),
+        arp_accept: conf.get_u32(idx(IPV4_DEVCONF_ARP_ACCEPT)),
+        arp_notify: conf.get_bool(idx(IPV4_DEVCONF_ARP_NOTIFY)),
+        accept_local: conf.get_bool(idx(IPV4_DEVCONF_ACCEPT_LOCAL)),
+        src_valid_mark: conf.get_bool(idx(IPV4_DEVCONF_SRC_VMARK)),
+        proxy_arp_pvlan: conf.get_bool(idx(IPV4_DEVCONF_PROXY_ARP_PVLAN)),
+        route_localnet: conf.get_bool(idx(IPV4_DEVCONF_ROUTE_LOCALNET)),
+        igmpv2_unsolicited_report_interval: conf
+            .get_u32(idx(IPV4_DEVCONF_IGMPV2_UNSOLICITED_REPORT_INTERVAL)),
+        igmpv3_unsolicited_report_interval: conf
+            .get_u32(idx(IPV4_DEVCONF_IGMPV3_UNSOLICITED_REPORT_INTERVAL)),
+        ignore_routes_with_linkdown: conf
+            .get_bool(idx(IPV4_DEVCONF_IGNORE_ROUTES_WITH_LINKDOWN)),
+        drop_unicast_in_l2_multicast: conf
+            .get_bool(idx(IPV4_DEVCONF_DROP_UNICAST_IN_L2_MULTICAST)),
+        drop_gratuitous_arp: conf
+            .get_bool(idx(IPV4_DEVCONF_DROP_GRATUITOUS_ARP)),
+        bc_forwarding: conf.get_bool(idx(IPV4_DEVCONF_BC_FORWARDING)),
+        arp_evict_nocarrier: conf
+            .get_bool(idx(IPV4_DEVCONF_ARP_EVICT_NOCARRIER)),
+    }
+}
+
+pub(crate) fn parse_ipv6_sysctl(data: &[u8]) -> Ipv6SysctlInfo {
+    let conf = DevConfArray(data);
+    Ipv6SysctlInfo {
+        forwarding: conf.get_bool(DEVCONF_FORWARDING),
+        hop_limit: conf.get_u32(DEVCONF_HOPLIMIT),
+        mtu: conf.get_u32(DEVCONF_MTU6),
+        accept_ra: conf.get_u32(DEVCONF_ACCEPT_RA),
+        accept_redirects: conf.get_bool(DEVCONF_ACCEPT_REDIRECTS),
+        autoconf: conf.get_bool(DEVCONF_AUTOCONF),
+        dad_transmits: conf.get_u32(DEVCONF_DAD_TRANSMITS),
+        router_solicitations: conf.get_i32(DEVCONF_RTR_SOLICITS),
+        router_solicitation_interval: conf
+            .get_u32(DEVCONF_RTR_SOLICIT_INTERVAL),
+        router_solicitation_delay: conf.get_u32(DEVCONF_RTR_SOLICIT_DELAY),
+        router_solicitation_max_interval: conf
+            .get_u32(DEVCONF_RTR_SOLICIT_MAX_INTERVAL),
+        use_tempaddr: conf.get_i32(DEVCONF_USE_TEMPADDR),
+        temp_valid_lft: conf.get_u32(DEVCONF_TEMP_VALID_LFT),
+        temp_prefered_lft: conf.get_u32(DEVCONF_TEMP_PREFERED_LFT),
+        regen_max_retry: conf.get_u32(DEVCONF_REGEN_MAX_RETRY),
+        max_desync_factor: conf.get_u32(DEVCONF_MAX_DESYNC_FACTOR),
+        max_addresses: conf.get_u32(DEVCONF_MAX_ADDRESSES),
+        force_mld_version: conf.get_u32(DEVCONF_FORCE_MLD_VERSION),
+        accept_ra_defrtr: conf.get_bool(DEVCONF_ACCEPT_RA_DEFRTR),
+        accept_ra_pinfo: conf.get_bool(DEVCONF_ACCEPT_RA_PINFO),
+        accept_ra_rtr_pref: conf.get_bool(DEVCONF_ACCEPT_RA_RTR_PREF),
+        router_probe_interval: conf.get_u32(DEVCONF_RTR_PROBE_INTERVAL),
+        accept_ra_rt_info_min_plen: conf
+            .get_u32(DEVCONF_ACCEPT_RA_RT_INFO_MIN_PLEN),
+        accept_ra_rt_info_max_plen: conf
+            .get_u32(DEVCONF_ACCEPT_RA_RT_INFO_MAX_PLEN),
+        proxy_ndp: conf.get_bool(DEVCONF_PROXY_NDP),
+        optimistic_dad: conf.get_bool(DEVCONF_OPTIMISTIC_DAD),
+        accept_source_route: conf.get_i32(DEVCONF_ACCEPT_SOURCE_ROUTE),
+        mc_forwarding: conf.get_bool(DEVCONF_MC_FORWARDING),
+        disable_ipv6: conf.get_bool(DEVCONF_DISABLE_IPV6),
+        accept_dad: conf.get_u32(DEVCONF_ACCEPT_DAD),
+        force_tllao: conf.get_bool(DEVCONF_FORCE_TLLAO),
+        ndisc_notify: conf.get_bool(DEVCONF_NDISC_NOTIFY),
+        mldv1_unsolicited_report_interval: conf
+            .get_u32(DEVCONF_MLDV1_UNSOLICITED_REPORT_INTERVAL),
+        mldv2_unsolicited_report_interval: conf
+            .get_u32(DEVCONF_MLDV2_UNSOLICITED_REPORT_INTERVAL),
+        suppress_frag_ndisc: conf.get_bool(DEVCONF_SUPPRESS_FRAG_NDISC),
+        accept_ra_from_local: conf.get_bool(DEVCONF_ACCEPT_RA_FROM_LOCAL),
+        use_optimistic: conf.get_bool(DEVCONF_USE_OPTIMISTIC),
+        accept_ra_mtu: conf.get_bool(DEVCONF_ACCEPT_RA_MTU),
+        use_oif_addrs_only: conf.get_bool(DEVCONF_USE_OIF_ADDRS_ONLY),
+        accept_ra_min_hop_limit: conf.get_u32(DEVCONF_ACCEPT_RA_MIN_HOP_LIMIT),
+        ignore_routes_with_linkdown: conf
+            .get_bool(DEVCONF_IGNORE_ROUTES_WITH_LINKDOWN),
+        drop_unicast_in_l2_multicast: conf
+            .get_bool(DEVCONF_DROP_UNICAST_IN_L2_MULTICAST),
+        drop_unsolicited_na: conf.get_bool(DEVCONF_DROP_UNSOLICITED_NA),
+        keep_addr_on_down: conf.get_i32(DEVCONF_KEEP_ADDR_ON_DOWN),
+        seg6_enabled: conf.get_bool(DEVCONF_SEG6_ENABLED),
+        seg6_require_hmac: conf.get_i32(DEVCONF_SEG6_REQUIRE_HMAC),
+        enhanced_dad: conf.get_bool(DEVCONF_ENHANCED_DAD),
+        disable_policy: conf.get_bool(DEVCONF_DISABLE_POLICY),
+        ndisc_tclass: conf.get_u32(DEVCONF_NDISC_TCLASS),
+        rpl_seg_enabled: conf.get_bool(DEVCONF_RPL_SEG_ENABLED),
+        ra_defrtr_metric: conf.get_u32(DEVCONF_RA_DEFRTR_METRIC),
+        ioam6_enabled: conf.get_bool(DEVCONF_IOAM6_ENABLED),
+        ioam6_id: conf.get_u32(DEVCONF_IOAM6_ID),
+        ioam6_id_wide: conf.get_u32(DEVCONF_IOAM6_ID_WIDE),
+        ndisc_evict_nocarrier: conf.get_bool(DEVCONF_NDISC_EVICT_NOCARRIER),
+        accept_untracked_na: conf.get_u32(DEVCONF_ACCEPT_UNTRACKED_NA),
+        accept_ra_min_lft: conf.get_u32(DEVCONF_ACCEPT_RA_MIN_LFT),
+    }
+}
@@ -0,0 +1,576 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_utils::nla::NlasIterator;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::ErrorKind,
+    netlink::{
+        netlink_dump, parse_as_be16, parse_as_be32, parse_as_i32,
+        parse_as_ipv4, parse_as_ipv6, parse_as_u16, parse_as_u32, parse_as_u64,
+    },
+    AddressFamily, NetStateIpsecFilter, NisporError,
+};
+
+const NETLINK_XFRM: isize = 6;
+
+const XFRM_MSG_NEWSA: u16 = 16;
+const XFRM_MSG_GETSA: u16 = 18;
+const XFRM_MSG_NEWPOLICY: u16 = 19;
+const XFRM_MSG_GETPOLICY: u16 = 21;
+
+// struct xfrm_usersa_info
+const XFRM_USERSA_INFO_LEN: usize = 224;
+// struct xfrm_userpolicy_info
+const XFRM_USERPOLICY_INFO_LEN: usize = 168;
+// struct xfrm_user_tmpl
+const XFRM_USER_TMPL_LEN: usize = 64;
+// struct xfrm_selector
+const XFRM_SELECTOR_LEN: usize = 56;
+// struct xfrm_lifetime_cfg
+const XFRM_LIFETIME_CFG_LEN: usize = 64;
+// struct xfrm_lifetime_cur
+const XFRM_LIFETIME_CUR_LEN: usize = 32;
+
+const XFRMA_TMPL: u16 = 5;
+const XFRMA_IF_ID: u16 = 31;
+
+const XFRM_INF: u64 = u64::MAX;
+
+const AF_INET: u16 = 2;
+const AF_INET6: u16 = 10;
+
+// The keys of IPsec states are never included.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IpsecInfo {
+    pub states: Vec<IpsecState>,
+    pub policies: Vec<IpsecPolicy>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IpsecState {
+    pub address_family: AddressFamily,
+    pub src: String,
+    pub dst: String,
+    pub protocol: IpsecProtocol,
+    pub spi: u32,
+    pub mode: IpsecMode,
+    pub reqid: u32,
+    pub replay_window: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub if_id: Option<u32>,
+    pub selector: IpsecSelector,
+    pub lifetime: IpsecLifetime,
+    pub current: IpsecLifetimeCurrent,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IpsecPolicy {
+    pub selector: IpsecSelector,
+    pub direction: IpsecPolicyDirection,
+    pub action: IpsecPolicyAction,
+    pub priority: u32,
+    pub index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub if_id: Option<u32>,
+    pub lifetime: IpsecLifetime,
+    pub current: IpsecLifetimeCurrent,
+    pub templates: Vec<IpsecTemplate>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IpsecSelector {
+    /// Source network in the format of `address/prefix_length`
+    pub src: String,
+    /// Destination network in the format of `address/prefix_length`
+    pub dst: String,
+    /// IP protocol number, None for any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proto: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sport: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dport: Option<u16>,
+    /// Interface name, None for any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iface: Option<String>,
+}
+
+/// The limits are None when set to infinity.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IpsecLifetime {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub soft_byte_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hard_byte_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub soft_packet_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hard_packet_limit: Option<u64>,
+    pub soft_add_expires_seconds: u64,
+    pub hard_add_expires_seconds: u64,
+    pub soft_use_expires_seconds: u64,
+    pub hard_use_expires_seconds: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IpsecLifetimeCurrent {
+    pub bytes: u64,
+    pub packets: u64,
+    /// Seconds since epoch when created
+    pub add_time: u64,
+    /// Seconds since epoch when first used, 0 if never used
+    pub use_time: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct IpsecTemplate {
+    pub address_family: AddressFamily,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub src: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dst: Option<String>,
+    pub protocol: IpsecProtocol,
+    /// SPI of IPsec state, 0 for any
+    pub spi: u32,
+    pub mode: IpsecMode,
+    pub reqid: u32,
+    /// Whether the IPsec state is optional(`level use` in iproute)
+    pub optional: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum IpsecProtocol {
+    Esp,
+    Ah,
+    Comp,
+    Route2,
+    HaoOpt,
+    Other(u8),
+    Unknown,
+}
+
+const IPPROTO_ESP: u8 = 50;
+const IPPROTO_AH: u8 = 51;
+const IPPROTO_COMP: u8 = 108;
+const IPPROTO_ROUTING: u8 = 43;
+const IPPROTO_DSTOPTS: u8 = 60;
+
+impl From<u8> for IpsecProtocol {
+    fn from(d: u8) -> Self {
+        match d {
+            IPPROTO_ESP => Self::Esp,
+            IPPROTO_AH => Self::Ah,
+            IPPROTO_COMP => Self::Comp,
+            IPPROTO_ROUTING => Self::Route2,
+            IPPROTO_DSTOPTS => Self::HaoOpt,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl Default for IpsecProtocol {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum IpsecMode {
+    Transport,
+    Tunnel,
+    RouteOptimization,
+    InTrigger,
+    Beet,
+    Other(u8),
+    Unknown,
+}
+
+const XFRM_MODE_TRANSPORT: u8 = 0;
+const XFRM_MODE_TUNNEL: u8 = 1;
+const XFRM_MODE_ROUTEOPTIMIZATION: u8 = 2;
+const XFRM_MODE_IN_TRIGGER: u8 = 3;
+const XFRM_MODE_BEET: u8 = 4;
+
+impl From<u8> for IpsecMode {
+    fn from(d: u8) -> Self {
+        match d {
+            XFRM_MODE_TRANSPORT => Self::Transport,
+            XFRM_MODE_TUNNEL => Self::Tunnel,
+            XFRM_MODE_ROUTEOPTIMIZATION => Self::RouteOptimization,
+            XFRM_MODE_IN_TRIGGER => Self::InTrigger,
+            XFRM_MODE_BEET => Self::Beet,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl Default for IpsecMode {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum IpsecPolicyDirection {
+    In,
+    Out,
+    Fwd,
+    Other(u8),
+    Unknown,
+}
+
+const XFRM_POLICY_IN: u8 = 0;
+const XFRM_POLICY_OUT: u8 = 1;
+const XFRM_POLICY_FWD: u8 = 2;
+
+impl From<u8> for IpsecPolicyDirection {
+    fn from(d: u8) -> Self {
+        match d {
+            XFRM_POLICY_IN => Self::In,
+            XFRM_POLICY_OUT => Self::Out,
+            XFRM_POLICY_FWD => Self::Fwd,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl Default for IpsecPolicyDirection {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum IpsecPolicyAction {
+    Allow,
+    Block,
+    Other(u8),
+    Unknown,
+}
+
+const XFRM_POLICY_ALLOW: u8 = 0;
+const XFRM_POLICY_BLOCK: u8 = 1;
+
+impl From<u8> for IpsecPolicyAction {
+    fn from(d: u8) -> Self {
+        match d {
+            XFRM_POLICY_ALLOW => Self::Allow,
+            XFRM_POLICY_BLOCK => Self::Block,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl Default for IpsecPolicyAction {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+// IPsec information is considered as optional, return None on failure.
+// Querying IPsec requires `CAP_NET_ADMIN`, only log at info level when
+// permission denied.
+pub(crate) async fn get_ipsec(
+    iface_name2index: &HashMap<String, u32>,
+    filter: &NetStateIpsecFilter,
+) -> Option<IpsecInfo> {
+    match _get_ipsec(iface_name2index, filter).await {
+        Ok(i) => Some(i),
+        Err(NisporError {
+            kind: ErrorKind::PermissionDeny,
+            ..
+        }) => {
+            log::info!("No permission to query IPsec, ignoring");
+            None
+        }
+        Err(e) => {
+            log::warn!("Failed to query IPsec info: {}", e);
+            None
+        }
+    }
+}
+
+async fn _get_ipsec(
+    iface_name2index: &HashMap<String, u32>,
+    _filter: &NetStateIpsecFilter,
+) -> Result<IpsecInfo, NisporError> {
+    let mut ipsec_info = IpsecInfo::default();
+
+    let mut ifindex_to_name = HashMap::new();
+    for (name, index) in iface_name2index.iter() {
+        ifindex_to_name.insert(format!("{index}"), name.to_string());
+    }
+
+    for nl_msg in netlink_dump(NETLINK_XFRM, XFRM_MSG_GETSA, &[], false).await?
+    {
+        if nl_msg.msg_type != XFRM_MSG_NEWSA
+            || nl_msg.payload.len() < XFRM_USERSA_INFO_LEN
+        {
+            continue;
+        }
+        ipsec_info
+            .states
+            .push(parse_ipsec_state(&nl_msg.payload, &ifindex_to_name)?);
+    }
+    for nl_msg in
+        netlink_dump(NETLINK_XFRM, XFRM_MSG_GETPOLICY, &[], false).await?
+    {
+        if nl_msg.msg_type != XFRM_MSG_NEWPOLICY
+            || nl_msg.payload.len() < XFRM_USERPOLICY_INFO_LEN
+        {
+            continue;
+        }
+        ipsec_info
+            .policies
+            .push(parse_ipsec_policy(&nl_msg.payload, &ifindex_to_name)?);
+    }
+    Ok(ipsec_info)
+}
+
+// struct xfrm_usersa_info {
+//     struct xfrm_selector     sel;           0
+//     struct xfrm_id           id;            56
+//     xfrm_address_t           saddr;         80
+//     struct xfrm_lifetime_cfg lft;           96
+//     struct xfrm_lifetime_cur curlft;        160
+//     struct xfrm_stats        stats;         192
+//     __u32                    seq;           204
+//     __u32                    reqid;         208
+//     __u16                    family;        212
+//     __u8                     mode;          214
+//     __u8                     replay_window; 215
+//     __u8                     flags;         216
+// };
+fn parse_ipsec_state(
+    data: &[u8],
+    ifindex_to_name: &HashMap<String, String>,
+) -> Result<IpsecState, NisporError> {
+    let family = parse_as_u16(&data[212..214])?;
+    let mut state = IpsecState {
+        address_family: (family as u8).into(),
+        selector: parse_xfrm_selector(
+            &data[..XFRM_SELECTOR_LEN],
+            family,
+            ifindex_to_name,
+        )?,
+        dst: parse_xfrm_addr(&data[56..72], family)?,
+        spi: parse_as_be32(&data[72..76])?,
+        protocol: data[76].into(),
+        src: parse_xfrm_addr(&data[80..96], family)?,
+        lifetime: parse_xfrm_lifetime_cfg(
+            &data[96..96 + XFRM_LIFETIME_CFG_LEN],
+        )?,
+        current: parse_xfrm_lifetime_cur(
+            &data[160..160 + XFRM_LIFETIME_CUR_LEN],
+        )?,
+        reqid: parse_as_u32(&data[208..212])?,
+        mode: data[214].into(),
+        replay_window: data[215],
+        ..Default::default()
+    };
+    for nla in NlasIterator::new(&data[XFRM_USERSA_INFO_LEN..]) {
+        let nla = nla?;
+        if nla.kind() == XFRMA_IF_ID {
+            state.if_id = Some(parse_as_u32(nla.value())?);
+        }
+    }
+    Ok(state)
+}
+
+// struct xfrm_userpolicy_info {
+//     struct xfrm_selector     sel;      0
+//     struct xfrm_lifetime_cfg lft;      56
+//     struct xfrm_lifetime_cur curlft;   120
+//     __u32                    priority; 152
+//     __u32                    index;    156
+//     __u8                     dir;      160
+//     __u8                     action;   161
+//     __u8                     flags;    162
+//     __u8                     share;    163
+// };
+fn parse_ipsec_policy(
+    data: &[u8],
+    ifindex_to_name: &HashMap<String, String>,
+) -> Result<IpsecPolicy, NisporError> {
+    let mut policy = IpsecPolicy {
+        selector: parse_xfrm_selector(
+            &data[..XFRM_SELECTOR_LEN],
+            AF_INET,
+            ifindex_to_name,
+        )?,
+        lifetime: parse_xfrm_lifetime_cfg(
+            &data[56..56 + XFRM_LIFETIME_CFG_LEN],
+        )?,
+        current: parse_xfrm_lifetime_cur(
+            &data[120..120 + XFRM_LIFETIME_CUR_LEN],
+        )?,
+        priority: parse_as_u32(&data[152..156])?,
+        index: parse_as_u32(&data[156..160])?,
+        direction: data[160].into(),
+        action: data[161].into(),
+        ..Default::default()
+    };
+    for nla in NlasIterator::new(&data[XFRM_USERPOLICY_INFO_LEN..]) {
+        let nla = nla?;
+        match nla.kind() {
+            XFRMA_TMPL => {
+                for tmpl in nla.value().chunks_exact(XFRM_USER_TMPL_LEN) {
+                    policy.templates.push(parse_xfrm_user_tmpl(tmpl)?);
+                }
+            }
+            XFRMA_IF_ID => {
+                policy.if_id = Some(parse_as_u32(nla.value())?);
+            }
+            _ => (),
+        }
+    }
+    Ok(policy)
+}
+
+// struct xfrm_user_tmpl {
+//     struct xfrm_id id;       0
+//     __u16          family;   24
+//     xfrm_address_t saddr;    28
+//     __u32          reqid;    44
+//     __u8           mode;     48
+//     __u8           share;    49
+//     __u8           optional; 50
+//     __u32          aalgos;   52
+//     __u32          ealgos;   56
+//     __u32          calgos;   60
+// };
+fn parse_xfrm_user_tmpl(data: &[u8]) -> Result<IpsecTemplate, NisporError> {
+    let family = parse_as_u16(&data[24..26])?;
+    let dst = parse_xfrm_addr(&data[0..16], family)?;
+    let src = parse_xfrm_addr(&data[28..44], family)?;
+    Ok(IpsecTemplate {
+        address_family: (family as u8).into(),
+        src: if is_xfrm_addr_any(&data[28..44]) {
+            None
+        } else {
+            Some(src)
+        },
+        dst: if is_xfrm_addr_any(&data[0..16]) {
+            None
+        } else {
+            Some(dst)
+        },
+        spi: parse_as_be32(&data[16..20])?,
+        protocol: data[20].into(),
+        reqid: parse_as_u32(&data[44..48])?,
+        mode: data[48].into(),
+        optional: data[50] > 0,
+    })
+}
+
+// struct xfrm_selector {
+//     xfrm_address_t daddr;       0
+//     xfrm_address_t saddr;       16
+//     __be16         dport;       32
+//     __be16         dport_mask;  34
+//     __be16         sport;       36
+//     __be16         sport_mask;  38
+//     __u16          family;      40
+//     __u8           prefixlen_d; 42
+//     __u8           prefixlen_s; 43
+//     __u8           proto;       44
+//     int            ifindex;     48
+//     __kernel_uid32_t user;      52
+// };
+// The selector family could be AF_UNSPEC, use the fallback family in that
+// case.
+fn parse_xfrm_selector(
+    data: &[u8],
+    fallback_family: u16,
+    ifindex_to_name: &HashMap<String, String>,
+) -> Result<IpsecSelector, NisporError> {
+    let family = match parse_as_u16(&data[40..42])? {
+        d @ (AF_INET | AF_INET6) => d,
+        _ => fallback_family,
+    };
+    let dport_mask = parse_as_be16(&data[34..36])?;
+    let sport_mask = parse_as_be16(&data[38..40])?;
+    let ifindex = parse_as_i32(&data[48..52])?;
+    Ok(IpsecSelector {
+        dst: format!("{}/{}", parse_xfrm_addr(&data[0..16], family)?, data[42]),
+        src: format!(
+            "{}/{}",
+            parse_xfrm_addr(&data[16..32], family)?,
+            data[43]
+        ),
+        dport: if dport_mask != 0 {
+            Some(parse_as_be16(&data[32..34])?)
+        } else {
+            None
+        },
+        sport: if sport_mask != 0 {
+            Some(parse_as_be16(&data[36..38])?)
+        } else {
+            None
+        },
+        proto: if data[44] != 0 { Some(data[44]) } else { None },
+        iface: if ifindex != 0 {
+            Some(match ifindex_to_name.get(&format!("{ifindex}")) {
+                Some(iface_name) => iface_name.clone(),
+                None => format!("{ifindex}"),
+            })
+        } else {
+            None
+        },
+    })
+}
+
+fn parse_xfrm_lifetime_cfg(data: &[u8]) -> Result<IpsecLifetime, NisporError> {
+    let limit = |d: u64| if d == XFRM_INF { None } else { Some(d) };
+    Ok(IpsecLifetime {
+        soft_byte_limit: limit(parse_as_u64(&data[0..8])?),
+        hard_byte_limit: limit(parse_as_u64(&data[8..16])?),
+        soft_packet_limit: limit(parse_as_u64(&data[16..24])?),
+        hard_packet_limit: limit(parse_as_u64(&data[24..32])?),
+        soft_add_expires_seconds: parse_as_u64(&data[32..40])?,
+        hard_add_expires_seconds: parse_as_u64(&data[40..48])?,
+        soft_use_expires_seconds: parse_as_u64(&data[48..56])?,
+        hard_use_expires_seconds: parse_as_u64(&data[56..64])?,
+    })
+}
+
+fn parse_xfrm_lifetime_cur(
+    data: &[u8],
+) -> Result<IpsecLifetimeCurrent, NisporError> {
+    Ok(IpsecLifetimeCurrent {
+        bytes: parse_as_u64(&data[0..8])?,
+        packets: parse_as_u64(&data[8..16])?,
+        add_time: parse_as_u64(&data[16..24])?,
+        use_time: parse_as_u64(&data[24..32])?,
+    })
+}
+
+// The xfrm_address_t is a 16 bytes union of IPv4 and IPv6 address
+fn parse_xfrm_addr(data: &[u8], family: u16) -> Result<String, NisporError> {
+    Ok(match family {
+        AF_INET6 => parse_as_ipv6(data)?.to_string(),
+        _ => parse_as_ipv4(&data[..4])?.to_string(),
+    })
+}
+
+fn is_xfrm_addr_any(data: &[u8]) -> bool {
+    data.iter().all(|d| *d == 0)
+}
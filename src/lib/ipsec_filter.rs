@@ -0,0 +1,7 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct NetStateIpsecFilter {
+    // Place holder
+}
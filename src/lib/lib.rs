@@ -3,6 +3,8 @@
 #[cfg(test)]
 mod crate_tests;
 mod error;
+mod fdb;
+mod fdb_filter;
 mod filter;
 mod iface_filter;
 // Since rust 1.62, the `#[default]` can be used for setting default value of
@@ -12,41 +14,78 @@ mod iface_filter;
 #[allow(clippy::derivable_impls)]
 mod ifaces;
 mod ip;
+mod ip_sysctl;
+mod ipsec;
+mod ipsec_filter;
 mod mac;
 mod mptcp;
+mod neighbor;
+mod neighbor_filter;
 mod net_conf;
 mod net_state;
 mod netlink;
+mod netns;
+mod netns_filter;
+mod nexthop;
+mod nexthop_filter;
 mod route;
 mod route_filter;
 mod route_rule;
 mod route_rule_filter;
 
 pub use crate::error::NisporError;
+pub use crate::fdb::{FdbEntry, FdbEntryState};
+pub use crate::fdb_filter::NetStateFdbFilter;
 pub use crate::filter::NetStateFilter;
 pub use crate::iface_filter::NetStateIfaceFilter;
 pub use crate::ifaces::{
     BondAdInfo, BondAdSelect, BondAllSubordinatesActive, BondArpValidate,
     BondConf, BondFailOverMac, BondInfo, BondLacpRate, BondMiiStatus, BondMode,
     BondModeArpAllTargets, BondPrimaryReselect, BondSubordinateInfo,
-    BondSubordinateState, BondXmitHashPolicy, BridgeConf, BridgeInfo,
+    BondSubordinateState, BondXmitHashPolicy, BpfProgType, BridgeConf,
+    BridgeInfo, BridgeMdbEntry, BridgeMdbEntryFlag, BridgeMdbEntryState,
+    BridgeMdbFilterMode, BridgeMdbSource, BridgeMulticastRouterPort,
     BridgePortInfo, BridgePortMulticastRouterType, BridgePortStpState,
     BridgeStpState, BridgeVlanEntry, BridgeVlanProtocol, ControllerType,
-    EthtoolCoalesceInfo, EthtoolFeatureInfo, EthtoolInfo,
-    EthtoolLinkModeDuplex, EthtoolLinkModeInfo, EthtoolPauseInfo,
-    EthtoolRingInfo, Iface, IfaceConf, IfaceFlags, IfaceState, IfaceType,
-    IpoibInfo, IpoibMode, MacVlanInfo, MacVlanMode, MacVtapInfo, MacVtapMode,
-    SriovInfo, TunInfo, TunMode, VethConf, VethInfo, VfInfo, VfLinkState,
-    VfState, VlanConf, VlanInfo, VlanProtocol, VrfInfo, VrfSubordinateInfo,
-    VxlanInfo,
+    EthtoolChannelInfo, EthtoolCoalesceConf, EthtoolCoalesceInfo, EthtoolConf,
+    EthtoolDriverInfo, EthtoolEeeInfo, EthtoolFeatureInfo, EthtoolFecInfo,
+    EthtoolFecMode, EthtoolInfo, EthtoolLinkExtState, EthtoolLinkModeConf,
+    EthtoolLinkModeDuplex, EthtoolLinkModeInfo, EthtoolLinkStateInfo,
+    EthtoolPauseConf, EthtoolPauseInfo, EthtoolRingConf, EthtoolRingInfo,
+    EthtoolStatsHistBucket, EthtoolStatsInfo, EthtoolTimestampingInfo,
+    GeneveConf, GeneveInfo, GreInfo, HsrInfo, HsrProtocol, Iface, IfaceConf,
+    IfaceFlags, IfaceHwStats, IfaceOffloadStats, IfaceState, IfaceStats,
+    IfaceType, IpTunnelEncapType, IpTunnelInfo, IpVlanConf, IpVlanFlag,
+    IpVlanInfo, IpVlanMode, IpoibInfo, IpoibMode, MacVlanInfo, MacVlanMode,
+    MacVtapInfo, MacVtapMode, MacsecCipherSuite, MacsecInfo, MacsecOffload,
+    MacsecRxSc, MacsecSa, MacsecValidate, SriovConf, SriovInfo, TcClass,
+    TcFilter, TcFqCodelInfo, TcHtbClassInfo, TcHtbInfo, TcInfo, TcNetemInfo,
+    TcQdisc, TcStats, TcTbfInfo, TunInfo, TunMode, VethConf, VethInfo, VfConf,
+    VfInfo, VfLinkState, VfState, VlanConf, VlanInfo, VlanProtocol, VrfInfo,
+    VrfSubordinateInfo, VxlanInfo, WireguardConf, WireguardInfo,
+    WireguardPeerConf, WireguardPeerInfo, XdpAttachMode, XdpInfo, XdpProgram,
+    XfrmInfo,
 };
 pub use crate::ip::{
-    IpAddrConf, IpConf, IpFamily, Ipv4AddrInfo, Ipv4Info, Ipv6AddrInfo,
-    Ipv6Info,
+    IpAddrConf, IpConf, IpFamily, Ipv4AddrInfo, Ipv4Info, Ipv6AddrGenMode,
+    Ipv6AddrInfo, Ipv6Info,
 };
+pub use crate::ip_sysctl::{Ipv4SysctlInfo, Ipv6SysctlInfo};
+pub use crate::ipsec::{
+    IpsecInfo, IpsecLifetime, IpsecLifetimeCurrent, IpsecMode, IpsecPolicy,
+    IpsecPolicyAction, IpsecPolicyDirection, IpsecProtocol, IpsecSelector,
+    IpsecState, IpsecTemplate,
+};
+pub use crate::ipsec_filter::NetStateIpsecFilter;
 pub use crate::mptcp::{Mptcp, MptcpAddress, MptcpAddressFlag};
+pub use crate::neighbor::{Neighbor, NeighborFlag, NeighborState};
+pub use crate::neighbor_filter::NetStateNeighborFilter;
 pub use crate::net_conf::NetConf;
 pub use crate::net_state::NetState;
+pub use crate::netns::{NetnsInfo, NetnsSelector};
+pub use crate::netns_filter::NetStateNetnsFilter;
+pub use crate::nexthop::{Nexthop, NexthopGroupMember, NexthopGroupType};
+pub use crate::nexthop_filter::NetStateNexthopFilter;
 pub use crate::route::{
     AddressFamily, MultipathRoute, MultipathRouteFlags, Route, RouteConf,
     RouteProtocol, RouteScope, RouteType,
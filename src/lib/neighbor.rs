@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::os::unix::io::AsRawFd;
+
+use futures::stream::TryStreamExt;
+use netlink_packet_route::{
+    neighbour::Nla, NeighbourMessage, NTF_EXT_LEARNED, NTF_MASTER,
+    NTF_OFFLOADED, NTF_PROXY, NTF_ROUTER, NTF_SELF, NTF_USE, NUD_DELAY,
+    NUD_FAILED, NUD_INCOMPLETE, NUD_NOARP, NUD_NONE, NUD_PERMANENT, NUD_PROBE,
+    NUD_REACHABLE, NUD_STALE,
+};
+use rtnetlink::{new_connection, IpVersion};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    filter::enable_kernel_strict_check,
+    mac::parse_as_mac,
+    neighbor_filter::{apply_kernel_neighbor_filter, should_drop_by_filter},
+    netlink::{parse_as_ipv4, parse_as_ipv6, parse_as_u32},
+    AddressFamily, IpFamily, NetStateNeighborFilter, NisporError,
+};
+
+const NTF_STICKY: u8 = 0x40;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct Neighbor {
+    pub address_family: AddressFamily,
+    pub dst: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lladdr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iface: Option<String>,
+    pub state: NeighborState,
+    pub flags: Vec<NeighborFlag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub probes: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum NeighborState {
+    /* Still attempting to resolve */
+    Incomplete,
+    /* Confirmed working address */
+    Reachable,
+    /* Expired, still usable but needs revalidation */
+    Stale,
+    /* Waiting for upper layer confirmation before probing */
+    Delay,
+    /* Sending unicast probes to revalidate */
+    Probe,
+    /* Resolution failed */
+    Failed,
+    /* Device does not need neighbor resolution */
+    Noarp,
+    /* Statically configured */
+    Permanent,
+    None,
+    Other(u16),
+    Unknown,
+}
+
+impl From<u16> for NeighborState {
+    fn from(d: u16) -> Self {
+        match d {
+            NUD_INCOMPLETE => Self::Incomplete,
+            NUD_REACHABLE => Self::Reachable,
+            NUD_STALE => Self::Stale,
+            NUD_DELAY => Self::Delay,
+            NUD_PROBE => Self::Probe,
+            NUD_FAILED => Self::Failed,
+            NUD_NOARP => Self::Noarp,
+            NUD_PERMANENT => Self::Permanent,
+            NUD_NONE => Self::None,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl Default for NeighborState {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum NeighborFlag {
+    Use,
+    #[serde(rename = "self")]
+    OwnSelf,
+    Master,
+    Proxy,
+    ExternLearn,
+    Offloaded,
+    Sticky,
+    Router,
+    Other(u8),
+}
+
+pub(crate) fn parse_neighbor_flags(flags: u8) -> Vec<NeighborFlag> {
+    let mut ret = Vec::new();
+    for (flag, value) in [
+        (NTF_USE, NeighborFlag::Use),
+        (NTF_SELF, NeighborFlag::OwnSelf),
+        (NTF_MASTER, NeighborFlag::Master),
+        (NTF_PROXY, NeighborFlag::Proxy),
+        (NTF_EXT_LEARNED, NeighborFlag::ExternLearn),
+        (NTF_OFFLOADED, NeighborFlag::Offloaded),
+        (NTF_STICKY, NeighborFlag::Sticky),
+        (NTF_ROUTER, NeighborFlag::Router),
+    ] {
+        if flags & flag > 0 {
+            ret.push(value);
+        }
+    }
+    ret
+}
+
+pub(crate) async fn get_neighbors(
+    iface_name2index: &HashMap<String, u32>,
+    filter: &NetStateNeighborFilter,
+) -> Result<Vec<Neighbor>, NisporError> {
+    let mut neighbors = Vec::new();
+    let (mut connection, handle, _) = new_connection()?;
+
+    let mut ifindex_to_name = HashMap::new();
+    for (name, index) in iface_name2index.iter() {
+        ifindex_to_name.insert(format!("{index}"), name.to_string());
+    }
+
+    enable_kernel_strict_check(connection.socket_mut().as_raw_fd())?;
+    tokio::spawn(connection);
+
+    let ip_families = match filter.family {
+        Some(IpFamily::Ipv4) => vec![IpVersion::V4],
+        Some(IpFamily::Ipv6) => vec![IpVersion::V6],
+        None => vec![IpVersion::V6, IpVersion::V4],
+    };
+
+    for ip_family in ip_families {
+        // Proxy entries are stored in a different table in kernel which
+        // requires a dedicated dump request.
+        for is_proxy in [false, true] {
+            let mut nb_handle =
+                handle.neighbours().get().set_family(ip_family.clone());
+            if is_proxy {
+                nb_handle = nb_handle.proxies();
+            }
+            apply_kernel_neighbor_filter(
+                &mut nb_handle,
+                filter,
+                iface_name2index,
+            )?;
+            let mut nb_msgs = nb_handle.execute();
+            while let Some(nb_msg) = nb_msgs.try_next().await? {
+                let neighbor = get_neighbor(nb_msg, &ifindex_to_name)?;
+                if should_drop_by_filter(&neighbor, filter) {
+                    continue;
+                }
+                neighbors.push(neighbor);
+            }
+        }
+    }
+    Ok(neighbors)
+}
+
+fn get_neighbor(
+    nb_msg: NeighbourMessage,
+    ifindex_to_name: &HashMap<String, String>,
+) -> Result<Neighbor, NisporError> {
+    let mut neighbor = Neighbor::default();
+    let header = &nb_msg.header;
+    neighbor.address_family = header.family.into();
+    neighbor.state = header.state.into();
+    neighbor.flags = parse_neighbor_flags(header.flags);
+    if header.ifindex > 0 {
+        neighbor.iface =
+            Some(match ifindex_to_name.get(&format!("{}", header.ifindex)) {
+                Some(iface_name) => iface_name.clone(),
+                None => format!("{}", header.ifindex),
+            });
+    }
+    for nla in &nb_msg.nlas {
+        match nla {
+            Nla::Destination(d) => {
+                neighbor.dst = match neighbor.address_family {
+                    AddressFamily::IPv4 => parse_as_ipv4(d)?.to_string(),
+                    AddressFamily::IPv6 => parse_as_ipv6(d)?.to_string(),
+                    _ => format!("{d:?}"),
+                };
+            }
+            Nla::LinkLocalAddress(d) => {
+                if !d.is_empty() {
+                    neighbor.lladdr = Some(parse_as_mac(d.len(), d)?);
+                }
+            }
+            Nla::Probes(d) => {
+                neighbor.probes = Some(parse_as_u32(d)?);
+            }
+            Nla::CacheInfo(_) => (),
+            _ => log::debug!("Unknown NLA message for neighbor {:?}", nla),
+        }
+    }
+    Ok(neighbor)
+}
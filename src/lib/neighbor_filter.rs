@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::neighbour::Nla;
+use rtnetlink::NeighbourGetRequest;
+
+use crate::{IpFamily, Neighbor, NeighborState, NisporError};
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct NetStateNeighborFilter {
+    /// Returned neighbors will only contain entries of specified interface.
+    pub iface: Option<String>,
+    /// Returned neighbors will only contain entries of specified IP family.
+    pub family: Option<IpFamily>,
+    /// Returned neighbors will only contain entries in specified state.
+    pub state: Option<NeighborState>,
+}
+
+pub(crate) fn apply_kernel_neighbor_filter(
+    handle: &mut NeighbourGetRequest,
+    filter: &NetStateNeighborFilter,
+    iface_name2index: &HashMap<String, u32>,
+) -> Result<(), NisporError> {
+    if let Some(iface) = filter.iface.as_ref() {
+        match iface_name2index.get(iface) {
+            Some(index) => {
+                handle.message_mut().nlas.push(Nla::IfIndex(*index));
+            }
+            None => {
+                let e = NisporError::invalid_argument(format!(
+                    "Interface {iface} not found"
+                ));
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn should_drop_by_filter(
+    neighbor: &Neighbor,
+    filter: &NetStateNeighborFilter,
+) -> bool {
+    // Kernel does not support filtering neighbors by state, hence we do it
+    // in user space.
+    (filter.state.is_some() && filter.state != Some(neighbor.state))
+        || (filter.iface.is_some() && filter.iface != neighbor.iface)
+}
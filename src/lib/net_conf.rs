@@ -2,8 +2,8 @@
 
 use crate::{
     ifaces::{
-        change_ifaces, create_ifaces, delete_ifaces, get_iface_name2index,
-        get_ifaces,
+        apply_sriov_total_vfs, change_ifaces, create_ifaces, delete_ifaces,
+        get_iface_name2index, get_ifaces,
     },
     route::apply_routes_conf,
     IfaceConf, IfaceState, NisporError, RouteConf,
@@ -44,7 +44,12 @@ impl NetConf {
             rt.block_on(delete_ifaces(&del_ifaces))?;
             rt.block_on(create_ifaces(&new_ifaces, &cur_iface_name_2_index))?;
 
-            let cur_ifaces = rt.block_on(get_ifaces(None))?;
+            let mut cur_ifaces = rt.block_on(get_ifaces(None))?;
+            // All VFs are recreated after total VFs changed, refresh the
+            // current state for applying VF properties.
+            if apply_sriov_total_vfs(&chg_ifaces, &cur_ifaces)? {
+                cur_ifaces = rt.block_on(get_ifaces(None))?;
+            }
             rt.block_on(change_ifaces(&chg_ifaces, &cur_ifaces))?;
         }
 
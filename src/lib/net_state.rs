@@ -7,11 +7,16 @@ use tokio::runtime;
 
 use crate::{
     error::NisporError,
-    ifaces::{get_ifaces, Iface},
+    fdb::{get_fdb_entries, FdbEntry},
+    ifaces::{get_ifaces, sriov_remove_sysfs_info, Iface},
+    ipsec::{get_ipsec, IpsecInfo},
     mptcp::{get_mptcp, merge_mptcp_info, Mptcp},
+    neighbor::{get_neighbors, Neighbor},
+    netns::{get_netns_list, resolve_cross_netns_links, NetnsGuard, NetnsInfo},
+    nexthop::{get_nexthops, Nexthop},
     route::{get_routes, Route},
     route_rule::{get_route_rules, RouteRule},
-    NetStateFilter, NetStateIfaceFilter,
+    NetStateFilter, NetStateIfaceFilter, NetStateNetnsFilter,
 };
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
@@ -20,8 +25,18 @@ pub struct NetState {
     pub ifaces: HashMap<String, Iface>,
     pub routes: Vec<Route>,
     pub rules: Vec<RouteRule>,
+    #[serde(default)]
+    pub neighbors: Vec<Neighbor>,
+    #[serde(default)]
+    pub fdb: Vec<FdbEntry>,
+    #[serde(default)]
+    pub nexthops: Vec<Nexthop>,
+    #[serde(default)]
+    pub netns_list: Vec<NetnsInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mptcp: Option<Mptcp>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipsec: Option<IpsecInfo>,
 }
 
 impl NetState {
@@ -34,6 +49,12 @@ impl NetState {
     pub fn retrieve_with_filter(
         filter: &NetStateFilter,
     ) -> Result<NetState, NisporError> {
+        // The current thread runtime ensures all sockets are created in the
+        // selected network namespace.
+        let _netns_guard = match filter.netns.as_ref() {
+            Some(netns) => Some(NetnsGuard::enter(netns)?),
+            None => None,
+        };
         let rt = runtime::Builder::new_current_thread().enable_io().build()?;
         rt.block_on(Self::_retrieve_with_filter(filter))
     }
@@ -47,6 +68,10 @@ impl NetState {
             get_ifaces(filter.iface.as_ref()).await?
         };
 
+        if filter.netns.is_some() {
+            sriov_remove_sysfs_info(&mut ifaces);
+        }
+
         let mut ifname_to_index = HashMap::new();
         for iface in ifaces.values() {
             ifname_to_index.insert(iface.name.clone(), iface.index);
@@ -64,6 +89,52 @@ impl NetState {
             Vec::new()
         };
 
+        let neighbors = if let Some(neighbor_filter) = filter.neighbor.as_ref()
+        {
+            get_neighbors(&ifname_to_index, neighbor_filter).await?
+        } else {
+            Vec::new()
+        };
+
+        let fdb = if let Some(fdb_filter) = filter.fdb.as_ref() {
+            get_fdb_entries(&ifname_to_index, fdb_filter).await?
+        } else {
+            Vec::new()
+        };
+
+        let nexthops = if let Some(nexthop_filter) = filter.nexthop.as_ref() {
+            get_nexthops(&ifname_to_index, nexthop_filter).await?
+        } else {
+            Vec::new()
+        };
+
+        let ipsec = if let Some(ipsec_filter) = filter.ipsec.as_ref() {
+            get_ipsec(&ifname_to_index, ipsec_filter).await
+        } else {
+            None
+        };
+
+        // The network namespace list is also required for resolving
+        // interfaces linked to other network namespace.
+        let netns_list = if filter.netns_list.is_some()
+            || (filter.iface.is_some()
+                && ifaces.values().any(|i| i.link_netnsid.is_some()))
+        {
+            let netns_list = get_netns_list(
+                filter
+                    .netns_list
+                    .as_ref()
+                    .unwrap_or(&NetStateNetnsFilter::default()),
+            )
+            .await?;
+            if filter.iface.is_some() {
+                resolve_cross_netns_links(&mut ifaces, &netns_list).await;
+            }
+            netns_list
+        } else {
+            Vec::new()
+        };
+
         let mptcp =
             if filter.iface.as_ref().map(|f| f.include_mptcp) == Some(true) {
                 let mut mptcp = get_mptcp().await?;
@@ -79,7 +150,16 @@ impl NetState {
             ifaces,
             routes,
             rules,
+            neighbors,
+            fdb,
+            nexthops,
+            netns_list: if filter.netns_list.is_some() {
+                netns_list
+            } else {
+                Vec::new()
+            },
             mptcp,
+            ipsec,
         })
     }
 }
@@ -0,0 +1,281 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_route::{AF_BRIDGE, RTM_GETMDB, RTM_NEWMDB};
+use netlink_packet_utils::nla::NlasIterator;
+use netlink_sys::protocols::NETLINK_ROUTE;
+
+use crate::{
+    ifaces::parse_bridge_mdb_entry_flags,
+    mac::{parse_as_mac, ETH_ALEN},
+    netlink::{
+        netlink_dump, parse_as_ipv4, parse_as_ipv6, parse_as_u16, parse_as_u32,
+        parse_as_u8,
+    },
+    BridgeMdbEntry, BridgeMdbSource, BridgeMulticastRouterPort, Iface,
+    NisporError,
+};
+
+// struct br_port_msg
+const BR_PORT_MSG_LEN: usize = 8;
+
+const MDBA_MDB: u16 = 1;
+const MDBA_ROUTER: u16 = 2;
+
+const MDBA_MDB_ENTRY: u16 = 1;
+const MDBA_MDB_ENTRY_INFO: u16 = 1;
+
+const MDBA_MDB_EATTR_TIMER: u16 = 1;
+const MDBA_MDB_EATTR_SRC_LIST: u16 = 2;
+const MDBA_MDB_EATTR_GROUP_MODE: u16 = 3;
+const MDBA_MDB_EATTR_RTPROT: u16 = 5;
+
+const MDBA_MDB_SRCLIST_ENTRY: u16 = 1;
+const MDBA_MDB_SRCATTR_ADDRESS: u16 = 1;
+const MDBA_MDB_SRCATTR_TIMER: u16 = 2;
+
+const MDBA_ROUTER_PORT: u16 = 1;
+const MDBA_ROUTER_PATTR_TIMER: u16 = 1;
+const MDBA_ROUTER_PATTR_TYPE: u16 = 2;
+const MDBA_ROUTER_PATTR_INET_TIMER: u16 = 3;
+const MDBA_ROUTER_PATTR_INET6_TIMER: u16 = 4;
+const MDBA_ROUTER_PATTR_VID: u16 = 5;
+
+// struct br_mdb_entry is 4 bytes aligned.
+const BR_MDB_ENTRY_LEN: usize = 28;
+const BR_MDB_ENTRY_ADDR_OFFSET: usize = 8;
+const BR_MDB_ENTRY_PROTO_OFFSET: usize = 24;
+
+const ETH_P_IP: u16 = 0x0800;
+const ETH_P_IPV6: u16 = 0x86DD;
+
+pub(crate) async fn fill_bridge_mdb_info(
+    iface_states: &mut HashMap<String, Iface>,
+) -> Result<(), NisporError> {
+    if !iface_states.values().any(|i| i.bridge.is_some()) {
+        return Ok(());
+    }
+    let mut index_to_name = HashMap::new();
+    for iface in iface_states.values() {
+        index_to_name.insert(format!("{}", iface.index), iface.name.clone());
+    }
+
+    // Kernel does not support filtering MDB dump by bridge interface index.
+    let mut br_port_msg = [0u8; BR_PORT_MSG_LEN];
+    br_port_msg[0] = AF_BRIDGE as u8;
+    for nl_msg in
+        netlink_dump(NETLINK_ROUTE, RTM_GETMDB, &br_port_msg, false).await?
+    {
+        if nl_msg.msg_type != RTM_NEWMDB
+            || nl_msg.payload.len() < BR_PORT_MSG_LEN
+        {
+            continue;
+        }
+        let br_index = parse_as_u32(&nl_msg.payload[4..8])?;
+        let br_name = match index_to_name.get(&format!("{br_index}")) {
+            Some(n) => n.to_string(),
+            None => continue,
+        };
+        let bridge_info = match iface_states
+            .get_mut(&br_name)
+            .and_then(|i| i.bridge.as_mut())
+        {
+            Some(b) => b,
+            None => continue,
+        };
+        for nla in NlasIterator::new(&nl_msg.payload[BR_PORT_MSG_LEN..]) {
+            let nla = nla?;
+            match nla.kind() {
+                MDBA_MDB => {
+                    let entries =
+                        parse_mdb_entries(nla.value(), &index_to_name)?;
+                    match bridge_info.mdb.as_mut() {
+                        Some(mdb) => mdb.extend(entries),
+                        None => bridge_info.mdb = Some(entries),
+                    }
+                }
+                MDBA_ROUTER => {
+                    let ports =
+                        parse_mdb_router_ports(nla.value(), &index_to_name)?;
+                    match bridge_info.multicast_router_ports.as_mut() {
+                        Some(rt_ports) => rt_ports.extend(ports),
+                        None => {
+                            bridge_info.multicast_router_ports = Some(ports)
+                        }
+                    }
+                }
+                _ => log::debug!(
+                    "Unknown bridge MDB NLA {} {:?}",
+                    nla.kind(),
+                    nla.value()
+                ),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_mdb_entries(
+    data: &[u8],
+    index_to_name: &HashMap<String, String>,
+) -> Result<Vec<BridgeMdbEntry>, NisporError> {
+    let mut entries = Vec::new();
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        if nla.kind() != MDBA_MDB_ENTRY {
+            continue;
+        }
+        for info_nla in NlasIterator::new(nla.value()) {
+            let info_nla = info_nla?;
+            if info_nla.kind() == MDBA_MDB_ENTRY_INFO {
+                entries.push(parse_mdb_entry_info(
+                    info_nla.value(),
+                    index_to_name,
+                )?);
+            }
+        }
+    }
+    Ok(entries)
+}
+
+fn parse_mdb_entry_info(
+    data: &[u8],
+    index_to_name: &HashMap<String, String>,
+) -> Result<BridgeMdbEntry, NisporError> {
+    if data.len() < BR_MDB_ENTRY_LEN {
+        return Err(NisporError::bug(format!(
+            "Got invalid struct br_mdb_entry {data:?}"
+        )));
+    }
+    let mut entry = BridgeMdbEntry::default();
+    let port_index = parse_as_u32(&data[0..4])?;
+    entry.port = match index_to_name.get(&format!("{port_index}")) {
+        Some(n) => n.to_string(),
+        None => format!("{port_index}"),
+    };
+    entry.state = parse_as_u8(&data[4..5])?.into();
+    entry.flags = parse_bridge_mdb_entry_flags(parse_as_u8(&data[5..6])?);
+    let vid = parse_as_u16(&data[6..8])?;
+    if vid != 0 {
+        entry.vid = Some(vid);
+    }
+    let addr = &data[BR_MDB_ENTRY_ADDR_OFFSET..BR_MDB_ENTRY_PROTO_OFFSET];
+    entry.group = match u16::from_be_bytes([
+        data[BR_MDB_ENTRY_PROTO_OFFSET],
+        data[BR_MDB_ENTRY_PROTO_OFFSET + 1],
+    ]) {
+        ETH_P_IP => parse_as_ipv4(&addr[..4])?.to_string(),
+        ETH_P_IPV6 => parse_as_ipv6(addr)?.to_string(),
+        _ => parse_as_mac(ETH_ALEN, addr)?,
+    };
+
+    for nla in NlasIterator::new(&data[BR_MDB_ENTRY_LEN..]) {
+        let nla = nla?;
+        match nla.kind() {
+            MDBA_MDB_EATTR_TIMER => {
+                entry.timer = Some(parse_as_u32(nla.value())?);
+            }
+            MDBA_MDB_EATTR_SRC_LIST => {
+                entry.sources = Some(parse_mdb_sources(nla.value())?);
+            }
+            MDBA_MDB_EATTR_GROUP_MODE => {
+                entry.filter_mode = Some(parse_as_u8(nla.value())?.into());
+            }
+            MDBA_MDB_EATTR_RTPROT => {
+                entry.protocol = Some(parse_as_u8(nla.value())?.into());
+            }
+            _ => log::debug!(
+                "Unknown bridge MDB entry NLA {} {:?}",
+                nla.kind(),
+                nla.value()
+            ),
+        }
+    }
+    Ok(entry)
+}
+
+fn parse_mdb_sources(data: &[u8]) -> Result<Vec<BridgeMdbSource>, NisporError> {
+    let mut sources = Vec::new();
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        if nla.kind() != MDBA_MDB_SRCLIST_ENTRY {
+            continue;
+        }
+        let mut source = BridgeMdbSource::default();
+        for src_nla in NlasIterator::new(nla.value()) {
+            let src_nla = src_nla?;
+            match src_nla.kind() {
+                MDBA_MDB_SRCATTR_ADDRESS => {
+                    let d = src_nla.value();
+                    source.address = if d.len() == 16 {
+                        parse_as_ipv6(d)?.to_string()
+                    } else {
+                        parse_as_ipv4(d)?.to_string()
+                    };
+                }
+                MDBA_MDB_SRCATTR_TIMER => {
+                    source.timer = Some(parse_as_u32(src_nla.value())?);
+                }
+                _ => log::debug!(
+                    "Unknown bridge MDB source NLA {} {:?}",
+                    src_nla.kind(),
+                    src_nla.value()
+                ),
+            }
+        }
+        sources.push(source);
+    }
+    Ok(sources)
+}
+
+fn parse_mdb_router_ports(
+    data: &[u8],
+    index_to_name: &HashMap<String, String>,
+) -> Result<Vec<BridgeMulticastRouterPort>, NisporError> {
+    let mut ports = Vec::new();
+    for nla in NlasIterator::new(data) {
+        let nla = nla?;
+        if nla.kind() != MDBA_ROUTER_PORT {
+            continue;
+        }
+        let d = nla.value();
+        let mut port = BridgeMulticastRouterPort::default();
+        let port_index = parse_as_u32(d)?;
+        port.port = match index_to_name.get(&format!("{port_index}")) {
+            Some(n) => n.to_string(),
+            None => format!("{port_index}"),
+        };
+        // Old kernel only provides the port index
+        if d.len() > 4 {
+            for pattr in NlasIterator::new(&d[4..]) {
+                let pattr = pattr?;
+                match pattr.kind() {
+                    MDBA_ROUTER_PATTR_TIMER => {
+                        port.timer = Some(parse_as_u32(pattr.value())?);
+                    }
+                    MDBA_ROUTER_PATTR_TYPE => {
+                        port.router_type =
+                            Some(parse_as_u8(pattr.value())?.into());
+                    }
+                    MDBA_ROUTER_PATTR_INET_TIMER => {
+                        port.inet_timer = Some(parse_as_u32(pattr.value())?);
+                    }
+                    MDBA_ROUTER_PATTR_INET6_TIMER => {
+                        port.inet6_timer = Some(parse_as_u32(pattr.value())?);
+                    }
+                    MDBA_ROUTER_PATTR_VID => {
+                        port.vid = Some(parse_as_u16(pattr.value())?);
+                    }
+                    _ => log::debug!(
+                        "Unknown bridge multicast router port NLA {} {:?}",
+                        pattr.kind(),
+                        pattr.value()
+                    ),
+                }
+            }
+        }
+        ports.push(port);
+    }
+    Ok(ports)
+}
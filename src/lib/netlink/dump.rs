@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Some netlink messages(e.g. RTM_GETMDB) are not supported by
+// netlink-packet-route yet, this module provides a minimum netlink dump and
+// request implementation returning the raw payload of each reply message.
+
+use netlink_sys::{AsyncSocket, AsyncSocketExt, SocketAddr, TokioSocket};
+
+use crate::{
+    netlink::{parse_as_i32, parse_as_u16, parse_as_u32},
+    NisporError,
+};
+
+const NLMSG_HDR_LEN: usize = 16;
+const NLMSG_ERROR: u16 = 2;
+const NLMSG_DONE: u16 = 3;
+const NLM_F_REQUEST: u16 = 1;
+const NLM_F_ACK: u16 = 4;
+const NLM_F_DUMP: u16 = 0x300;
+const NL_SEQ: u32 = 1;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) struct NetlinkRawMessage {
+    pub(crate) msg_type: u16,
+    pub(crate) payload: Vec<u8>,
+}
+
+pub(crate) async fn netlink_dump(
+    protocol: isize,
+    msg_type: u16,
+    payload: &[u8],
+    strict_check: bool,
+) -> Result<Vec<NetlinkRawMessage>, NisporError> {
+    netlink_send_recv(protocol, msg_type, payload, strict_check, true, None)
+        .await
+}
+
+// Send a non-dump request and return the reply message if any.
+// The request is sent with NLM_F_ACK, hence requests without reply(e.g.
+// setting) will return Ok(None) on success.
+pub(crate) async fn netlink_request(
+    protocol: isize,
+    msg_type: u16,
+    payload: &[u8],
+) -> Result<Option<NetlinkRawMessage>, NisporError> {
+    Ok(
+        netlink_send_recv(protocol, msg_type, payload, false, false, None)
+            .await?
+            .pop(),
+    )
+}
+
+// Same as `netlink_request()`, but return Ok(None) when kernel replied with
+// specified errno, e.g. EOPNOTSUPP for querying interface not supporting it.
+pub(crate) async fn netlink_request_ignore_errno(
+    protocol: isize,
+    msg_type: u16,
+    payload: &[u8],
+    ignore_errno: i32,
+) -> Result<Option<NetlinkRawMessage>, NisporError> {
+    Ok(netlink_send_recv(
+        protocol,
+        msg_type,
+        payload,
+        false,
+        false,
+        Some(ignore_errno),
+    )
+    .await?
+    .pop())
+}
+
+async fn netlink_send_recv(
+    protocol: isize,
+    msg_type: u16,
+    payload: &[u8],
+    strict_check: bool,
+    is_dump: bool,
+    ignore_errno: Option<i32>,
+) -> Result<Vec<NetlinkRawMessage>, NisporError> {
+    let mut socket = TokioSocket::new(protocol)?;
+    socket.socket_mut().bind_auto()?;
+    socket.socket_mut().connect(&SocketAddr::new(0, 0))?;
+    if strict_check {
+        socket.socket_mut().set_netlink_get_strict_chk(true)?;
+    }
+
+    let mut buffer = Vec::with_capacity(NLMSG_HDR_LEN + payload.len());
+    buffer.extend_from_slice(
+        &((NLMSG_HDR_LEN + payload.len()) as u32).to_ne_bytes(),
+    );
+    buffer.extend_from_slice(&msg_type.to_ne_bytes());
+    let flags = if is_dump {
+        NLM_F_REQUEST | NLM_F_DUMP
+    } else {
+        NLM_F_REQUEST | NLM_F_ACK
+    };
+    buffer.extend_from_slice(&flags.to_ne_bytes());
+    buffer.extend_from_slice(&NL_SEQ.to_ne_bytes());
+    buffer.extend_from_slice(&0u32.to_ne_bytes());
+    buffer.extend_from_slice(payload);
+    socket.send(&buffer).await?;
+
+    let mut ret = Vec::new();
+    loop {
+        let (data, _) = socket.recv_from_full().await?;
+        let mut offset = 0;
+        while data.len() >= offset + NLMSG_HDR_LEN {
+            let msg_len = parse_as_u32(&data[offset..offset + 4])? as usize;
+            let cur_msg_type = parse_as_u16(&data[offset + 4..offset + 6])?;
+            if msg_len < NLMSG_HDR_LEN || offset + msg_len > data.len() {
+                return Err(NisporError::bug(format!(
+                    "Got invalid netlink message length {msg_len}"
+                )));
+            }
+            let msg_payload = &data[offset + NLMSG_HDR_LEN..offset + msg_len];
+            match cur_msg_type {
+                NLMSG_DONE => return Ok(ret),
+                NLMSG_ERROR => {
+                    let errno = parse_as_i32(msg_payload)?;
+                    if errno != 0 && ignore_errno == Some(-errno) {
+                        return Ok(Vec::new());
+                    } else if errno != 0 {
+                        return Err(netlink_errno_to_nispor_error(
+                            errno, msg_type,
+                        ));
+                    } else if !is_dump {
+                        return Ok(ret);
+                    }
+                }
+                _ => {
+                    ret.push(NetlinkRawMessage {
+                        msg_type: cur_msg_type,
+                        payload: msg_payload.to_vec(),
+                    });
+                    // Non-dump request has only one reply without
+                    // NLMSG_DONE
+                    if !is_dump {
+                        return Ok(ret);
+                    }
+                }
+            }
+            // Netlink messages are 4 bytes aligned
+            offset += (msg_len + 3) & !3;
+        }
+    }
+}
+
+fn netlink_errno_to_nispor_error(errno: i32, msg_type: u16) -> NisporError {
+    let io_error = std::io::Error::from_raw_os_error(-errno);
+    if -errno == libc::EPERM {
+        NisporError::permission_deny(format!(
+            "Permission denied for netlink message type {msg_type}: \
+            {io_error}"
+        ))
+    } else {
+        NisporError::bug(format!(
+            "Got netlink error for message type {msg_type}: \
+            code {errno}, msg: {io_error}"
+        ))
+    }
+}
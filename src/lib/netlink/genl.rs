@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Minimum generic netlink support on top of the raw netlink dump and request
+// for families not supported by other crates yet.
+
+use netlink_packet_utils::{
+    nla::{DefaultNla, NlasIterator},
+    Emitable,
+};
+use netlink_sys::protocols::NETLINK_GENERIC;
+
+use crate::{
+    netlink::{
+        netlink_dump, netlink_request, netlink_request_ignore_errno,
+        parse_as_string, parse_as_u16,
+    },
+    NisporError,
+};
+
+const GENL_ID_CTRL: u16 = 0x10;
+const CTRL_CMD_GETFAMILY: u8 = 3;
+const CTRL_ATTR_FAMILY_ID: u16 = 1;
+const CTRL_ATTR_FAMILY_NAME: u16 = 2;
+
+// struct genlmsghdr
+const GENL_HDR_LEN: usize = 4;
+
+// Return None if specified generic netlink family is not registered, which
+// normally means the kernel module is not loaded.
+pub(crate) async fn genl_family_id(
+    family_name: &str,
+) -> Result<Option<u16>, NisporError> {
+    for payload in genl_dump(GENL_ID_CTRL, CTRL_CMD_GETFAMILY, 1, &[]).await? {
+        let mut family_id = None;
+        let mut name = None;
+        for nla in NlasIterator::new(payload.as_slice()) {
+            let nla = nla?;
+            match nla.kind() {
+                CTRL_ATTR_FAMILY_ID => {
+                    family_id = Some(parse_as_u16(nla.value())?);
+                }
+                CTRL_ATTR_FAMILY_NAME => {
+                    name = Some(parse_as_string(nla.value()));
+                }
+                _ => (),
+            }
+        }
+        if name.as_deref() == Some(family_name) {
+            return Ok(family_id);
+        }
+    }
+    Ok(None)
+}
+
+// Return the attributes data following the generic netlink header of each
+// reply message.
+pub(crate) async fn genl_dump(
+    family_id: u16,
+    cmd: u8,
+    version: u8,
+    nlas: &[DefaultNla],
+) -> Result<Vec<Vec<u8>>, NisporError> {
+    let mut ret = Vec::new();
+    for nl_msg in netlink_dump(
+        NETLINK_GENERIC,
+        family_id,
+        &gen_genl_payload(cmd, version, nlas),
+        false,
+    )
+    .await?
+    {
+        if nl_msg.msg_type == family_id && nl_msg.payload.len() >= GENL_HDR_LEN
+        {
+            ret.push(nl_msg.payload[GENL_HDR_LEN..].to_vec());
+        }
+    }
+    Ok(ret)
+}
+
+// Send generic netlink request and wait for the acknowledgement
+pub(crate) async fn genl_request(
+    family_id: u16,
+    cmd: u8,
+    version: u8,
+    nlas: &[DefaultNla],
+) -> Result<(), NisporError> {
+    netlink_request(
+        NETLINK_GENERIC,
+        family_id,
+        &gen_genl_payload(cmd, version, nlas),
+    )
+    .await?;
+    Ok(())
+}
+
+// Send generic netlink request and return the attributes data following the
+// generic netlink header of the reply message.
+// Return None if the kernel replied with EOPNOTSUPP.
+pub(crate) async fn genl_get(
+    family_id: u16,
+    cmd: u8,
+    version: u8,
+    nlas: &[DefaultNla],
+) -> Result<Option<Vec<u8>>, NisporError> {
+    Ok(netlink_request_ignore_errno(
+        NETLINK_GENERIC,
+        family_id,
+        &gen_genl_payload(cmd, version, nlas),
+        libc::EOPNOTSUPP,
+    )
+    .await?
+    .and_then(|nl_msg| {
+        if nl_msg.msg_type == family_id && nl_msg.payload.len() >= GENL_HDR_LEN
+        {
+            Some(nl_msg.payload[GENL_HDR_LEN..].to_vec())
+        } else {
+            None
+        }
+    }))
+}
+
+// Emit the attributes as data of a nested attribute
+pub(crate) fn emit_nlas(nlas: &[DefaultNla]) -> Vec<u8> {
+    let mut buffer = vec![0u8; nlas.buffer_len()];
+    nlas.emit(&mut buffer);
+    buffer
+}
+
+fn gen_genl_payload(cmd: u8, version: u8, nlas: &[DefaultNla]) -> Vec<u8> {
+    let mut payload = vec![0u8; GENL_HDR_LEN + nlas.buffer_len()];
+    payload[0] = cmd;
+    payload[1] = version;
+    nlas.emit(&mut payload[GENL_HDR_LEN..]);
+    payload
+}
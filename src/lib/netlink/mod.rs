@@ -1,13 +1,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod bridge;
+mod bridge_mdb;
 mod bridge_port;
 mod bridge_vlan;
+mod dump;
+mod genl;
 mod ip;
 mod nla;
 
 pub(crate) use crate::netlink::bridge::*;
+pub(crate) use crate::netlink::bridge_mdb::*;
 pub(crate) use crate::netlink::bridge_port::*;
 pub(crate) use crate::netlink::bridge_vlan::*;
+pub(crate) use crate::netlink::dump::*;
+pub(crate) use crate::netlink::genl::*;
 pub(crate) use crate::netlink::ip::*;
 pub(crate) use crate::netlink::nla::*;
@@ -106,3 +106,19 @@ pub(crate) fn parse_as_ipv6(data: &[u8]) -> Result<Ipv6Addr, NisporError> {
     })?;
     Ok(Ipv6Addr::from(addr_bytes))
 }
+
+// Parse network order u16
+pub(crate) fn parse_as_be16(data: &[u8]) -> Result<u16, NisporError> {
+    Ok(u16::from_be(parse_as_u16(data)?))
+}
+
+// Parse network order u32
+pub(crate) fn parse_as_be32(data: &[u8]) -> Result<u32, NisporError> {
+    Ok(u32::from_be(parse_as_u32(data)?))
+}
+
+pub(crate) fn parse_as_string(data: &[u8]) -> String {
+    String::from_utf8_lossy(data)
+        .trim_end_matches('\0')
+        .to_string()
+}
@@ -0,0 +1,359 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::io::AsRawFd;
+
+use netlink_packet_utils::{
+    nla::{DefaultNla, NlasIterator},
+    Emitable,
+};
+use netlink_sys::protocols::NETLINK_ROUTE;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    netlink::{netlink_request, parse_as_i32, parse_as_string},
+    Iface, IfaceType, NetStateNetnsFilter, NisporError, VethInfo,
+};
+
+const NETNS_RUN_DIR: &str = "/run/netns";
+const CURRENT_THREAD_NETNS_PATH: &str = "/proc/thread-self/ns/net";
+
+const RTM_NEWLINK: u16 = 16;
+const RTM_GETLINK: u16 = 18;
+const RTM_NEWNSID: u16 = 88;
+const RTM_GETNSID: u16 = 90;
+
+// struct rtgenmsg with padding
+const RTGENMSG_LEN: usize = 4;
+// struct ifinfomsg
+const IFINFOMSG_LEN: usize = 16;
+
+const NETNSA_NSID: u16 = 1;
+const NETNSA_FD: u16 = 3;
+const NETNSA_NSID_NOT_ASSIGNED: i32 = -1;
+
+const IFLA_IFNAME: u16 = 3;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct NetnsInfo {
+    /// Name of network namespace created by `ip netns add`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Path to the network namespace file
+    pub path: String,
+    /// Inode number of the network namespace, unique for each namespace
+    pub inode: u64,
+    /// The netnsid assigned to this network namespace by the queried network
+    /// namespace. Matches the `link_netnsid` of interfaces.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nsid: Option<i32>,
+    /// Processes running in this network namespace. Only included when
+    /// `NetStateNetnsFilter.include_process` is true.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub pids: Vec<u32>,
+}
+
+impl NetnsInfo {
+    // Named network namespace is identified by name, others by path.
+    fn id(&self) -> String {
+        match self.name.as_ref() {
+            Some(name) => name.to_string(),
+            None => self.path.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum NetnsSelector {
+    /// Network namespace created by `ip netns add`, stored in `/run/netns/`
+    Name(String),
+    /// Path to network namespace file, e.g. `/var/run/docker/netns/xxx`
+    Path(String),
+    /// Network namespace used by specified process
+    Pid(u32),
+}
+
+impl NetnsSelector {
+    fn path(&self) -> Result<String, NisporError> {
+        match self {
+            Self::Name(name) => {
+                if name.is_empty() || name.contains('/') || name == ".." {
+                    let e = NisporError::invalid_argument(format!(
+                        "Invalid network namespace name {name}"
+                    ));
+                    log::error!("{}", e);
+                    Err(e)
+                } else {
+                    Ok(format!("{NETNS_RUN_DIR}/{name}"))
+                }
+            }
+            Self::Path(path) => Ok(path.to_string()),
+            Self::Pid(pid) => Ok(format!("/proc/{pid}/ns/net")),
+        }
+    }
+}
+
+// Switch the network namespace of current thread and switch back when
+// dropped. All the netlink sockets created in between will be bound to the
+// selected network namespace.
+// The sysfs content is not affected as it is bound to the network namespace
+// of mount namespace, hence sysfs-derived information is removed from the
+// result when querying other network namespace.
+pub(crate) struct NetnsGuard {
+    orig_netns: File,
+}
+
+impl NetnsGuard {
+    pub(crate) fn enter(netns: &NetnsSelector) -> Result<Self, NisporError> {
+        let path = netns.path()?;
+        let target_netns = File::open(&path).map_err(|e| {
+            let e = if e.kind() == std::io::ErrorKind::NotFound {
+                NisporError::invalid_argument(format!(
+                    "Network namespace {path} not found"
+                ))
+            } else {
+                NisporError::bug(format!(
+                    "Failed to open network namespace {path}: {e}"
+                ))
+            };
+            log::error!("{}", e);
+            e
+        })?;
+        let orig_netns = File::open(CURRENT_THREAD_NETNS_PATH)?;
+        set_netns(&target_netns, &path)?;
+        Ok(Self { orig_netns })
+    }
+}
+
+impl Drop for NetnsGuard {
+    fn drop(&mut self) {
+        if let Err(e) = set_netns(&self.orig_netns, CURRENT_THREAD_NETNS_PATH) {
+            log::error!("Failed to restore original network namespace: {e}");
+        }
+    }
+}
+
+fn set_netns(netns_file: &File, path: &str) -> Result<(), NisporError> {
+    if unsafe { libc::setns(netns_file.as_raw_fd(), libc::CLONE_NEWNET) } != 0 {
+        let io_error = std::io::Error::last_os_error();
+        let e = match io_error.raw_os_error() {
+            Some(libc::EPERM) => NisporError::permission_deny(format!(
+                "Permission denied when switching to network namespace \
+                {path}: {io_error}"
+            )),
+            Some(libc::EINVAL) => NisporError::invalid_argument(format!(
+                "{path} is not a network namespace: {io_error}"
+            )),
+            _ => NisporError::bug(format!(
+                "Failed to switch to network namespace {path}: {io_error}"
+            )),
+        };
+        log::error!("{}", e);
+        return Err(e);
+    }
+    Ok(())
+}
+
+pub(crate) async fn get_netns_list(
+    filter: &NetStateNetnsFilter,
+) -> Result<Vec<NetnsInfo>, NisporError> {
+    let mut netns_list: Vec<NetnsInfo> = Vec::new();
+    let mut inode_to_pos: HashMap<u64, usize> = HashMap::new();
+
+    let mut names = Vec::new();
+    match std::fs::read_dir(NETNS_RUN_DIR) {
+        Ok(entries) => {
+            for entry in entries {
+                names.push(entry?.file_name().to_string_lossy().to_string());
+            }
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => (),
+        Err(e) => return Err(e.into()),
+    }
+    names.sort_unstable();
+    for name in names {
+        let path = format!("{NETNS_RUN_DIR}/{name}");
+        // Stale file without network namespace mounted is ignored
+        let inode = match get_netns_inode(&path) {
+            Some(i) => i,
+            None => continue,
+        };
+        inode_to_pos.insert(inode, netns_list.len());
+        netns_list.push(NetnsInfo {
+            name: Some(name),
+            path,
+            inode,
+            ..Default::default()
+        });
+    }
+
+    if filter.include_process {
+        for entry in std::fs::read_dir("/proc")? {
+            let pid = match entry?.file_name().to_string_lossy().parse::<u32>()
+            {
+                Ok(p) => p,
+                Err(_) => continue,
+            };
+            let path = format!("/proc/{pid}/ns/net");
+            // Process might be gone or not accessible
+            let inode = match get_netns_inode(&path) {
+                Some(i) => i,
+                None => continue,
+            };
+            match inode_to_pos.get(&inode) {
+                Some(pos) => netns_list[*pos].pids.push(pid),
+                None => {
+                    inode_to_pos.insert(inode, netns_list.len());
+                    netns_list.push(NetnsInfo {
+                        path,
+                        inode,
+                        pids: vec![pid],
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+        for netns in netns_list.iter_mut() {
+            netns.pids.sort_unstable();
+        }
+    }
+
+    for netns in netns_list.iter_mut() {
+        netns.nsid = get_nsid(&netns.path).await;
+    }
+    Ok(netns_list)
+}
+
+fn get_netns_inode(path: &str) -> Option<u64> {
+    match std::fs::metadata(path) {
+        Ok(m) => Some(m.ino()),
+        Err(e) => {
+            log::debug!("Failed to stat network namespace {path}: {e}");
+            None
+        }
+    }
+}
+
+// Query the netnsid assigned by current network namespace to the network
+// namespace of specified file.
+async fn get_nsid(path: &str) -> Option<i32> {
+    let fd = match File::open(path) {
+        Ok(f) => f,
+        Err(e) => {
+            log::debug!("Failed to open network namespace {path}: {e}");
+            return None;
+        }
+    };
+    let nlas = vec![DefaultNla::new(
+        NETNSA_FD,
+        (fd.as_raw_fd() as u32).to_ne_bytes().to_vec(),
+    )];
+    let mut payload = vec![0u8; RTGENMSG_LEN];
+    let mut nla_buffer = vec![0u8; nlas.as_slice().buffer_len()];
+    nlas.as_slice().emit(&mut nla_buffer);
+    payload.extend(nla_buffer);
+
+    let reply =
+        match netlink_request(NETLINK_ROUTE, RTM_GETNSID, &payload).await {
+            Ok(Some(r)) if r.msg_type == RTM_NEWNSID => r,
+            Ok(_) => return None,
+            Err(e) => {
+                log::debug!("Failed to query netnsid of {path}: {e}");
+                return None;
+            }
+        };
+    if reply.payload.len() < RTGENMSG_LEN {
+        return None;
+    }
+    for nla in NlasIterator::new(&reply.payload[RTGENMSG_LEN..]) {
+        match nla {
+            Ok(nla) if nla.kind() == NETNSA_NSID => {
+                return match parse_as_i32(nla.value()) {
+                    Ok(NETNSA_NSID_NOT_ASSIGNED) | Err(_) => None,
+                    Ok(nsid) => Some(nsid),
+                };
+            }
+            _ => (),
+        }
+    }
+    None
+}
+
+// Resolve `link_netnsid` of interfaces and interface index of veth peer in
+// other network namespace.
+pub(crate) async fn resolve_cross_netns_links(
+    iface_states: &mut HashMap<String, Iface>,
+    netns_list: &[NetnsInfo],
+) {
+    let mut nsid_to_netns = HashMap::new();
+    for netns in netns_list {
+        if let Some(nsid) = netns.nsid {
+            nsid_to_netns.insert(nsid, netns);
+        }
+    }
+
+    for iface in iface_states.values_mut() {
+        let netns = match iface
+            .link_netnsid
+            .and_then(|nsid| nsid_to_netns.get(&nsid))
+        {
+            Some(n) => n,
+            None => continue,
+        };
+        iface.link_netns = Some(netns.id());
+
+        if iface.iface_type != IfaceType::Veth {
+            continue;
+        }
+        if let Some(veth_info) = iface.veth.as_mut() {
+            veth_info.peer_netns = Some(netns.id());
+            if let Ok(peer_index) = veth_info.peer.parse::<u32>() {
+                if let Some(peer_name) =
+                    get_iface_name_in_netns(&netns.path, peer_index).await
+                {
+                    *veth_info = VethInfo {
+                        peer: peer_name,
+                        peer_netns: Some(netns.id()),
+                    };
+                }
+            }
+        }
+    }
+}
+
+// Switching network namespace requires `CAP_SYS_ADMIN`, return None on
+// failure.
+async fn get_iface_name_in_netns(path: &str, index: u32) -> Option<String> {
+    let mut payload = vec![0u8; IFINFOMSG_LEN];
+    payload[4..8].copy_from_slice(&index.to_ne_bytes());
+
+    let reply = {
+        let _netns_guard =
+            NetnsGuard::enter(&NetnsSelector::Path(path.to_string())).ok()?;
+        netlink_request(NETLINK_ROUTE, RTM_GETLINK, &payload).await
+    };
+    let reply = match reply {
+        Ok(Some(r)) if r.msg_type == RTM_NEWLINK => r,
+        Ok(_) => return None,
+        Err(e) => {
+            log::debug!(
+                "Failed to query interface {index} in network namespace \
+                {path}: {e}"
+            );
+            return None;
+        }
+    };
+    if reply.payload.len() < IFINFOMSG_LEN {
+        return None;
+    }
+    for nla in NlasIterator::new(&reply.payload[IFINFOMSG_LEN..]).flatten() {
+        if nla.kind() == IFLA_IFNAME {
+            return Some(parse_as_string(nla.value()));
+        }
+    }
+    None
+}
@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct NetStateNetnsFilter {
+    /// Include the network namespaces of all processes found in `/proc`.
+    /// Default is false -- only network namespaces created by `ip netns add`
+    /// are included.
+    pub include_process: bool,
+}
@@ -0,0 +1,192 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_utils::nla::NlasIterator;
+use netlink_sys::protocols::NETLINK_ROUTE;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    netlink::{
+        netlink_dump, parse_as_ipv4, parse_as_ipv6, parse_as_u16, parse_as_u32,
+        parse_as_u8,
+    },
+    nexthop_filter::{apply_kernel_nexthop_filter, should_drop_by_filter},
+    route::parse_rtnh_flags,
+    AddressFamily, MultipathRouteFlags, NetStateNexthopFilter, NisporError,
+    RouteProtocol, RouteScope,
+};
+
+const RTM_NEWNEXTHOP: u16 = 104;
+const RTM_GETNEXTHOP: u16 = 106;
+
+// struct nhmsg
+const NHMSG_LEN: usize = 8;
+
+const NHA_ID: u16 = 1;
+const NHA_GROUP: u16 = 2;
+const NHA_GROUP_TYPE: u16 = 3;
+const NHA_BLACKHOLE: u16 = 4;
+const NHA_OIF: u16 = 5;
+const NHA_GATEWAY: u16 = 6;
+const NHA_FDB: u16 = 11;
+
+// struct nexthop_grp
+const NEXTHOP_GRP_LEN: usize = 8;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct Nexthop {
+    pub id: u32,
+    pub address_family: AddressFamily,
+    pub protocol: RouteProtocol,
+    pub scope: RouteScope,
+    pub flags: Vec<MultipathRouteFlags>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iface: Option<String>,
+    pub blackhole: bool,
+    /// Nexthop used by VxLAN FDB entries instead of routes
+    pub fdb: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_type: Option<NexthopGroupType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<Vec<NexthopGroupMember>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct NexthopGroupMember {
+    pub id: u32,
+    pub weight: u16, // The kernel is u8, but ip nexthop show it after + 1.
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum NexthopGroupType {
+    /* Hash-threshold multipath group */
+    Multipath,
+    /* Resilient hashing group */
+    Resilient,
+    Other(u16),
+    Unknown,
+}
+
+const NEXTHOP_GRP_TYPE_MPATH: u16 = 0;
+const NEXTHOP_GRP_TYPE_RES: u16 = 1;
+
+impl From<u16> for NexthopGroupType {
+    fn from(d: u16) -> Self {
+        match d {
+            NEXTHOP_GRP_TYPE_MPATH => Self::Multipath,
+            NEXTHOP_GRP_TYPE_RES => Self::Resilient,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl Default for NexthopGroupType {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+pub(crate) async fn get_nexthops(
+    iface_name2index: &HashMap<String, u32>,
+    filter: &NetStateNexthopFilter,
+) -> Result<Vec<Nexthop>, NisporError> {
+    let mut nexthops = Vec::new();
+
+    let mut ifindex_to_name = HashMap::new();
+    for (name, index) in iface_name2index.iter() {
+        ifindex_to_name.insert(format!("{index}"), name.to_string());
+    }
+
+    let mut payload = vec![0u8; NHMSG_LEN];
+    payload.extend(apply_kernel_nexthop_filter(filter, iface_name2index)?);
+
+    for nl_msg in
+        netlink_dump(NETLINK_ROUTE, RTM_GETNEXTHOP, &payload, true).await?
+    {
+        if nl_msg.msg_type != RTM_NEWNEXTHOP || nl_msg.payload.len() < NHMSG_LEN
+        {
+            continue;
+        }
+        let nexthop = parse_nexthop(&nl_msg.payload, &ifindex_to_name)?;
+        if should_drop_by_filter(&nexthop, filter) {
+            continue;
+        }
+        nexthops.push(nexthop);
+    }
+    Ok(nexthops)
+}
+
+fn parse_nexthop(
+    data: &[u8],
+    ifindex_to_name: &HashMap<String, String>,
+) -> Result<Nexthop, NisporError> {
+    let mut nexthop = Nexthop {
+        address_family: data[0].into(),
+        scope: data[1].into(),
+        protocol: data[2].into(),
+        // All RTNH_F_* flags are stored in the lowest byte of u32 nh_flags
+        flags: parse_rtnh_flags(parse_as_u32(&data[4..8])? as u8),
+        ..Default::default()
+    };
+    for nla in NlasIterator::new(&data[NHMSG_LEN..]) {
+        let nla = nla?;
+        match nla.kind() {
+            NHA_ID => {
+                nexthop.id = parse_as_u32(nla.value())?;
+            }
+            NHA_GROUP => {
+                nexthop.group = Some(parse_nexthop_group(nla.value())?);
+            }
+            NHA_GROUP_TYPE => {
+                nexthop.group_type = Some(parse_as_u16(nla.value())?.into());
+            }
+            NHA_BLACKHOLE => {
+                nexthop.blackhole = true;
+            }
+            NHA_OIF => {
+                let index = parse_as_u32(nla.value())?;
+                nexthop.iface =
+                    Some(match ifindex_to_name.get(&format!("{index}")) {
+                        Some(iface_name) => iface_name.clone(),
+                        None => format!("{index}"),
+                    });
+            }
+            NHA_GATEWAY => {
+                let d = nla.value();
+                nexthop.gateway = Some(match d.len() {
+                    4 => parse_as_ipv4(d)?.to_string(),
+                    16 => parse_as_ipv6(d)?.to_string(),
+                    _ => format!("{d:?}"),
+                });
+            }
+            NHA_FDB => {
+                nexthop.fdb = true;
+            }
+            _ => log::debug!(
+                "Unknown NLA message for nexthop {} {:?}",
+                nla.kind(),
+                nla.value()
+            ),
+        }
+    }
+    Ok(nexthop)
+}
+
+fn parse_nexthop_group(
+    data: &[u8],
+) -> Result<Vec<NexthopGroupMember>, NisporError> {
+    let mut members = Vec::new();
+    for grp in data.chunks_exact(NEXTHOP_GRP_LEN) {
+        members.push(NexthopGroupMember {
+            id: parse_as_u32(&grp[0..4])?,
+            weight: parse_as_u8(&grp[4..5])? as u16 + 1,
+        });
+    }
+    Ok(members)
+}
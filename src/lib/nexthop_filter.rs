@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use netlink_packet_utils::{nla::DefaultNla, Emitable};
+
+use crate::{Nexthop, NisporError};
+
+const NHA_OIF: u16 = 5;
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct NetStateNexthopFilter {
+    /// Returned nexthops will only contain nexthops using specified
+    /// interface.
+    pub iface: Option<String>,
+}
+
+// Return the netlink attributes to append after struct nhmsg
+pub(crate) fn apply_kernel_nexthop_filter(
+    filter: &NetStateNexthopFilter,
+    iface_name2index: &HashMap<String, u32>,
+) -> Result<Vec<u8>, NisporError> {
+    let mut nlas = Vec::new();
+    if let Some(iface) = filter.iface.as_ref() {
+        match iface_name2index.get(iface) {
+            Some(index) => nlas
+                .push(DefaultNla::new(NHA_OIF, index.to_ne_bytes().to_vec())),
+            None => {
+                let e = NisporError::invalid_argument(format!(
+                    "Interface {iface} not found"
+                ));
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+    }
+    let mut buffer = vec![0u8; nlas.as_slice().buffer_len()];
+    nlas.as_slice().emit(&mut buffer);
+    Ok(buffer)
+}
+
+pub(crate) fn should_drop_by_filter(
+    nexthop: &Nexthop,
+    filter: &NetStateNexthopFilter,
+) -> bool {
+    filter.iface.is_some() && filter.iface != nexthop.iface
+}
@@ -14,7 +14,7 @@ use netlink_packet_route::{
     RT_TABLE_MAIN,
 };
 use netlink_packet_utils::{
-    nla::{NlaBuffer, NlasIterator},
+    nla::{Nla as _, NlaBuffer, NlasIterator},
     traits::Parseable,
 };
 use rtnetlink::{new_connection, IpVersion};
@@ -27,12 +27,15 @@ use crate::{
         parse_as_i32, parse_as_ipv4, parse_as_ipv6, parse_as_u16, parse_as_u32,
         AF_INET, AF_INET6,
     },
+    nexthop::get_nexthops,
     route_filter::{apply_kernel_route_filter, should_drop_by_filter},
-    NetStateRouteFilter, NisporError,
+    NetStateNexthopFilter, NetStateRouteFilter, Nexthop, NisporError,
 };
 
 const USER_HZ: u32 = 100;
 
+const RTA_NH_ID: u16 = 30;
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
 #[non_exhaustive]
 pub struct Route {
@@ -125,7 +128,13 @@ pub struct Route {
     pub perf: Option<u8>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub multipath: Option<Vec<MultipathRoute>>,
-    // Missing support of RTA_NH_ID
+
+    // Below are for routes using nexthop object
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nh_id: Option<u32>,
+    /// The nexthop object referred by `nh_id`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nexthop: Option<Nexthop>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
@@ -467,6 +476,32 @@ const RTNH_F_OFFLOAD: u8 = 8; /* offloaded route */
 const RTNH_F_LINKDOWN: u8 = 16; /* carrier-down on nexthop */
 const RTNH_F_UNRESOLVED: u8 = 32; /* The entry is unresolved (ipmr) */
 
+pub(crate) fn parse_rtnh_flags(flags: u8) -> Vec<MultipathRouteFlags> {
+    let mut ret = Vec::new();
+    for (flag, value) in [
+        (RTNH_F_DEAD, MultipathRouteFlags::Dead),
+        (RTNH_F_PERVASIVE, MultipathRouteFlags::Pervasive),
+        (RTNH_F_ONLINK, MultipathRouteFlags::OnLink),
+        (RTNH_F_OFFLOAD, MultipathRouteFlags::Offload),
+        (RTNH_F_LINKDOWN, MultipathRouteFlags::LinkDown),
+        (RTNH_F_UNRESOLVED, MultipathRouteFlags::Unresolved),
+    ] {
+        if flags & flag > 0 {
+            ret.push(value);
+        }
+    }
+    let known_flags = RTNH_F_DEAD
+        | RTNH_F_PERVASIVE
+        | RTNH_F_ONLINK
+        | RTNH_F_OFFLOAD
+        | RTNH_F_LINKDOWN
+        | RTNH_F_UNRESOLVED;
+    if flags & !known_flags > 0 {
+        ret.push(MultipathRouteFlags::Other(flags & !known_flags));
+    }
+    ret
+}
+
 pub(crate) async fn get_routes(
     iface_name2index: &HashMap<String, u32>,
     filter: Option<&NetStateRouteFilter>,
@@ -515,9 +550,55 @@ pub(crate) async fn get_routes(
             routes.push(route);
         }
     }
+
+    if routes.iter().any(|r| r.nh_id.is_some()) {
+        let nexthops =
+            get_nexthops(iface_name2index, &NetStateNexthopFilter::default())
+                .await?;
+        resolve_route_nexthops(&mut routes, &nexthops);
+    }
     Ok(routes)
 }
 
+fn resolve_route_nexthops(routes: &mut [Route], nexthops: &[Nexthop]) {
+    let mut id_to_nexthop = HashMap::new();
+    for nexthop in nexthops {
+        id_to_nexthop.insert(nexthop.id, nexthop);
+    }
+    for rt in routes.iter_mut() {
+        let nexthop = match rt.nh_id.and_then(|id| id_to_nexthop.get(&id)) {
+            Some(n) => *n,
+            None => continue,
+        };
+        // When sysctl `net.ipv4.nexthop_compat_mode` is 0, kernel will not
+        // include gateway, oif or multipath for routes using nexthop object.
+        if let Some(members) = nexthop.group.as_ref() {
+            if rt.multipath.is_none() {
+                let mut next_hops = Vec::new();
+                for member in members {
+                    if let Some(member_nh) = id_to_nexthop.get(&member.id) {
+                        next_hops.push(MultipathRoute {
+                            via: member_nh.gateway.clone().unwrap_or_default(),
+                            iface: member_nh.iface.clone().unwrap_or_default(),
+                            weight: member.weight,
+                            flags: member_nh.flags.clone(),
+                        });
+                    }
+                }
+                rt.multipath = Some(next_hops);
+            }
+        } else {
+            if rt.gateway.is_none() {
+                rt.gateway = nexthop.gateway.clone();
+            }
+            if rt.oif.is_none() {
+                rt.oif = nexthop.iface.clone();
+            }
+        }
+        rt.nexthop = Some(nexthop.clone());
+    }
+}
+
 fn get_route(
     route_msg: RouteMessage,
     ifindex_to_name: &HashMap<String, String>,
@@ -759,6 +840,11 @@ fn get_route(
             Nla::Pref(d) => {
                 rt.perf = Some(d[0]);
             }
+            Nla::Other(d) if d.kind() == RTA_NH_ID => {
+                let mut buffer = vec![0u8; d.value_len()];
+                d.emit_value(&mut buffer);
+                rt.nh_id = Some(parse_as_u32(&buffer)?);
+            }
             _ => log::warn!("Unknown NLA message for route {:?}", nla),
         }
     }
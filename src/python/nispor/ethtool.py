@@ -3,6 +3,7 @@
 
 class NisporEthtool:
     def __init__(self, info):
+        self._info = info
         if "pause" in info:
             self._pause = NisporEthtoolPause(info["pause"])
         else:
@@ -28,6 +29,38 @@ class NisporEthtool:
         else:
             self._link_mode = None
 
+        if "channels" in info:
+            self._channels = NisporEthtoolChannels(info["channels"])
+        else:
+            self._channels = None
+
+        if "eee" in info:
+            self._eee = NisporEthtoolEee(info["eee"])
+        else:
+            self._eee = None
+
+        if "fec" in info:
+            self._fec = NisporEthtoolFec(info["fec"])
+        else:
+            self._fec = None
+
+        if "timestamping" in info:
+            self._timestamping = NisporEthtoolTimestamping(
+                info["timestamping"]
+            )
+        else:
+            self._timestamping = None
+
+        if "link_state" in info:
+            self._link_state = NisporEthtoolLinkState(info["link_state"])
+        else:
+            self._link_state = None
+
+        if "driver" in info:
+            self._driver = NisporEthtoolDriver(info["driver"])
+        else:
+            self._driver = None
+
     @property
     def pause(self):
         return self._pause
@@ -48,6 +81,38 @@ class NisporEthtool:
     def link_mode(self):
         return self._link_mode
 
+    @property
+    def channels(self):
+        return self._channels
+
+    @property
+    def eee(self):
+        return self._eee
+
+    @property
+    def fec(self):
+        return self._fec
+
+    @property
+    def timestamping(self):
+        return self._timestamping
+
+    @property
+    def link_state(self):
+        return self._link_state
+
+    @property
+    def driver(self):
+        return self._driver
+
+    @property
+    def private_flags(self):
+        return self._info.get("private_flags")
+
+    @property
+    def statistics(self):
+        return self._info.get("statistics")
+
 
 class NisporEthtoolPause:
     def __init__(self, info):
@@ -244,3 +309,165 @@ class NisporEthtoolLinkMode:
     @property
     def lanes(self):
         return self._info.get("lanes")
+
+
+class NisporEthtoolChannels:
+    def __init__(self, info):
+        self._info = info
+
+    @property
+    def rx(self):
+        return self._info.get("rx")
+
+    @property
+    def rx_max(self):
+        return self._info.get("rx_max")
+
+    @property
+    def tx(self):
+        return self._info.get("tx")
+
+    @property
+    def tx_max(self):
+        return self._info.get("tx_max")
+
+    @property
+    def other(self):
+        return self._info.get("other")
+
+    @property
+    def other_max(self):
+        return self._info.get("other_max")
+
+    @property
+    def combined(self):
+        return self._info.get("combined")
+
+    @property
+    def combined_max(self):
+        return self._info.get("combined_max")
+
+
+class NisporEthtoolEee:
+    def __init__(self, info):
+        self._info = info
+
+    @property
+    def supported(self):
+        return self._info["supported"]
+
+    @property
+    def advertised(self):
+        return self._info["advertised"]
+
+    @property
+    def peer(self):
+        return self._info.get("peer")
+
+    @property
+    def active(self):
+        return self._info["active"]
+
+    @property
+    def enabled(self):
+        return self._info["enabled"]
+
+    @property
+    def tx_lpi_enabled(self):
+        return self._info["tx_lpi_enabled"]
+
+    @property
+    def tx_lpi_timer(self):
+        return self._info["tx_lpi_timer"]
+
+
+class NisporEthtoolFec:
+    def __init__(self, info):
+        self._info = info
+
+    @property
+    def configured(self):
+        return self._info["configured"]
+
+    @property
+    def auto(self):
+        return self._info["auto"]
+
+    @property
+    def active(self):
+        return self._info.get("active")
+
+
+class NisporEthtoolTimestamping:
+    def __init__(self, info):
+        self._info = info
+
+    @property
+    def capabilities(self):
+        return self._info["capabilities"]
+
+    @property
+    def tx_types(self):
+        return self._info["tx_types"]
+
+    @property
+    def rx_filters(self):
+        return self._info["rx_filters"]
+
+    @property
+    def phc_index(self):
+        return self._info.get("phc_index")
+
+
+class NisporEthtoolLinkState:
+    def __init__(self, info):
+        self._info = info
+
+    @property
+    def link_detected(self):
+        return self._info.get("link_detected")
+
+    @property
+    def sqi(self):
+        return self._info.get("sqi")
+
+    @property
+    def sqi_max(self):
+        return self._info.get("sqi_max")
+
+    @property
+    def ext_state(self):
+        return self._info.get("ext_state")
+
+    @property
+    def ext_substate(self):
+        return self._info.get("ext_substate")
+
+    @property
+    def ext_down_count(self):
+        return self._info.get("ext_down_count")
+
+
+class NisporEthtoolDriver:
+    def __init__(self, info):
+        self._info = info
+
+    @property
+    def driver(self):
+        return self._info["driver"]
+
+    @property
+    def version(self):
+        return self._info.get("version")
+
+    @property
+    def firmware_version(self):
+        return self._info.get("firmware_version")
+
+    @property
+    def bus_info(self):
+        return self._info.get("bus_info")
+
+    @property
+    def expansion_rom_version(self):
+        return self._info.get("expansion_rom_version")
@@ -43,6 +43,10 @@ class NisporIPv4:
     def addresses(self):
         return self._address
 
+    @property
+    def sysctl(self):
+        return self._info.get("sysctl")
+
     def __str__(self):
         return f"{self._info}"
 
@@ -62,5 +66,25 @@ class NisporIPv6:
     def token(self):
         return self._info.get("token")
 
+    @property
+    def addr_gen_mode(self):
+        return self._info.get("addr_gen_mode")
+
+    @property
+    def ra_received(self):
+        return self._info.get("ra_received")
+
+    @property
+    def managed(self):
+        return self._info.get("managed")
+
+    @property
+    def other_config(self):
+        return self._info.get("other_config")
+
+    @property
+    def sysctl(self):
+        return self._info.get("sysctl")
+
     def __str__(self):
         return f"{self._info}"